@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS oauth_signing_key (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    kid TEXT NOT NULL,
+    algorithm TEXT NOT NULL,
+    private_key TEXT NOT NULL,
+    status INT DEFAULT 0,
+    activated_at TIMESTAMP NULL,
+    retired_at TIMESTAMP NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT uq_oauth_signing_key_kid UNIQUE (kid)
+);
+
+CREATE INDEX IF NOT EXISTS idx_oauth_signing_key_status ON oauth_signing_key using hash(status);
@@ -0,0 +1 @@
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS post_logout_redirect_uris _TEXT DEFAULT ARRAY[]::TEXT[];
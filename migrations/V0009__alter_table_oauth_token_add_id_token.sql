@@ -0,0 +1,3 @@
+ALTER TABLE oauth_token ADD COLUMN IF NOT EXISTS id_token TEXT DEFAULT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_oauth_token_id_token ON oauth_token using hash(id_token);
@@ -0,0 +1 @@
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS backchannel_logout_uri TEXT DEFAULT NULL;
@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS oauth_logout_delivery (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    session_id UUID NOT NULL,
+    client_id TEXT NOT NULL,
+    logout_uri TEXT NOT NULL,
+    status INT DEFAULT 0,
+    attempts INT DEFAULT 0,
+    response_status INT NULL,
+    last_error TEXT NULL,
+    delivered_at TIMESTAMP NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_oauth_logout_delivery_session FOREIGN KEY (session_id) REFERENCES oauth_session(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_oauth_logout_delivery_session_id ON oauth_logout_delivery using hash(session_id);
+CREATE INDEX IF NOT EXISTS idx_oauth_logout_delivery_status ON oauth_logout_delivery using hash(status);
@@ -0,0 +1 @@
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS grant_types _TEXT DEFAULT ARRAY['authorization_code', 'refresh_token']::TEXT[];
@@ -0,0 +1 @@
+ALTER TABLE oauth_token ALTER COLUMN refresh_token DROP NOT NULL;
@@ -0,0 +1,7 @@
+ALTER TABLE oauth_token ADD COLUMN IF NOT EXISTS subject_token_id UUID DEFAULT NULL;
+ALTER TABLE oauth_token ADD COLUMN IF NOT EXISTS actor_token_id UUID DEFAULT NULL;
+
+ALTER TABLE oauth_token ADD CONSTRAINT fk_oauth_token_subject_token FOREIGN KEY (subject_token_id) REFERENCES oauth_token(id) ON DELETE SET NULL;
+ALTER TABLE oauth_token ADD CONSTRAINT fk_oauth_token_actor_token FOREIGN KEY (actor_token_id) REFERENCES oauth_token(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_oauth_token_subject_token_id ON oauth_token using hash(subject_token_id);
@@ -0,0 +1,2 @@
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS backchannel_token_delivery_mode TEXT DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS backchannel_client_notification_endpoint TEXT DEFAULT NULL;
@@ -0,0 +1 @@
+ALTER TABLE oauth_token ADD COLUMN IF NOT EXISTS dpop_jkt TEXT DEFAULT NULL;
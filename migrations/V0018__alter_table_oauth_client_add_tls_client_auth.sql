@@ -0,0 +1,6 @@
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS tls_client_auth_subject_dn TEXT DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS tls_client_auth_san_dns TEXT DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS tls_client_auth_san_uri TEXT DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS tls_client_auth_san_ip TEXT DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS tls_client_auth_san_email TEXT DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS tls_client_certificate_thumbprint TEXT DEFAULT NULL;
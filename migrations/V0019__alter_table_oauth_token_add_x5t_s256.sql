@@ -0,0 +1 @@
+ALTER TABLE oauth_token ADD COLUMN IF NOT EXISTS x5t_s256 TEXT DEFAULT NULL;
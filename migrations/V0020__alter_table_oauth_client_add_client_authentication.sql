@@ -0,0 +1,3 @@
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS token_endpoint_auth_method TEXT DEFAULT 'client_secret_post';
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS jwks JSONB DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS jwks_uri TEXT DEFAULT NULL;
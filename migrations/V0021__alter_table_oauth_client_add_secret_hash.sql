@@ -0,0 +1,7 @@
+ALTER TABLE oauth_client ALTER COLUMN secret DROP DEFAULT;
+DROP INDEX IF EXISTS idx_oauth_client_secret;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS secret_hash TEXT DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS previous_secret TEXT DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS previous_secret_hash TEXT DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS previous_secret_expires_at TIMESTAMP DEFAULT NULL;
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS secret_rotated_at TIMESTAMP DEFAULT NULL;
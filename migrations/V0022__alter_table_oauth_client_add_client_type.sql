@@ -0,0 +1 @@
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS client_type TEXT DEFAULT 'confidential';
@@ -0,0 +1,3 @@
+ALTER TABLE oauth_token ADD COLUMN IF NOT EXISTS previous_refresh_token TEXT DEFAULT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_oauth_token_previous_refresh_token ON oauth_token using hash (previous_refresh_token);
@@ -0,0 +1 @@
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS request_uris TEXT[] DEFAULT ARRAY[]::text[];
@@ -0,0 +1 @@
+ALTER TABLE oauth_session ADD COLUMN IF NOT EXISTS response_mode TEXT NULL;
\ No newline at end of file
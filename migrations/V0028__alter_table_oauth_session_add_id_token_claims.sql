@@ -0,0 +1,4 @@
+ALTER TABLE oauth_session ADD COLUMN IF NOT EXISTS nonce TEXT NULL;
+ALTER TABLE oauth_session ADD COLUMN IF NOT EXISTS auth_time TIMESTAMP NULL;
+ALTER TABLE oauth_session ADD COLUMN IF NOT EXISTS acr TEXT NULL;
+ALTER TABLE oauth_session ADD COLUMN IF NOT EXISTS amr TEXT[] NULL;
\ No newline at end of file
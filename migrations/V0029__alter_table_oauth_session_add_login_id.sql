@@ -0,0 +1,3 @@
+ALTER TABLE oauth_session ADD COLUMN IF NOT EXISTS login_id TEXT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_oauth_session_login_id ON oauth_session using hash (login_id);
\ No newline at end of file
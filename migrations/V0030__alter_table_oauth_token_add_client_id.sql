@@ -0,0 +1,5 @@
+ALTER TABLE oauth_token ALTER COLUMN session_id DROP NOT NULL;
+ALTER TABLE oauth_token ADD COLUMN IF NOT EXISTS client_id TEXT NULL;
+ALTER TABLE oauth_token ADD CONSTRAINT fk_oauth_token_client_slug FOREIGN KEY (client_id) REFERENCES oauth_client(slug) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_oauth_token_client_id ON oauth_token using hash (client_id);
\ No newline at end of file
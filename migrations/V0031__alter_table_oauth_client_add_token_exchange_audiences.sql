@@ -0,0 +1 @@
+ALTER TABLE oauth_client ADD COLUMN IF NOT EXISTS token_exchange_audiences TEXT[] DEFAULT ARRAY[]::text[];
\ No newline at end of file
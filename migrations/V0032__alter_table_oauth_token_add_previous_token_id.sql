@@ -0,0 +1,5 @@
+ALTER TABLE oauth_token ADD COLUMN IF NOT EXISTS previous_token_id UUID DEFAULT NULL;
+
+ALTER TABLE oauth_token ADD CONSTRAINT fk_oauth_token_previous_token FOREIGN KEY (previous_token_id) REFERENCES oauth_token(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_oauth_token_previous_token_id ON oauth_token using hash(previous_token_id);
\ No newline at end of file
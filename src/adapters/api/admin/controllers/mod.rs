@@ -0,0 +1,2 @@
+pub mod rotate_signing_keys;
+pub mod rotate_client_secret;
\ No newline at end of file
@@ -0,0 +1,31 @@
+use std::sync::Arc;
+use actix_web::HttpResponse;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::admin::rotate_client_secret::RotateClientSecretUseCase;
+use crate::dto::admin::client_secret::request::RotateClientSecretRequest;
+
+pub struct RotateClientSecretController {
+    repository: Arc<OAuthClientRepository>,
+}
+
+impl ControllerInterface for RotateClientSecretController {
+    type Data = RotateClientSecretRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match RotateClientSecretUseCase::new(
+            self.repository.clone(),
+        ).handle(data).await {
+            Ok(e) => HttpResponse::Ok().append_header(("Cache-Control", "no-store")).json(e.data),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl RotateClientSecretController {
+    pub fn new(repository: Arc<OAuthClientRepository>) -> Self {
+        Self { repository }
+    }
+}
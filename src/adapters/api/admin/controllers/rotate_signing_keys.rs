@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use actix_web::HttpResponse;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::admin::rotate_signing_keys::RotateSigningKeysUseCase;
+
+pub struct RotateSigningKeysController {
+    repository: Arc<OAuthSigningKeyRepository>,
+}
+
+impl ControllerInterface for RotateSigningKeysController {
+    type Data = ();
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match RotateSigningKeysUseCase::new(
+            self.repository.clone(),
+        ).handle(data).await {
+            Ok(e) => HttpResponse::Ok().json(e.data),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl RotateSigningKeysController {
+    pub fn new(repository: Arc<OAuthSigningKeyRepository>) -> Self {
+        Self { repository }
+    }
+}
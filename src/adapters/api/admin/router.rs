@@ -0,0 +1,68 @@
+use actix_web::http::StatusCode;
+use actix_web::{HttpRequest, HttpResponse, Responder, Scope, post, web};
+use crate::adapters::api::admin::controllers::rotate_client_secret::RotateClientSecretController;
+use crate::adapters::api::admin::controllers::rotate_signing_keys::RotateSigningKeysController;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::application::api::controller::ControllerInterface;
+use crate::dto::admin::client_secret::request::RotateClientSecretRequest;
+use crate::utils::api_response::ApiError;
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::hasher::hash_sha256;
+
+pub fn admin_router() -> Scope {
+    web::scope("/admin")
+        .service(rotate_signing_keys_handler)
+        .service(rotate_client_secret_handler)
+}
+
+#[post("/keys/rotate", name = "admin_keys_rotate")]
+async fn rotate_signing_keys_handler(
+    req: HttpRequest,
+    repository: web::Data<OAuthSigningKeyRepository>,
+) -> impl Responder {
+    if let Err(e) = check_admin_key(&req) {
+        return e;
+    }
+
+    RotateSigningKeysController::new(
+        repository.into_inner(),
+    ).handle(()).await
+}
+
+#[post("/clients/{client_id}/secret/rotate", name = "admin_client_secret_rotate")]
+async fn rotate_client_secret_handler(
+    req: HttpRequest,
+    path: web::Path<String>,
+    data: web::Query<RotateClientSecretRequest>,
+    repository: web::Data<OAuthClientRepository>,
+) -> impl Responder {
+    if let Err(e) = check_admin_key(&req) {
+        return e;
+    }
+
+    let mut data = data.into_inner();
+    data.client_id = path.into_inner();
+
+    RotateClientSecretController::new(
+        repository.into_inner(),
+    ).handle(data).await
+}
+
+fn check_admin_key(req: &HttpRequest) -> Result<(), HttpResponse> {
+    const ADMIN_KEY_HEADER: &str = "x-admin-key";
+
+    let Ok(admin_key) = std::env::var("ADMIN_API_KEY") else {
+        return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "Admin API is disabled").with_status(StatusCode::FORBIDDEN).to_response());
+    };
+
+    let Some(header) = req.headers().get(ADMIN_KEY_HEADER).and_then(|e| e.to_str().ok()) else {
+        return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, format!("Missing {} header", ADMIN_KEY_HEADER)).with_status(StatusCode::UNAUTHORIZED).to_response());
+    };
+
+    if admin_key.is_empty() || hash_sha256(header) != hash_sha256(admin_key.as_str()) {
+        return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "Invalid admin key").with_status(StatusCode::UNAUTHORIZED).to_response());
+    }
+
+    Ok(())
+}
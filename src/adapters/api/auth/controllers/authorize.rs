@@ -1,22 +1,16 @@
-use std::sync::Arc;
-use actix_web::http::StatusCode;
 use actix_web::HttpResponse;
-use crate::adapters::spi::cache::redis::RedisCache;
-use crate::adapters::spi::gateways::idp::IdpGateway;
-use crate::adapters::spi::repositories::oauth_consent::OAuthConsentRepository;
-use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
 use crate::application::api::controller::ControllerInterface;
 use crate::application::api::use_case::UseCaseInterface;
 use crate::application::use_cases::auth::authorize::AuthorizeUseCase;
 use crate::application::use_cases::auth::authorize_continue::AuthorizeContinueUseCase;
 use crate::dto::auth::authorize::request::AuthorizeRequest;
-use crate::utils::api_response::{ApiError, ApiErrorResponse, ApiSuccess};
+use crate::dto::auth::authorize::response::AuthorizeResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::html::escape_html;
 
 pub struct AuthorizeController {
-    cache: Arc<RedisCache>,
-    repository: Arc<OAuthSessionRepository>,
-    consent_repository: Arc<OAuthConsentRepository>,
-    idp_gateway: Arc<IdpGateway>
+    dependencies: AuthDependencies,
 }
 
 impl ControllerInterface for AuthorizeController {
@@ -24,42 +18,56 @@ impl ControllerInterface for AuthorizeController {
     type Result = HttpResponse;
 
     async fn handle(&self, data: Self::Data) -> Self::Result {
-        if !data.session_id.is_none()  {
+        if data.session_id.is_some()  {
             return self.format_result(AuthorizeContinueUseCase::new(
-                self.cache.clone(),
-                self.repository.clone(),
-                self.consent_repository.clone(),
-                self.idp_gateway.clone()
+                self.dependencies.cache.clone(),
+                self.dependencies.repository.clone(),
+                self.dependencies.consent_repository.clone(),
+                self.dependencies.signing_key_repository.clone(),
+                self.dependencies.idp_gateway.clone()
             ).handle(data).await);
         }
 
         self.format_result(AuthorizeUseCase::new(
-            self.cache.clone(),
-            self.repository.clone()
+            self.dependencies.cache.clone(),
+            self.dependencies.repository.clone(),
+            self.dependencies.client_repository.clone(),
+            self.dependencies.jwks_gateway.clone(),
+            self.dependencies.request_object_gateway.clone(),
+            self.dependencies.signing_key_repository.clone(),
         ).handle(data).await)
     }
 }
 
 impl AuthorizeController {
-    pub fn new(
-        cache: Arc<RedisCache>,
-        repository: Arc<OAuthSessionRepository>,
-        consent_repository: Arc<OAuthConsentRepository>,
-        idp_gateway: Arc<IdpGateway>
-    ) -> Self {
-        Self {
-            cache,
-            repository,
-            consent_repository,
-            idp_gateway,
-        }
+    pub fn new(dependencies: AuthDependencies) -> Self {
+        Self { dependencies }
     }
-    fn format_result(&self, result: Result<ApiSuccess<String>, ApiError>) -> HttpResponse {
+
+    fn format_result(&self, result: Result<ApiSuccess<AuthorizeResponse>, ApiError>) -> HttpResponse {
         match result {
-            Ok(e) => HttpResponse::SeeOther().append_header(("Location", e.data)).finish(),
-            Err(e) => HttpResponse::build(
-                StatusCode::from_u16(e.status_code).unwrap()
-            ).json(ApiErrorResponse::new(e.error))
+            Ok(e) => match e.data {
+                AuthorizeResponse::Redirect(url) => HttpResponse::SeeOther().append_header(("Location", url)).finish(),
+                AuthorizeResponse::FormPost { action, params } => HttpResponse::Ok()
+                    .content_type("text/html; charset=utf-8")
+                    .append_header(("Cache-Control", "no-store"))
+                    .body(self.render_form_post(action, params)),
+            },
+            Err(e) => e.to_response()
         }
     }
+
+    fn render_form_post(&self, action: String, params: Vec<(String, String)>) -> String {
+        let inputs = params
+            .iter()
+            .map(|(k, v)| format!(r#"<input type="hidden" name="{}" value="{}">"#, escape_html(k), escape_html(v)))
+            .collect::<Vec<String>>()
+            .join("");
+
+        format!(
+            r#"<!DOCTYPE html><html><head><meta charset="utf-8"><title>Submitting</title></head><body onload="document.forms[0].submit()"><form method="post" action="{}">{}<noscript><button type="submit">Continue</button></noscript></form></body></html>"#,
+            escape_html(action.as_str()),
+            inputs
+        )
+    }
 }
\ No newline at end of file
@@ -0,0 +1,35 @@
+use actix_web::HttpResponse;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::bc_authorize::BcAuthorizeUseCase;
+use crate::dto::auth::bc_authorize::request::BcAuthorizeRequest;
+
+pub struct BcAuthorizeController {
+    dependencies: AuthDependencies,
+}
+
+impl ControllerInterface for BcAuthorizeController {
+    type Data = BcAuthorizeRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match BcAuthorizeUseCase::new(
+            self.dependencies.cache.clone(),
+            self.dependencies.repository.clone(),
+            self.dependencies.token_repository.clone(),
+            self.dependencies.client_repository.clone(),
+            self.dependencies.idp_gateway.clone(),
+            self.dependencies.jwks_gateway.clone(),
+        ).handle(data).await {
+            Ok(e) => HttpResponse::Ok().append_header(("Cache-Control", "no-store")).json(e.data),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl BcAuthorizeController {
+    pub fn new(dependencies: AuthDependencies) -> Self {
+        Self { dependencies }
+    }
+}
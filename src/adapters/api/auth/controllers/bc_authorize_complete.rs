@@ -0,0 +1,34 @@
+use actix_web::HttpResponse;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::bc_authorize_complete::BcAuthorizeCompleteUseCase;
+use crate::dto::auth::bc_authorize_complete::request::BcAuthorizeCompleteRequest;
+
+pub struct BcAuthorizeCompleteController {
+    dependencies: AuthDependencies,
+}
+
+impl ControllerInterface for BcAuthorizeCompleteController {
+    type Data = BcAuthorizeCompleteRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match BcAuthorizeCompleteUseCase::new(
+            self.dependencies.cache.clone(),
+            self.dependencies.repository.clone(),
+            self.dependencies.client_repository.clone(),
+            self.dependencies.idp_gateway.clone(),
+            self.dependencies.ciba_gateway.clone(),
+        ).handle(data).await {
+            Ok(_) => HttpResponse::Ok().finish(),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl BcAuthorizeCompleteController {
+    pub fn new(dependencies: AuthDependencies) -> Self {
+        Self { dependencies }
+    }
+}
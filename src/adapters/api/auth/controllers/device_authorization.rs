@@ -0,0 +1,34 @@
+use actix_web::HttpResponse;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::device_authorization::DeviceAuthorizationUseCase;
+use crate::dto::auth::device_authorization::request::DeviceAuthorizationRequest;
+
+pub struct DeviceAuthorizationController {
+    dependencies: AuthDependencies,
+    verification_path: String,
+}
+
+impl ControllerInterface for DeviceAuthorizationController {
+    type Data = DeviceAuthorizationRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match DeviceAuthorizationUseCase::new(
+            self.dependencies.cache.clone(),
+            self.dependencies.client_repository.clone(),
+            self.dependencies.jwks_gateway.clone(),
+            self.verification_path.clone(),
+        ).handle(data).await {
+            Ok(e) => HttpResponse::Ok().append_header(("Cache-Control", "no-store")).json(e.data),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl DeviceAuthorizationController {
+    pub fn new(dependencies: AuthDependencies, verification_path: String) -> Self {
+        Self { dependencies, verification_path }
+    }
+}
@@ -0,0 +1,31 @@
+use actix_web::HttpResponse;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::device_confirm::DeviceConfirmUseCase;
+use crate::dto::auth::device_confirm::request::DeviceConfirmRequest;
+
+pub struct DeviceConfirmController {
+    dependencies: AuthDependencies,
+}
+
+impl ControllerInterface for DeviceConfirmController {
+    type Data = DeviceConfirmRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match DeviceConfirmUseCase::new(
+            self.dependencies.cache.clone(),
+            self.dependencies.repository.clone(),
+        ).handle(data).await {
+            Ok(e) => HttpResponse::Ok().json(e.data),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl DeviceConfirmController {
+    pub fn new(dependencies: AuthDependencies) -> Self {
+        Self { dependencies }
+    }
+}
@@ -0,0 +1,30 @@
+use actix_web::HttpResponse;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::device_verify::DeviceVerifyUseCase;
+use crate::dto::auth::device_verify::request::DeviceVerifyRequest;
+
+pub struct DeviceVerifyController {
+    dependencies: AuthDependencies,
+}
+
+impl ControllerInterface for DeviceVerifyController {
+    type Data = DeviceVerifyRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match DeviceVerifyUseCase::new(
+            self.dependencies.cache.clone(),
+        ).handle(data).await {
+            Ok(e) => HttpResponse::SeeOther().append_header(("Location", e.data)).finish(),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl DeviceVerifyController {
+    pub fn new(dependencies: AuthDependencies) -> Self {
+        Self { dependencies }
+    }
+}
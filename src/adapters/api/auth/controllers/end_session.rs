@@ -0,0 +1,65 @@
+use actix_web::HttpResponse;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::end_session::EndSessionUseCase;
+use crate::dto::auth::end_session::request::EndSessionRequest;
+use crate::dto::auth::end_session::response::EndSessionResponse;
+use crate::utils::html::escape_html;
+
+pub struct EndSessionController {
+    dependencies: AuthDependencies,
+}
+
+impl ControllerInterface for EndSessionController {
+    type Data = EndSessionRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match EndSessionUseCase::new(
+            self.dependencies.cache.clone(),
+            self.dependencies.repository.clone(),
+            self.dependencies.token_repository.clone(),
+            self.dependencies.client_repository.clone(),
+            self.dependencies.delivery_repository.clone(),
+            self.dependencies.signing_key_repository.clone(),
+            self.dependencies.logout_gateway.clone(),
+        ).handle(data).await {
+            Ok(e) if e.data.frontchannel_logout_uris.is_empty() && e.data.redirect_uri.is_some() => HttpResponse::SeeOther()
+                .append_header(("Location", e.data.redirect_uri.unwrap_or_default()))
+                .finish(),
+            Ok(e) => HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .append_header(("Cache-Control", "no-store"))
+                .body(self.render_frontchannel_logout(e.data)),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl EndSessionController {
+    pub fn new(dependencies: AuthDependencies) -> Self {
+        Self { dependencies }
+    }
+
+    fn render_frontchannel_logout(&self, data: EndSessionResponse) -> String {
+        let iframes = data.frontchannel_logout_uris
+            .iter()
+            .map(|e| format!(r#"<iframe src="{}" style="display:none"></iframe>"#, escape_html(e)))
+            .collect::<Vec<String>>()
+            .join("");
+
+        let Some(redirect_uri) = data.redirect_uri.as_deref().map(escape_html) else {
+            return format!(
+                r#"<!DOCTYPE html><html><head><meta charset="utf-8"><title>Signed out</title></head><body>{}<p>You have been signed out.</p></body></html>"#,
+                iframes
+            );
+        };
+
+        format!(
+            r#"<!DOCTYPE html><html><head><meta charset="utf-8"><meta http-equiv="refresh" content="5;url={0}"><title>Signing out</title></head><body onload="window.location.replace(document.getElementById('continue').href)">{1}<a id="continue" href="{0}">Continue</a></body></html>"#,
+            redirect_uri,
+            iframes
+        )
+    }
+}
@@ -0,0 +1,52 @@
+use actix_web::HttpResponse;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::introspect::IntrospectUseCase;
+use crate::application::use_cases::auth::introspect_jwt;
+use crate::application::use_cases::auth::introspect_jwt::IntrospectJwtUseCase;
+use crate::dto::auth::introspect::request::IntrospectRequest;
+
+pub struct IntrospectController {
+    dependencies: AuthDependencies,
+    as_jwt: bool,
+}
+
+impl ControllerInterface for IntrospectController {
+    type Data = IntrospectRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        if self.as_jwt {
+            return match IntrospectJwtUseCase::new(
+                self.dependencies.cache.clone(),
+                self.dependencies.repository.clone(),
+                self.dependencies.token_repository.clone(),
+                self.dependencies.client_repository.clone(),
+                self.dependencies.signing_key_repository.clone(),
+                self.dependencies.jwks_gateway.clone(),
+            ).handle(data).await {
+                Ok(e) => HttpResponse::Ok().content_type(introspect_jwt::CONTENT_TYPE).body(e.data),
+                Err(e) => e.to_response(),
+            };
+        }
+
+        match IntrospectUseCase::new(
+            self.dependencies.cache.clone(),
+            self.dependencies.repository.clone(),
+            self.dependencies.token_repository.clone(),
+            self.dependencies.client_repository.clone(),
+            self.dependencies.signing_key_repository.clone(),
+            self.dependencies.jwks_gateway.clone(),
+        ).handle(data).await {
+            Ok(e) => HttpResponse::Ok().json(e.data),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl IntrospectController {
+    pub fn new(dependencies: AuthDependencies, as_jwt: bool) -> Self {
+        Self { dependencies, as_jwt }
+    }
+}
@@ -3,4 +3,12 @@ pub mod authorize;
 pub mod token;
 pub mod consent_info;
 pub mod consent_confirm;
-pub mod userinfo;
\ No newline at end of file
+pub mod userinfo;
+pub mod revoke;
+pub mod introspect;
+pub mod end_session;
+pub mod device_authorization;
+pub mod device_verify;
+pub mod device_confirm;
+pub mod bc_authorize;
+pub mod bc_authorize_complete;
\ No newline at end of file
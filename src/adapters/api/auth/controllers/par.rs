@@ -1,17 +1,12 @@
-use std::sync::Arc;
-use actix_web::http::StatusCode;
 use actix_web::HttpResponse;
-use crate::adapters::spi::cache::redis::RedisCache;
-use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
 use crate::application::api::controller::ControllerInterface;
 use crate::application::api::use_case::UseCaseInterface;
 use crate::application::use_cases::auth::par::ParUseCase;
 use crate::dto::auth::par::request::ParRequest;
-use crate::utils::api_response::ApiErrorResponse;
 
 pub struct ParController {
-    cache: Arc<RedisCache>,
-    repository: Arc<OAuthClientRepository>
+    dependencies: AuthDependencies,
 }
 
 impl ControllerInterface for ParController {
@@ -20,17 +15,19 @@ impl ControllerInterface for ParController {
 
     async fn handle(&self, data: Self::Data) -> Self::Result {
         match ParUseCase::new(
-            self.cache.clone(),
-            self.repository.clone(),
+            self.dependencies.cache.clone(),
+            self.dependencies.client_repository.clone(),
+            self.dependencies.jwks_gateway.clone(),
+            self.dependencies.request_object_gateway.clone(),
         ).handle(data).await {
             Ok(e) => HttpResponse::Created().json(e.data),
-            Err(e) => HttpResponse::build(StatusCode::from_u16(e.status_code).unwrap()).json(ApiErrorResponse::new(e.error)),
+            Err(e) => e.to_response(),
         }
     }
 }
 
 impl ParController {
-    pub fn new(cache: Arc<RedisCache>, repository: Arc<OAuthClientRepository>) -> Self {
-        Self { cache, repository  }
+    pub fn new(dependencies: AuthDependencies) -> Self {
+        Self { dependencies }
     }
 }
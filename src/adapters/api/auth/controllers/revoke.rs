@@ -0,0 +1,34 @@
+use actix_web::HttpResponse;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::revoke::RevokeUseCase;
+use crate::dto::auth::revoke::request::RevokeRequest;
+
+pub struct RevokeController {
+    dependencies: AuthDependencies,
+}
+
+impl ControllerInterface for RevokeController {
+    type Data = RevokeRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match RevokeUseCase::new(
+            self.dependencies.cache.clone(),
+            self.dependencies.repository.clone(),
+            self.dependencies.token_repository.clone(),
+            self.dependencies.client_repository.clone(),
+            self.dependencies.jwks_gateway.clone(),
+        ).handle(data).await {
+            Ok(_) => HttpResponse::Ok().finish(),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl RevokeController {
+    pub fn new(dependencies: AuthDependencies) -> Self {
+        Self { dependencies }
+    }
+}
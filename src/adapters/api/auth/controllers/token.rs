@@ -1,67 +1,137 @@
-use std::sync::Arc;
-use actix_web::http::StatusCode;
-use actix_web::{web, HttpResponse};
-use crate::adapters::spi::cache::redis::RedisCache;
-use crate::adapters::spi::gateways::idp::IdpGateway;
-use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
-use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
-use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use actix_web::HttpResponse;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
 use crate::application::api::controller::ControllerInterface;
 use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::dpop::DpopUseCase;
 use crate::application::use_cases::auth::token_authorization_code::TokenAuthorizationCodeUseCase;
+use crate::application::use_cases::auth::token_ciba::TokenCibaUseCase;
+use crate::application::use_cases::auth::token_client_credentials::TokenClientCredentialsUseCase;
+use crate::application::use_cases::auth::token_device_code::TokenDeviceCodeUseCase;
+use crate::application::use_cases::auth::token_exchange::TokenExchangeUseCase;
 use crate::application::use_cases::auth::token_refresh::TokenRefreshUseCase;
-use crate::dto::auth::token::request::{TokenRefreshRequest, TokenRequest};
-use crate::utils::api_response::ApiErrorResponse;
+use crate::dto::auth::dpop::request::DpopRequest;
+use crate::dto::auth::token::request::TokenGrantRequest;
+use crate::utils::api_response::{ApiError, ApiErrorResponse};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::dpop::DPOP_NONCE_HEADER;
 
 pub struct TokenController {
-    cache: Arc<RedisCache>,
-    repository: Arc<OAuthSessionRepository>,
-    token_repository: Arc<OAuthTokenRepository>,
-    client_repository: Arc<OAuthClientRepository>,
-    idp_gateway: Arc<IdpGateway>
+    dependencies: AuthDependencies,
+    dpop: DpopRequest,
 }
 
 impl ControllerInterface for TokenController {
-    type Data = web::Either<web::Form<TokenRequest>, web::Form<TokenRefreshRequest>>;
+    type Data = TokenGrantRequest;
     type Result = HttpResponse;
 
-    async fn handle(&self, data: Self::Data) -> Self::Result {
-        match data {
-            web::Either::Left(e) => {
-                match TokenAuthorizationCodeUseCase::new(
-                    self.cache.clone(),
-                    self.repository.clone(),
-                    self.token_repository.clone(),
-                    self.client_repository.clone(),
-                    self.idp_gateway.clone(),
-                ).handle(e.into_inner()).await {
-                    Ok(e) => HttpResponse::Ok().json(e.data),
-                    Err(e) => HttpResponse::build(StatusCode::from_u16(e.status_code).unwrap()).json(ApiErrorResponse::new(e.error)),
+    async fn handle(&self, mut data: Self::Data) -> Self::Result {
+        let dpop_use_case = DpopUseCase::new(self.dependencies.cache.clone());
+
+        let client_id = data.client_id().map(|e| e.to_string());
+
+        let dpop_jkt = match dpop_use_case.handle(DpopRequest { client_id: client_id.clone(), ..self.dpop.clone() }).await {
+            Ok(e) => e.data,
+            Err(e) => {
+                let mut response = HttpResponse::build(e.status());
+                response.append_header(("Cache-Control", "no-store"));
+
+                if e.error == OAuthErrorCode::UseDpopNonce && let Ok(nonce) = dpop_use_case.issue_nonce(client_id.as_deref()).await {
+                    response.append_header((DPOP_NONCE_HEADER, nonce));
                 }
+
+                return response.json(ApiErrorResponse::from(&e));
+            }
+        };
+
+        data.set_dpop_jkt(dpop_jkt.clone());
+
+        let result = match data {
+            TokenGrantRequest::AuthorizationCode(e) => {
+                TokenAuthorizationCodeUseCase::new(
+                    self.dependencies.cache.clone(),
+                    self.dependencies.repository.clone(),
+                    self.dependencies.token_repository.clone(),
+                    self.dependencies.client_repository.clone(),
+                    self.dependencies.signing_key_repository.clone(),
+                    self.dependencies.idp_gateway.clone(),
+                    self.dependencies.jwks_gateway.clone(),
+                ).handle(e).await
             },
-            web::Either::Right(e) => {
-                match TokenRefreshUseCase::new(
-                    self.repository.clone(),
-                    self.token_repository.clone(),
-                    self.client_repository.clone(),
-                    self.idp_gateway.clone(),
-                ).handle(e.into_inner()).await {
-                    Ok(e) => HttpResponse::Ok().json(e.data),
-                    Err(e) => HttpResponse::build(StatusCode::from_u16(e.status_code).unwrap()).json(ApiErrorResponse::new(e.error)),
-                }
+            TokenGrantRequest::RefreshToken(e) => {
+                TokenRefreshUseCase::new(
+                    self.dependencies.cache.clone(),
+                    self.dependencies.repository.clone(),
+                    self.dependencies.token_repository.clone(),
+                    self.dependencies.client_repository.clone(),
+                    self.dependencies.signing_key_repository.clone(),
+                    self.dependencies.idp_gateway.clone(),
+                    self.dependencies.jwks_gateway.clone(),
+                ).handle(e).await
+            },
+            TokenGrantRequest::ClientCredentials(e) => {
+                TokenClientCredentialsUseCase::new(
+                    self.dependencies.cache.clone(),
+                    self.dependencies.token_repository.clone(),
+                    self.dependencies.client_repository.clone(),
+                    self.dependencies.signing_key_repository.clone(),
+                    self.dependencies.jwks_gateway.clone(),
+                ).handle(e).await
+            },
+            TokenGrantRequest::DeviceCode(e) => {
+                TokenDeviceCodeUseCase::new(
+                    self.dependencies.cache.clone(),
+                    self.dependencies.repository.clone(),
+                    self.dependencies.token_repository.clone(),
+                    self.dependencies.client_repository.clone(),
+                    self.dependencies.signing_key_repository.clone(),
+                    self.dependencies.idp_gateway.clone(),
+                    self.dependencies.jwks_gateway.clone(),
+                ).handle(e).await
+            },
+            TokenGrantRequest::TokenExchange(e) => {
+                TokenExchangeUseCase::new(
+                    self.dependencies.cache.clone(),
+                    self.dependencies.repository.clone(),
+                    self.dependencies.token_repository.clone(),
+                    self.dependencies.client_repository.clone(),
+                    self.dependencies.signing_key_repository.clone(),
+                    self.dependencies.jwks_gateway.clone(),
+                ).handle(e).await
+            },
+            TokenGrantRequest::Ciba(e) => {
+                TokenCibaUseCase::new(
+                    self.dependencies.cache.clone(),
+                    self.dependencies.repository.clone(),
+                    self.dependencies.token_repository.clone(),
+                    self.dependencies.client_repository.clone(),
+                    self.dependencies.signing_key_repository.clone(),
+                    self.dependencies.idp_gateway.clone(),
+                    self.dependencies.jwks_gateway.clone(),
+                ).handle(e).await
+            },
+            TokenGrantRequest::Unsupported => {
+                Err(ApiError::oauth(OAuthErrorCode::UnsupportedGrantType, "Unsupported grant type"))
             }
+        };
+
+        match result {
+            Ok(e) => {
+                let mut response = HttpResponse::Ok();
+                response.append_header(("Cache-Control", "no-store"));
+
+                if dpop_jkt.is_some() && DpopUseCase::nonce_required() && let Ok(nonce) = dpop_use_case.issue_nonce(client_id.as_deref()).await {
+                    response.append_header((DPOP_NONCE_HEADER, nonce));
+                }
+
+                response.json(e.data)
+            },
+            Err(e) => e.to_response(),
         }
     }
 }
 
 impl TokenController {
-    pub fn new(
-        cache: Arc<RedisCache>,
-        repository: Arc<OAuthSessionRepository>,
-        token_repository: Arc<OAuthTokenRepository>,
-        client_repository: Arc<OAuthClientRepository>,
-        idp_gateway: Arc<IdpGateway>
-    ) -> Self {
-        Self { cache, repository, token_repository, client_repository, idp_gateway  }
+    pub fn new(dependencies: AuthDependencies, dpop: DpopRequest) -> Self {
+        Self { dependencies, dpop }
     }
 }
\ No newline at end of file
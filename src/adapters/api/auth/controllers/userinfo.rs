@@ -1,22 +1,21 @@
-use std::sync::Arc;
-use actix_web::http::StatusCode;
 use actix_web::HttpResponse;
-use crate::adapters::spi::cache::redis::RedisCache;
-use crate::adapters::spi::gateways::idp::IdpGateway;
-use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
-use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
 use crate::application::api::controller::ControllerInterface;
 use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::dpop::DpopUseCase;
 use crate::application::use_cases::auth::userinfo::UserinfoUseCase;
+use crate::dto::auth::dpop::request::DpopRequest;
 use crate::dto::auth::userinfo::request::UserinfoRequest;
+use crate::utils::mtls::ClientCertificate;
 use crate::utils::api_response::ApiErrorResponse;
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::dpop::DPOP_NONCE_HEADER;
 
 pub struct UserinfoController {
-    cache: Arc<RedisCache>,
-    repository: Arc<OAuthSessionRepository>,
-    token_repository: Arc<OAuthTokenRepository>,
-    idp_gateway: Arc<IdpGateway>,
-    access_token: String
+    dependencies: AuthDependencies,
+    access_token: String,
+    dpop: DpopRequest,
+    client_certificate: Option<ClientCertificate>,
 }
 
 impl ControllerInterface for UserinfoController {
@@ -24,27 +23,42 @@ impl ControllerInterface for UserinfoController {
     type Result = HttpResponse;
 
     async fn handle(&self, data: Self::Data) -> Self::Result {
-        match UserinfoUseCase::new(
-            self.cache.clone(),
-            self.repository.clone(),
-            self.token_repository.clone(),
-            self.idp_gateway.clone(),
-            self.access_token.clone()
-        ).handle(data).await {
+        let use_case = UserinfoUseCase::new(
+            self.dependencies.cache.clone(),
+            self.dependencies.repository.clone(),
+            self.dependencies.token_repository.clone(),
+            self.dependencies.idp_gateway.clone(),
+            self.access_token.clone(),
+            self.dpop.clone(),
+            self.client_certificate.clone(),
+        );
+
+        match use_case.handle(data).await {
             Ok(e) => HttpResponse::Ok().json(e.data),
-            Err(e) => HttpResponse::build(StatusCode::from_u16(e.status_code).unwrap()).json(ApiErrorResponse::new(e.error)),
+            Err(e) if e.error == OAuthErrorCode::UseDpopNonce || e.error == OAuthErrorCode::InvalidDpopProof => {
+                let mut response = HttpResponse::Unauthorized();
+                response.append_header(("Cache-Control", "no-store"));
+                response.append_header(("WWW-Authenticate", format!("DPoP error=\"{}\"", e.error.as_str())));
+
+                if e.error == OAuthErrorCode::UseDpopNonce
+                    && let Ok(nonce) = DpopUseCase::new(self.dependencies.cache.clone()).issue_nonce(use_case.client_id().await.as_deref()).await {
+                    response.append_header((DPOP_NONCE_HEADER, nonce));
+                }
+
+                response.json(ApiErrorResponse::from(&e))
+            },
+            Err(e) => e.to_response(),
         }
     }
 }
 
 impl UserinfoController {
     pub fn new(
-        cache: Arc<RedisCache>,
-        repository: Arc<OAuthSessionRepository>,
-        token_repository: Arc<OAuthTokenRepository>,
-        idp_gateway: Arc<IdpGateway>,
-        access_token: String
+        dependencies: AuthDependencies,
+        access_token: String,
+        dpop: DpopRequest,
+        client_certificate: Option<ClientCertificate>,
     ) -> Self {
-        Self { cache, repository, token_repository, idp_gateway, access_token  }
+        Self { dependencies, access_token, dpop, client_certificate }
     }
 }
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::future::{ready, Ready};
+use std::sync::Arc;
+use actix_web::dev::Payload;
+use actix_web::{web, FromRequest, HttpRequest};
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::ciba::CibaGateway;
+use crate::adapters::spi::gateways::idp::IdpGateway;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::gateways::logout::LogoutGateway;
+use crate::adapters::spi::gateways::request_object::RequestObjectGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_consent::OAuthConsentRepository;
+use crate::adapters::spi::repositories::oauth_logout_delivery::OAuthLogoutDeliveryRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+
+/// Repositories and gateways registered by `add_dependencies`, extracted together by the auth handlers that need several of them.
+pub struct AuthDependencies {
+    pub cache: Arc<RedisCache>,
+    pub repository: Arc<OAuthSessionRepository>,
+    pub token_repository: Arc<OAuthTokenRepository>,
+    pub client_repository: Arc<OAuthClientRepository>,
+    pub consent_repository: Arc<OAuthConsentRepository>,
+    pub delivery_repository: Arc<OAuthLogoutDeliveryRepository>,
+    pub signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    pub idp_gateway: Arc<IdpGateway>,
+    pub jwks_gateway: Arc<JwksGateway>,
+    pub request_object_gateway: Arc<RequestObjectGateway>,
+    pub logout_gateway: Arc<LogoutGateway>,
+    pub ciba_gateway: Arc<CibaGateway>,
+}
+
+impl FromRequest for AuthDependencies {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(Self::from_app_data(req))
+    }
+}
+
+impl AuthDependencies {
+    fn from_app_data(req: &HttpRequest) -> Result<Self, actix_web::Error> {
+        Ok(Self {
+            cache: app_data(req)?,
+            repository: app_data(req)?,
+            token_repository: app_data(req)?,
+            client_repository: app_data(req)?,
+            consent_repository: app_data(req)?,
+            delivery_repository: app_data(req)?,
+            signing_key_repository: app_data(req)?,
+            idp_gateway: app_data(req)?,
+            jwks_gateway: app_data(req)?,
+            request_object_gateway: app_data(req)?,
+            logout_gateway: app_data(req)?,
+            ciba_gateway: app_data(req)?,
+        })
+    }
+}
+
+fn app_data<T: 'static>(req: &HttpRequest) -> Result<Arc<T>, actix_web::Error> {
+    match req.app_data::<web::Data<T>>() {
+        Some(e) => Ok(e.clone().into_inner()),
+        None => Err(actix_web::error::ErrorInternalServerError(format!("{} is not registered", std::any::type_name::<T>()))),
+    }
+}
@@ -1,2 +1,3 @@
 pub mod router;
-pub mod controllers;
\ No newline at end of file
+pub mod controllers;
+pub mod dependencies;
\ No newline at end of file
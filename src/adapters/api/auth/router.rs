@@ -1,23 +1,43 @@
-use actix_web::{Responder, Scope, get, post, web, HttpRequest, HttpResponse};
+use actix_web::http::StatusCode;
+use actix_web::{Responder, Scope, get, post, route, web, HttpRequest};
+use actix_web::http::header;
+use crate::adapters::api::auth::dependencies::AuthDependencies;
 use crate::adapters::api::auth::controllers::authorize::AuthorizeController;
+use crate::adapters::api::auth::controllers::bc_authorize::BcAuthorizeController;
+use crate::adapters::api::auth::controllers::bc_authorize_complete::BcAuthorizeCompleteController;
 use crate::adapters::api::auth::controllers::consent_confirm::ConsentConfirmController;
 use crate::adapters::api::auth::controllers::consent_info::ConsentInfoController;
+use crate::adapters::api::auth::controllers::device_authorization::DeviceAuthorizationController;
+use crate::adapters::api::auth::controllers::device_verify::DeviceVerifyController;
+use crate::adapters::api::auth::controllers::device_confirm::DeviceConfirmController;
+use crate::adapters::api::auth::controllers::end_session::EndSessionController;
+use crate::adapters::api::auth::controllers::introspect::IntrospectController;
 use crate::adapters::api::auth::controllers::par::ParController;
+use crate::adapters::api::auth::controllers::revoke::RevokeController;
 use crate::adapters::api::auth::controllers::token::TokenController;
 use crate::adapters::api::auth::controllers::userinfo::UserinfoController;
-use crate::adapters::spi::cache::redis::RedisCache;
-use crate::adapters::spi::gateways::idp::IdpGateway;
-use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
-use crate::adapters::spi::repositories::oauth_consent::OAuthConsentRepository;
-use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
-use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
 use crate::application::api::controller::ControllerInterface;
+use crate::application::use_cases::auth::introspect_jwt;
 use crate::dto::auth::authorize::request::AuthorizeRequest;
+use crate::dto::auth::bc_authorize::request::BcAuthorizeRequest;
+use crate::dto::auth::bc_authorize_complete::request::BcAuthorizeCompleteRequest;
+use crate::dto::auth::client_authentication::request::ClientAuthenticationContext;
 use crate::dto::auth::consent_confirm::request::ConsentConfirmRequest;
 use crate::dto::auth::consent_info::request::ConsentInfoRequest;
+use crate::dto::auth::device_authorization::request::DeviceAuthorizationRequest;
+use crate::dto::auth::device_verify::request::DeviceVerifyRequest;
+use crate::dto::auth::device_confirm::request::DeviceConfirmRequest;
+use crate::dto::auth::dpop::request::DpopRequest;
+use crate::dto::auth::end_session::request::EndSessionRequest;
+use crate::dto::auth::introspect::request::IntrospectRequest;
 use crate::dto::auth::par::{request::ParRequest};
-use crate::dto::auth::token::request::{TokenRefreshRequest, TokenRequest};
+use crate::dto::auth::revoke::request::RevokeRequest;
+use crate::dto::auth::token::request::TokenGrantRequest;
 use crate::dto::auth::userinfo::request::UserinfoRequest;
+use crate::utils::api_response::ApiError;
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::dpop::DPOP_HEADER;
+use crate::utils::mtls::ClientCertificate;
 
 pub fn auth_router() -> Scope {
     web::scope("/auth")
@@ -26,103 +46,204 @@ pub fn auth_router() -> Scope {
         .service(token_handler)
         .service(consent_info_handler)
         .service(consent_confirm_handler)
+        .service(device_authorization_handler)
+        .service(device_verify_handler)
+        .service(device_confirm_handler)
         .service(userinfo_handler)
+        .service(revoke_handler)
+        .service(introspect_handler)
+        .service(end_session_handler)
+        .service(bc_authorize_handler)
+        .service(bc_authorize_complete_handler)
 }
 
-#[post("/par")]
+#[post("/par", name = "auth_par")]
 async fn par_handler(
+    req: HttpRequest,
     data: web::Form<ParRequest>,
-    cache: web::Data<RedisCache>,
-    repository: web::Data<OAuthClientRepository>,
+    dependencies: AuthDependencies,
 ) -> impl Responder {
-    ParController::new(
-        cache.into_inner(),
-        repository.into_inner(),
-    ).handle(data.into_inner()).await
+    let mut data = data.into_inner();
+    data.set_client_context(client_context(&req));
+
+    ParController::new(dependencies).handle(data).await
 }
 
-#[get("/authorize")]
+#[get("/authorize", name = "auth_authorize")]
 async fn authorize_handler(
     data: web::Query<AuthorizeRequest>,
-    cache: web::Data<RedisCache>,
-    repository: web::Data<OAuthSessionRepository>,
-    consent_repository: web::Data<OAuthConsentRepository>,
-    idp_gateway: web::Data<IdpGateway>,
+    dependencies: AuthDependencies,
 ) -> impl Responder {
-    AuthorizeController::new(
-        cache.into_inner(),
-        repository.into_inner(),
-        consent_repository.into_inner(),
-        idp_gateway.into_inner(),
-    ).handle(data.into_inner()).await
+    AuthorizeController::new(dependencies).handle(data.into_inner()).await
 }
 
-#[post("/token")]
+#[post("/token", name = "auth_token")]
 async fn token_handler(
-    data: web::Either<web::Form<TokenRequest>, web::Form<TokenRefreshRequest>>,
-    cache: web::Data<RedisCache>,
-    repository: web::Data<OAuthSessionRepository>,
-    token_repository: web::Data<OAuthTokenRepository>,
-    client_repository: web::Data<OAuthClientRepository>,
-    idp_gateway: web::Data<IdpGateway>,
+    req: HttpRequest,
+    data: web::Form<TokenGrantRequest>,
+    dependencies: AuthDependencies,
 ) -> impl Responder {
-    TokenController::new(
-        cache.into_inner(),
-        repository.into_inner(),
-        token_repository.into_inner(),
-        client_repository.into_inner(),
-        idp_gateway.into_inner(),
-    ).handle(data).await
+    let mut data = data.into_inner();
+    data.set_client_context(client_context(&req));
+
+    TokenController::new(dependencies, dpop_request(&req, None)).handle(data).await
 }
 
 #[get("/consent/info")]
 async fn consent_info_handler(
     data: web::Query<ConsentInfoRequest>,
-    repository: web::Data<OAuthClientRepository>,
-    session_repository: web::Data<OAuthSessionRepository>,
+    dependencies: AuthDependencies,
 ) -> impl Responder {
-    ConsentInfoController::new(
-        repository.into_inner(),
-        session_repository.into_inner(),
-    ).handle(data.into_inner()).await
+    ConsentInfoController::new(dependencies).handle(data.into_inner()).await
 }
 
 #[post("/consent/confirm")]
 async fn consent_confirm_handler(
     data: web::Json<ConsentConfirmRequest>,
-    repository: web::Data<OAuthConsentRepository>,
-    client_repository: web::Data<OAuthClientRepository>,
-    session_repository: web::Data<OAuthSessionRepository>,
+    dependencies: AuthDependencies,
 ) -> impl Responder {
-    ConsentConfirmController::new(
-        repository.into_inner(),
-        session_repository.into_inner(),
-        client_repository.into_inner()
-    ).handle(data.into_inner()).await
+    ConsentConfirmController::new(dependencies).handle(data.into_inner()).await
+}
+
+#[post("/device_authorization", name = "auth_device_authorization")]
+async fn device_authorization_handler(
+    req: HttpRequest,
+    data: web::Form<DeviceAuthorizationRequest>,
+    dependencies: AuthDependencies,
+) -> impl Responder {
+    let Ok(verification_url) = req.url_for_static("auth_device") else {
+        return ApiError::new(String::from("Route auth_device is not registered"), StatusCode::INTERNAL_SERVER_ERROR).to_response();
+    };
+
+    let mut data = data.into_inner();
+    data.set_client_context(client_context(&req));
+
+    DeviceAuthorizationController::new(dependencies, verification_url.path().to_string()).handle(data).await
+}
+
+#[get("/device", name = "auth_device")]
+async fn device_verify_handler(
+    data: web::Query<DeviceVerifyRequest>,
+    dependencies: AuthDependencies,
+) -> impl Responder {
+    DeviceVerifyController::new(dependencies).handle(data.into_inner()).await
+}
+
+#[post("/device/confirm")]
+async fn device_confirm_handler(
+    data: web::Json<DeviceConfirmRequest>,
+    dependencies: AuthDependencies,
+) -> impl Responder {
+    DeviceConfirmController::new(dependencies).handle(data.into_inner()).await
 }
 
-#[post("/userinfo")]
+#[post("/userinfo", name = "auth_userinfo")]
 async fn userinfo_handler(
     req: HttpRequest,
     data: web::Json<UserinfoRequest>,
-    repository: web::Data<OAuthSessionRepository>,
-    token_repository: web::Data<OAuthTokenRepository>,
-    idp_gateway: web::Data<IdpGateway>,
-    cache: web::Data<RedisCache>,
+    dependencies: AuthDependencies,
 ) -> impl Responder {
     const TOKEN_HEADER: &str = "x-access-token";
     
-    if req.headers().get(TOKEN_HEADER).is_none() {
-        return HttpResponse::BadRequest().body(format!("Missing {} header", TOKEN_HEADER));
-    }
+    let Some(header) = req.headers().get(TOKEN_HEADER) else {
+        return ApiError::oauth(OAuthErrorCode::InvalidRequest, format!("Missing {} header", TOKEN_HEADER)).to_response();
+    };
     
-    let header = req.headers().get(TOKEN_HEADER).unwrap().to_str().ok().unwrap();
+    let Ok(header) = header.to_str() else {
+        return ApiError::oauth(OAuthErrorCode::InvalidRequest, format!("Invalid {} header", TOKEN_HEADER)).to_response();
+    };
     
     UserinfoController::new(
-        cache.into_inner(),
-        repository.into_inner(),
-        token_repository.into_inner(),
-        idp_gateway.into_inner(),
-        header.to_string()
+        dependencies,
+        header.to_string(),
+        dpop_request(&req, Some(header.to_string())),
+        client_certificate(&req),
     ).handle(data.into_inner()).await
+}
+
+#[post("/revoke", name = "auth_revoke")]
+async fn revoke_handler(
+    req: HttpRequest,
+    data: web::Form<RevokeRequest>,
+    dependencies: AuthDependencies,
+) -> impl Responder {
+    let mut data = data.into_inner();
+    data.set_client_context(client_context(&req));
+
+    RevokeController::new(dependencies).handle(data).await
+}
+
+#[post("/introspect", name = "auth_introspect")]
+async fn introspect_handler(
+    req: HttpRequest,
+    data: web::Form<IntrospectRequest>,
+    dependencies: AuthDependencies,
+) -> impl Responder {
+    let as_jwt = req.headers()
+        .get(header::ACCEPT)
+        .and_then(|e| e.to_str().ok())
+        .is_some_and(|e| e.split(',').any(|e| e.trim().starts_with(introspect_jwt::CONTENT_TYPE)));
+
+    let mut data = data.into_inner();
+    data.set_client_context(client_context(&req));
+
+    IntrospectController::new(dependencies, as_jwt).handle(data).await
+}
+
+#[route("/end_session", method = "GET", method = "POST", name = "auth_end_session")]
+async fn end_session_handler(
+    data: web::Either<web::Form<EndSessionRequest>, web::Query<EndSessionRequest>>,
+    dependencies: AuthDependencies,
+) -> impl Responder {
+    let data = match data {
+        web::Either::Left(e) => e.into_inner(),
+        web::Either::Right(e) => e.into_inner(),
+    };
+
+    EndSessionController::new(dependencies).handle(data).await
+}
+
+#[post("/bc-authorize", name = "auth_bc_authorize")]
+async fn bc_authorize_handler(
+    req: HttpRequest,
+    data: web::Form<BcAuthorizeRequest>,
+    dependencies: AuthDependencies,
+) -> impl Responder {
+    let mut data = data.into_inner();
+    data.set_client_context(client_context(&req));
+
+    BcAuthorizeController::new(dependencies).handle(data).await
+}
+
+#[post("/bc-authorize/complete", name = "auth_bc_authorize_complete")]
+async fn bc_authorize_complete_handler(
+    data: web::Json<BcAuthorizeCompleteRequest>,
+    dependencies: AuthDependencies,
+) -> impl Responder {
+    BcAuthorizeCompleteController::new(dependencies).handle(data.into_inner()).await
+}
+
+fn dpop_request(req: &HttpRequest, access_token: Option<String>) -> DpopRequest {
+    DpopRequest {
+        proof: req.headers().get(DPOP_HEADER).and_then(|e| e.to_str().ok()).map(|e| e.to_string()),
+        method: req.method().to_string(),
+        path: req.path().to_string(),
+        access_token,
+        client_id: None,
+    }
+}
+
+fn client_certificate(req: &HttpRequest) -> Option<ClientCertificate> {
+    req.conn_data::<ClientCertificate>().cloned()
+}
+
+fn client_context(req: &HttpRequest) -> ClientAuthenticationContext {
+    ClientAuthenticationContext {
+        authorization: req.headers().get(header::AUTHORIZATION).and_then(|e| e.to_str().ok()).map(|e| e.to_string()),
+        client_certificate: client_certificate(req),
+        endpoint_paths: [Some(req.path().to_string()), req.url_for_static("auth_token").ok().map(|e| e.path().to_string())]
+            .into_iter()
+            .flatten()
+            .collect(),
+    }
 }
\ No newline at end of file
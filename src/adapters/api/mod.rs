@@ -1,2 +1,5 @@
 pub mod health;
-pub mod auth;
\ No newline at end of file
+pub mod auth;
+pub mod well_known;
+pub mod admin;
+pub mod registration;
\ No newline at end of file
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use actix_web::HttpResponse;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::registration::delete_client::DeleteClientUseCase;
+use crate::dto::registration::request::ClientConfigurationRequest;
+
+pub struct DeleteClientController {
+    repository: Arc<OAuthClientRepository>,
+    session_repository: Arc<OAuthSessionRepository>,
+    token_repository: Arc<OAuthTokenRepository>,
+}
+
+impl ControllerInterface for DeleteClientController {
+    type Data = ClientConfigurationRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match DeleteClientUseCase::new(
+            self.repository.clone(),
+            self.session_repository.clone(),
+            self.token_repository.clone(),
+        ).handle(data).await {
+            Ok(_) => HttpResponse::NoContent().finish(),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl DeleteClientController {
+    pub fn new(repository: Arc<OAuthClientRepository>, session_repository: Arc<OAuthSessionRepository>, token_repository: Arc<OAuthTokenRepository>) -> Self {
+        Self { repository, session_repository, token_repository }
+    }
+}
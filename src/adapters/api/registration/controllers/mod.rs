@@ -0,0 +1,4 @@
+pub mod register_client;
+pub mod read_client;
+pub mod update_client;
+pub mod delete_client;
\ No newline at end of file
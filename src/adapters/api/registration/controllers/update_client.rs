@@ -0,0 +1,33 @@
+use std::sync::Arc;
+use actix_web::HttpResponse;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::registration::update_client::UpdateClientUseCase;
+use crate::dto::registration::request::ClientUpdateRequest;
+
+pub struct UpdateClientController {
+    repository: Arc<OAuthClientRepository>,
+    registration_path: String,
+}
+
+impl ControllerInterface for UpdateClientController {
+    type Data = ClientUpdateRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match UpdateClientUseCase::new(
+            self.repository.clone(),
+            self.registration_path.clone(),
+        ).handle(data).await {
+            Ok(e) => HttpResponse::Ok().append_header(("Cache-Control", "no-store")).json(e.data),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl UpdateClientController {
+    pub fn new(repository: Arc<OAuthClientRepository>, registration_path: String) -> Self {
+        Self { repository, registration_path }
+    }
+}
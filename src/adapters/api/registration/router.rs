@@ -0,0 +1,116 @@
+use actix_web::http::StatusCode;
+use actix_web::{HttpRequest, HttpResponse, Responder, Scope, delete, get, post, put, web};
+use actix_web::http::header;
+use crate::adapters::api::registration::controllers::delete_client::DeleteClientController;
+use crate::adapters::api::registration::controllers::read_client::ReadClientController;
+use crate::adapters::api::registration::controllers::register_client::RegisterClientController;
+use crate::adapters::api::registration::controllers::update_client::UpdateClientController;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::controller::ControllerInterface;
+use crate::dto::registration::request::{ClientConfigurationRequest, ClientRegistrationRequest, ClientUpdateRequest};
+use crate::utils::api_response::ApiError;
+
+pub fn registration_router() -> Scope {
+    web::scope("/register")
+        .service(register_client_handler)
+        .service(read_client_handler)
+        .service(update_client_handler)
+        .service(delete_client_handler)
+}
+
+#[post("", name = "registration_register")]
+async fn register_client_handler(
+    req: HttpRequest,
+    data: web::Json<ClientRegistrationRequest>,
+    repository: web::Data<OAuthClientRepository>,
+) -> impl Responder {
+    let registration_path = match registration_path(&req) {
+        Ok(e) => e,
+        Err(e) => return e,
+    };
+
+    let mut data = data.into_inner();
+    data.initial_access_token = bearer_token(&req);
+
+    RegisterClientController::new(
+        repository.into_inner(),
+        registration_path,
+    ).handle(data).await
+}
+
+#[get("/{client_id}")]
+async fn read_client_handler(
+    req: HttpRequest,
+    path: web::Path<String>,
+    repository: web::Data<OAuthClientRepository>,
+) -> impl Responder {
+    let registration_path = match registration_path(&req) {
+        Ok(e) => e,
+        Err(e) => return e,
+    };
+
+    ReadClientController::new(
+        repository.into_inner(),
+        registration_path,
+    ).handle(client_configuration(&req, path.into_inner())).await
+}
+
+#[put("/{client_id}")]
+async fn update_client_handler(
+    req: HttpRequest,
+    path: web::Path<String>,
+    data: web::Json<ClientUpdateRequest>,
+    repository: web::Data<OAuthClientRepository>,
+) -> impl Responder {
+    let registration_path = match registration_path(&req) {
+        Ok(e) => e,
+        Err(e) => return e,
+    };
+
+    let mut data = data.into_inner();
+    data.configuration = client_configuration(&req, path.into_inner());
+
+    UpdateClientController::new(
+        repository.into_inner(),
+        registration_path,
+    ).handle(data).await
+}
+
+#[delete("/{client_id}")]
+async fn delete_client_handler(
+    req: HttpRequest,
+    path: web::Path<String>,
+    repository: web::Data<OAuthClientRepository>,
+    session_repository: web::Data<OAuthSessionRepository>,
+    token_repository: web::Data<OAuthTokenRepository>,
+) -> impl Responder {
+    DeleteClientController::new(
+        repository.into_inner(),
+        session_repository.into_inner(),
+        token_repository.into_inner(),
+    ).handle(client_configuration(&req, path.into_inner())).await
+}
+
+fn client_configuration(req: &HttpRequest, client_id: String) -> ClientConfigurationRequest {
+    ClientConfigurationRequest {
+        client_id,
+        registration_access_token: bearer_token(req),
+    }
+}
+
+fn bearer_token(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|e| e.to_str().ok())
+        .and_then(|e| e.strip_prefix("Bearer "))
+        .map(|e| e.to_string())
+}
+
+fn registration_path(req: &HttpRequest) -> Result<String, HttpResponse> {
+    match req.url_for_static("registration_register") {
+        Ok(url) => Ok(url.path().to_string()),
+        Err(_) => Err(ApiError::new(String::from("Route registration_register is not registered"), StatusCode::INTERNAL_SERVER_ERROR).to_response()),
+    }
+}
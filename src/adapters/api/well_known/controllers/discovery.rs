@@ -0,0 +1,25 @@
+use actix_web::HttpResponse;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::well_known::discovery::DiscoveryUseCase;
+use crate::dto::well_known::discovery::request::DiscoveryRequest;
+
+pub struct DiscoveryController;
+
+impl ControllerInterface for DiscoveryController {
+    type Data = DiscoveryRequest;
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match DiscoveryUseCase::new().handle(data).await {
+            Ok(e) => HttpResponse::Ok().json(e.data),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl DiscoveryController {
+    pub fn new() -> Self {
+        Self
+    }
+}
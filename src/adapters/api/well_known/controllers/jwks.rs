@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use actix_web::HttpResponse;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::application::api::controller::ControllerInterface;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::well_known::jwks::JwksUseCase;
+
+pub struct JwksController {
+    repository: Arc<OAuthSigningKeyRepository>,
+}
+
+impl ControllerInterface for JwksController {
+    type Data = ();
+    type Result = HttpResponse;
+
+    async fn handle(&self, data: Self::Data) -> Self::Result {
+        match JwksUseCase::new(
+            self.repository.clone(),
+        ).handle(data).await {
+            Ok(e) => HttpResponse::Ok().json(e.data),
+            Err(e) => e.to_response(),
+        }
+    }
+}
+
+impl JwksController {
+    pub fn new(repository: Arc<OAuthSigningKeyRepository>) -> Self {
+        Self { repository }
+    }
+}
@@ -0,0 +1,56 @@
+use actix_web::http::StatusCode;
+use actix_web::{HttpRequest, Responder, Scope, get, web};
+use crate::adapters::api::well_known::controllers::discovery::DiscoveryController;
+use crate::adapters::api::well_known::controllers::jwks::JwksController;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::application::api::controller::ControllerInterface;
+use crate::dto::well_known::discovery::request::DiscoveryRequest;
+use crate::utils::api_response::ApiError;
+
+pub fn well_known_router() -> Scope {
+    web::scope("/.well-known")
+        .service(openid_configuration_handler)
+        .service(jwks_handler)
+}
+
+#[get("/openid-configuration")]
+async fn openid_configuration_handler(req: HttpRequest) -> impl Responder {
+    let data = match build_discovery_request(&req) {
+        Ok(e) => e,
+        Err(e) => return ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR).to_response(),
+    };
+
+    DiscoveryController::new().handle(data).await
+}
+
+#[get("/jwks.json", name = "well_known_jwks")]
+async fn jwks_handler(
+    repository: web::Data<OAuthSigningKeyRepository>,
+) -> impl Responder {
+    JwksController::new(
+        repository.into_inner(),
+    ).handle(()).await
+}
+
+fn build_discovery_request(req: &HttpRequest) -> Result<DiscoveryRequest, String> {
+    Ok(DiscoveryRequest {
+        par_path: route_path(req, "auth_par")?,
+        authorize_path: route_path(req, "auth_authorize")?,
+        token_path: route_path(req, "auth_token")?,
+        userinfo_path: route_path(req, "auth_userinfo")?,
+        revocation_path: route_path(req, "auth_revoke")?,
+        introspection_path: route_path(req, "auth_introspect")?,
+        end_session_path: route_path(req, "auth_end_session")?,
+        device_authorization_path: route_path(req, "auth_device_authorization")?,
+        backchannel_authentication_path: route_path(req, "auth_bc_authorize")?,
+        registration_path: route_path(req, "registration_register")?,
+        jwks_path: route_path(req, "well_known_jwks")?,
+    })
+}
+
+fn route_path(req: &HttpRequest, name: &str) -> Result<String, String> {
+    match req.url_for_static(name) {
+        Ok(url) => Ok(url.path().to_string()),
+        Err(_) => Err(format!("Route {} is not registered", name)),
+    }
+}
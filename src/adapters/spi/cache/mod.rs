@@ -1,2 +1,3 @@
 pub mod redis;
+#[allow(clippy::module_inception)]
 pub mod cache;
\ No newline at end of file
@@ -1,2 +1,3 @@
 pub mod postgres_db;
+#[allow(clippy::module_inception)]
 pub mod db;
\ No newline at end of file
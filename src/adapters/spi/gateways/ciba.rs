@@ -0,0 +1,38 @@
+use std::time::Duration;
+use crate::utils::outbound::{allow_private_networks, outbound_client, validate_outbound_uri};
+
+pub struct CibaGateway {
+    client: reqwest::Client,
+    allow_private: bool,
+}
+
+impl CibaGateway {
+    pub fn new() -> Self {
+        let timeout = std::env::var("CIBA_NOTIFICATION_TIMEOUT")
+            .ok()
+            .and_then(|e| e.parse::<u64>().ok())
+            .unwrap_or(5);
+
+        let allow_private = allow_private_networks();
+
+        Self {
+            client: outbound_client(Duration::from_secs(timeout), allow_private).expect("Cannot create CIBA HTTP client"),
+            allow_private,
+        }
+    }
+
+    pub async fn post_ping_notification_v1(&self, uri: String, client_notification_token: String, auth_req_id: String) -> Result<u16, String> {
+        let url = validate_outbound_uri(uri.as_str(), self.allow_private)?;
+
+        let resp = match self.client.post(url)
+            .bearer_auth(client_notification_token)
+            .json(&serde_json::json!({ "auth_req_id": auth_req_id }))
+            .send()
+            .await {
+            Ok(resp) => resp,
+            Err(e) => return Err(format!("Failed to send ping notification: {}", e))
+        };
+
+        Ok(resp.status().as_u16())
+    }
+}
@@ -1,6 +1,6 @@
 use actix_web::http::StatusCode;
 use serde::Serialize;
-use crate::domain::idp::{IdPIdTokenRequest, IdPIdTokenResponse, IdpUser, IdpVerifyCredentialRequest, IdpVerifyCredentialResponse};
+use crate::domain::idp::{IdPIdTokenRequest, IdPIdTokenResponse, IdpBackchannelAuthenticationRequest, IdpUser, IdpVerifyCredentialRequest, IdpVerifyCredentialResponse};
 use crate::utils::api_response::ApiError;
 
 pub struct IdpGateway {
@@ -20,7 +20,7 @@ impl IdpGateway {
 
         client.post(format!("{}{}", self.base_url, url))
             .json(&serde_json::json!(body))
-            .header("X-api-key", std::env::var("IDP_API_KEY").unwrap_or(String::new()))
+            .header("X-api-key", std::env::var("IDP_API_KEY").unwrap_or_default())
             .send()
             .await
     }
@@ -29,7 +29,7 @@ impl IdpGateway {
         let client = reqwest::Client::new();
 
         client.get(format!("{}{}", self.base_url, url))
-            .header("X-api-key", std::env::var("IDP_API_KEY").unwrap_or(String::new()))
+            .header("X-api-key", std::env::var("IDP_API_KEY").unwrap_or_default())
             .send()
             .await
     }
@@ -48,7 +48,7 @@ impl IdpGateway {
         Ok(result.id_token)
     }
 
-    pub async fn verify_auth_token_v1(&self, body: IdpVerifyCredentialRequest) -> Result<bool, ApiError> {
+    pub async fn verify_auth_token_v1(&self, body: IdpVerifyCredentialRequest) -> Result<IdpVerifyCredentialResponse, ApiError> {
         let resp = match self.post("/api/v1/user/verify-credential".to_string(), body).await {
             Ok(resp) => resp,
             Err(e) => return Err(ApiError::new(format!("Failed to send request to IDP: {}", e), StatusCode::INTERNAL_SERVER_ERROR))
@@ -59,7 +59,20 @@ impl IdpGateway {
             Err(e) => return Err(ApiError::new(format!("Failed to parse response from IDP: {}", e), StatusCode::INTERNAL_SERVER_ERROR))
         };
 
-        Ok(result.verified)
+        Ok(result)
+    }
+
+    pub async fn request_backchannel_authentication_v1(&self, body: IdpBackchannelAuthenticationRequest) -> Result<(), ApiError> {
+        let resp = match self.post("/api/v1/user/backchannel-authentication".to_string(), body).await {
+            Ok(resp) => resp,
+            Err(e) => return Err(ApiError::new(format!("Failed to send request to IDP: {}", e), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        if !resp.status().is_success() {
+            return Err(ApiError::new(format!("IDP rejected the backchannel authentication request with status {}", resp.status()), StatusCode::BAD_GATEWAY));
+        }
+
+        Ok(())
     }
 
     pub async fn get_user_by_id_v1(&self, id: uuid::Uuid) -> Result<IdpUser, ApiError> {
@@ -0,0 +1,46 @@
+use std::time::Duration;
+use jsonwebtoken::jwk::JwkSet;
+use crate::utils::outbound::{allow_private_networks, outbound_client, read_limited, validate_outbound_uri};
+
+const CLIENT_JWKS_MAX_BYTES: usize = 64 * 1024;
+
+pub struct JwksGateway {
+    client: reqwest::Client,
+    allow_private: bool,
+}
+
+impl JwksGateway {
+    pub fn new() -> Self {
+        let timeout = std::env::var("CLIENT_JWKS_TIMEOUT")
+            .ok()
+            .and_then(|e| e.parse::<u64>().ok())
+            .unwrap_or(5);
+
+        let allow_private = allow_private_networks();
+
+        Self {
+            client: outbound_client(Duration::from_secs(timeout), allow_private).expect("Cannot create JWKS HTTP client"),
+            allow_private,
+        }
+    }
+
+    pub async fn get_jwks_v1(&self, uri: String) -> Result<JwkSet, String> {
+        let url = validate_outbound_uri(uri.as_str(), self.allow_private)?;
+
+        let resp = match self.client.get(url)
+            .header("Accept", "application/json")
+            .send()
+            .await {
+            Ok(resp) => resp,
+            Err(e) => return Err(format!("Failed to fetch client JWKS: {}", e))
+        };
+
+        if !resp.status().is_success() {
+            return Err(format!("Failed to fetch client JWKS: {}", resp.status()));
+        }
+
+        let body = read_limited(resp, CLIENT_JWKS_MAX_BYTES).await.map_err(|e| format!("Failed to fetch client JWKS: {}", e))?;
+
+        serde_json::from_slice::<JwkSet>(&body).map_err(|e| format!("Invalid client JWKS: {}", e))
+    }
+}
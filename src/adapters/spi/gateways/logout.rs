@@ -0,0 +1,125 @@
+use std::time::Duration;
+use crate::utils::outbound::{allow_private_networks, outbound_client, validate_outbound_uri};
+
+pub struct LogoutGateway {
+    client: reqwest::Client,
+    allow_private: bool,
+}
+
+impl LogoutGateway {
+    pub fn new() -> Self {
+        let timeout = std::env::var("BACKCHANNEL_LOGOUT_TIMEOUT")
+            .ok()
+            .and_then(|e| e.parse::<u64>().ok())
+            .unwrap_or(5);
+
+        Self::build(Duration::from_secs(timeout), allow_private_networks())
+    }
+
+    fn build(timeout: Duration, allow_private: bool) -> Self {
+        Self {
+            client: outbound_client(timeout, allow_private).expect("Cannot create logout HTTP client"),
+            allow_private,
+        }
+    }
+
+    pub async fn post_backchannel_logout_v1(&self, uri: String, logout_token: String) -> Result<u16, String> {
+        let url = validate_outbound_uri(uri.as_str(), self.allow_private)?;
+
+        let resp = match self.client.post(url)
+            .form(&[("logout_token", logout_token)])
+            .header("Cache-Control", "no-store")
+            .send()
+            .await {
+            Ok(resp) => resp,
+            Err(e) => return Err(format!("Failed to send logout token: {}", e))
+        };
+
+        Ok(resp.status().as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::time::Duration;
+    use super::LogoutGateway;
+
+    fn receiver(status: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}/backchannel_logout", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+            let mut request = vec![];
+            let mut buf = [0u8; 4096];
+
+            while let Ok(n) = stream.read(&mut buf) {
+                request.extend_from_slice(&buf[..n]);
+
+                let text = String::from_utf8_lossy(&request);
+
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head.lines()
+                        .find_map(|e| e.to_ascii_lowercase().strip_prefix("content-length:").map(|e| e.trim().parse::<usize>().unwrap()))
+                        .unwrap_or(0);
+
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+
+                if n == 0 {
+                    break;
+                }
+            }
+
+            stream.write_all(format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status).as_bytes()).unwrap();
+
+            String::from_utf8(request).unwrap()
+        });
+
+        (uri, handle)
+    }
+
+    #[actix_web::test]
+    async fn posts_the_logout_token_to_the_receiver() {
+        let (uri, handle) = receiver("200 OK");
+
+        let status = LogoutGateway::build(Duration::from_secs(5), true)
+            .post_backchannel_logout_v1(uri, String::from("a.b.c"))
+            .await;
+
+        let request = handle.join().unwrap();
+
+        assert_eq!(status, Ok(200));
+        assert!(request.starts_with("POST /backchannel_logout HTTP/1.1"));
+        assert!(request.to_ascii_lowercase().contains("cache-control: no-store"));
+        assert!(request.ends_with("logout_token=a.b.c"));
+    }
+
+    #[actix_web::test]
+    async fn reports_the_receiver_status() {
+        let (uri, handle) = receiver("400 Bad Request");
+
+        let status = LogoutGateway::build(Duration::from_secs(5), true)
+            .post_backchannel_logout_v1(uri, String::from("a.b.c"))
+            .await;
+
+        handle.join().unwrap();
+
+        assert_eq!(status, Ok(400));
+    }
+
+    #[actix_web::test]
+    async fn refuses_private_receivers() {
+        let gateway = LogoutGateway::build(Duration::from_secs(5), false);
+
+        for uri in ["http://127.0.0.1:1/logout", "http://localhost:1/logout", "http://[::1]:1/logout", "http://169.254.169.254/logout", "http://10.0.0.1/logout"] {
+            assert!(gateway.post_backchannel_logout_v1(String::from(uri), String::from("a.b.c")).await.is_err(), "{}", uri);
+        }
+    }
+}
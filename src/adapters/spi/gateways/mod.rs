@@ -1 +1,5 @@
-pub mod idp;
\ No newline at end of file
+pub mod ciba;
+pub mod idp;
+pub mod jwks;
+pub mod logout;
+pub mod request_object;
\ No newline at end of file
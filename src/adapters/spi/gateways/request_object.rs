@@ -0,0 +1,45 @@
+use std::time::Duration;
+use crate::utils::outbound::{allow_private_networks, outbound_client, read_limited, validate_outbound_uri};
+
+const REQUEST_OBJECT_MAX_BYTES: usize = 64 * 1024;
+
+pub struct RequestObjectGateway {
+    client: reqwest::Client,
+    allow_private: bool,
+}
+
+impl RequestObjectGateway {
+    pub fn new() -> Self {
+        let timeout = std::env::var("REQUEST_OBJECT_TIMEOUT")
+            .ok()
+            .and_then(|e| e.parse::<u64>().ok())
+            .unwrap_or(5);
+
+        let allow_private = allow_private_networks();
+
+        Self {
+            client: outbound_client(Duration::from_secs(timeout), allow_private).expect("Cannot create request object HTTP client"),
+            allow_private,
+        }
+    }
+
+    pub async fn get_request_object_v1(&self, uri: String) -> Result<String, String> {
+        let url = validate_outbound_uri(uri.as_str(), self.allow_private)?;
+
+        let resp = match self.client.get(url)
+            .header("Accept", "application/oauth-authz-req+jwt")
+            .send()
+            .await {
+            Ok(resp) => resp,
+            Err(e) => return Err(format!("Failed to fetch request object: {}", e))
+        };
+
+        if !resp.status().is_success() {
+            return Err(format!("Failed to fetch request object: {}", resp.status()));
+        }
+
+        let body = read_limited(resp, REQUEST_OBJECT_MAX_BYTES).await.map_err(|e| format!("Failed to fetch request object: {}", e))?;
+
+        String::from_utf8(body).map(|e| e.trim().to_string()).map_err(|e| format!("Invalid request object: {}", e))
+    }
+}
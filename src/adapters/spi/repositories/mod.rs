@@ -2,3 +2,5 @@ pub mod oauth_client;
 pub mod oauth_session;
 pub mod oauth_token;
 pub mod oauth_consent;
+pub mod oauth_signing_key;
+pub mod oauth_logout_delivery;
\ No newline at end of file
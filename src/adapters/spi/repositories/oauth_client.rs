@@ -27,16 +27,62 @@ impl RepositoryInterface for OAuthClientRepository {
             INSERT INTO {} (
                 name,
                 slug,
+                secret,
+                secret_hash,
+                secret_rotated_at,
                 urls,
-                scopes
-            ) VALUES ($1, $2, $3, $4) RETURNING id
+                request_uris,
+                scopes,
+                logos,
+                post_logout_redirect_uris,
+                backchannel_logout_uri,
+                frontchannel_logout_uri,
+                grant_types,
+                backchannel_token_delivery_mode,
+                backchannel_client_notification_endpoint,
+                client_type,
+                application_type,
+                token_endpoint_auth_method,
+                tls_client_auth_subject_dn,
+                tls_client_auth_san_dns,
+                tls_client_auth_san_uri,
+                tls_client_auth_san_ip,
+                tls_client_auth_san_email,
+                tls_client_certificate_thumbprint,
+                jwks,
+                jwks_uri,
+                registration_access_token
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27) RETURNING id
             "#, self.table.clone());
 
         let insert_result = sqlx::query_scalar::<_, uuid::Uuid>(&query)
             .bind(data.name)
             .bind(data.slug)
+            .bind(data.secret)
+            .bind(data.secret_hash)
+            .bind(data.secret_rotated_at)
             .bind(data.urls)
+            .bind(data.request_uris)
             .bind(data.scopes)
+            .bind(data.logos)
+            .bind(data.post_logout_redirect_uris)
+            .bind(data.backchannel_logout_uri)
+            .bind(data.frontchannel_logout_uri)
+            .bind(data.grant_types)
+            .bind(data.backchannel_token_delivery_mode)
+            .bind(data.backchannel_client_notification_endpoint)
+            .bind(data.client_type)
+            .bind(data.application_type)
+            .bind(data.token_endpoint_auth_method)
+            .bind(data.tls_client_auth_subject_dn)
+            .bind(data.tls_client_auth_san_dns)
+            .bind(data.tls_client_auth_san_uri)
+            .bind(data.tls_client_auth_san_ip)
+            .bind(data.tls_client_auth_san_email)
+            .bind(data.tls_client_certificate_thumbprint)
+            .bind(data.jwks)
+            .bind(data.jwks_uri)
+            .bind(data.registration_access_token)
             .fetch_one(&self.db.pool)
             .await;
 
@@ -87,7 +133,7 @@ impl RepositoryInterface for OAuthClientRepository {
 
         let mut set_clauses = query.separated(", ");
 
-        for_each_field!(data, { name, urls, scopes, status }, |k: &str, v| {
+        for_each_field!(data, { name, secret, secret_hash, previous_secret, previous_secret_hash, previous_secret_expires_at, secret_rotated_at, urls, request_uris, scopes, logos, status, post_logout_redirect_uris, backchannel_logout_uri, frontchannel_logout_uri, grant_types, token_exchange_audiences, backchannel_token_delivery_mode, backchannel_client_notification_endpoint, client_type, application_type, token_endpoint_auth_method, tls_client_auth_subject_dn, tls_client_auth_san_dns, tls_client_auth_san_uri, tls_client_auth_san_ip, tls_client_auth_san_email, tls_client_certificate_thumbprint, jwks, jwks_uri, registration_access_token }, |k: &str, v| {
             if fields.contains(&k) {
                 set_clauses.push(format!(" {} = ", k));
                 set_clauses.push_bind_unseparated(v);
@@ -131,12 +177,11 @@ impl RepositoryInterface for OAuthClientRepository {
 
 #[allow(unused)]
 impl OAuthClientRepository {
-    pub async fn get_by_slug_secret(&self, slug: String, secret: String) -> Result<OauthClient, String> {
-        let query = format!("SELECT * FROM {} WHERE slug = $1 and secret = $2", self.table.clone());
+    pub async fn get_by_slug(&self, slug: String) -> Result<OauthClient, String> {
+        let query = format!("SELECT * FROM {} WHERE slug = $1", self.table.clone());
 
         match sqlx::query_as::<_, OauthClient>(&query)
             .bind(slug)
-            .bind(secret)
             .fetch_one(&self.db.pool)
             .await {
             Ok(e) => Ok(e),
@@ -144,15 +189,14 @@ impl OAuthClientRepository {
         }
     }
 
-    pub async fn get_by_slug(&self, slug: String) -> Result<OauthClient, String> {
-        let query = format!("SELECT * FROM {} WHERE slug = $1", self.table.clone());
+    pub async fn list_with_plaintext_secret(&self) -> Result<Vec<OauthClient>, String> {
+        let query = format!("SELECT * FROM {} WHERE secret IS NOT NULL OR previous_secret IS NOT NULL", self.table.clone());
 
         match sqlx::query_as::<_, OauthClient>(&query)
-            .bind(slug)
-            .fetch_one(&self.db.pool)
+            .fetch_all(&self.db.pool)
             .await {
             Ok(e) => Ok(e),
-            Err(_) => Err(String::from("Client not found"))
+            Err(_) => Err(String::from("Failed to list clients"))
         }
     }
 }
\ No newline at end of file
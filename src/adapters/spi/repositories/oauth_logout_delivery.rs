@@ -0,0 +1,154 @@
+use std::sync::Arc;
+use crate::adapters::spi::db::postgres_db::PostgresDB;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::domain::oauth_logout_delivery::OauthLogoutDelivery;
+use crate::for_each_field;
+
+pub struct OAuthLogoutDeliveryRepository {
+    db: Arc<PostgresDB>,
+    table: String,
+}
+
+#[allow(unused)]
+impl RepositoryInterface for OAuthLogoutDeliveryRepository {
+    type DB = PostgresDB;
+    type Model = OauthLogoutDelivery;
+    type Id = uuid::Uuid;
+
+    fn new(table_name: String, pool: Arc<Self::DB>) -> Self {
+        Self {
+            db: pool,
+            table: table_name,
+        }
+    }
+
+    async fn insert(&self, data: Self::Model) -> Result<Self::Model, String> {
+        let query = format!(r#"
+            INSERT INTO {} (
+                session_id,
+                client_id,
+                logout_uri,
+                status
+            ) VALUES ($1, $2, $3, $4) RETURNING id
+            "#, self.table.clone());
+
+        let insert_result = sqlx::query_scalar::<_, uuid::Uuid>(&query)
+            .bind(data.session_id)
+            .bind(data.client_id)
+            .bind(data.logout_uri)
+            .bind(data.status)
+            .fetch_one(&self.db.pool)
+            .await;
+
+        let id = match insert_result {
+            Ok(id) => id,
+            Err(_) => {
+                return Err(String::from("Failed to insert logout delivery"))
+            }
+        };
+
+        match sqlx::query_as::<_, Self::Model>(format!("SELECT * FROM {} WHERE id = $1", self.table.clone()).as_str())
+            .bind(id)
+            .fetch_one(&self.db.pool).await {
+            Ok(e) => Ok(e),
+            Err(_) => Err(String::from("Cannot retrieve logout delivery"))
+        }
+    }
+
+    async fn count(&self) -> i32 {
+        let query = format!("SELECT count(*) AS TOTAL FROM {}", self.table.clone());
+
+        sqlx::query_scalar::<_, i32>(&query)
+            .fetch_one(&self.db.pool)
+            .await.unwrap()
+    }
+
+    async fn list(&self, page: i32, limit: i32) -> Vec<Self::Model> {
+        let query = format!("SELECT * FROM {} LIMIT $1 OFFSET $2", self.table.clone());
+
+        sqlx::query_as::<_, Self::Model>(&query)
+            .bind(limit)
+            .bind(page * limit)
+            .fetch_all(&self.db.pool)
+            .await.unwrap()
+    }
+
+    async fn edit(
+        &self,
+        id: Self::Id,
+        data: Self::Model,
+        fields: Vec<&str>,
+    ) -> Result<Self::Model, String> {
+        let mut query =
+            sqlx::QueryBuilder::new(format!("UPDATE {} SET", self.table));
+
+        let mut set_clauses = query.separated(", ");
+
+        for_each_field!(data, { status, attempts, response_status, last_error, delivered_at, updated_at }, |k: &str, v| {
+            if fields.contains(&k) {
+                set_clauses.push(format!(" {} = ", k));
+                set_clauses.push_bind_unseparated(v);
+            }
+        });
+
+        query.push(" WHERE id = ");
+        query.push_bind(id);
+
+        let mut sql = query.build();
+
+        sql.execute(&self.db.pool)
+            .await
+            .map_err(|_| String::from("Failed to update logout delivery"))?;
+
+        self.get(id).await
+    }
+
+    async fn get(&self, id: Self::Id) -> Result<Self::Model, String> {
+        let query = format!("SELECT * FROM {} WHERE id = $1", self.table.clone());
+
+        match sqlx::query_as::<_, Self::Model>(&query)
+            .bind(id)
+            .fetch_one(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e),
+            Err(_) => Err(String::from("Logout delivery not found"))
+        }
+    }
+
+    async fn delete(&self, id: Self::Id) -> Result<Self::Id, String> {
+        match sqlx::query(&format!("DELETE FROM {} WHERE id = $1", self.table.clone()))
+            .bind(id)
+            .execute(&self.db.pool)
+            .await {
+            Ok(_) => Ok(id),
+            Err(_) => Err(String::from("Logout delivery not found"))
+        }
+    }
+}
+
+impl OAuthLogoutDeliveryRepository {
+    /// Claims pending deliveries that have not been touched since `stale_before`, so that concurrent workers never pick the same row.
+    pub async fn claim_pending(&self, stale_before: chrono::NaiveDateTime, limit: i64) -> Result<Vec<OauthLogoutDelivery>, String> {
+        let query = format!(r#"
+            UPDATE {0} SET updated_at = $4
+            WHERE id IN (
+                SELECT id FROM {0}
+                WHERE status = $1 AND updated_at < $2
+                ORDER BY updated_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            ) RETURNING *
+            "#, self.table.clone());
+
+        match sqlx::query_as::<_, OauthLogoutDelivery>(&query)
+            .bind(OauthLogoutDelivery::STATUS_PENDING)
+            .bind(stale_before)
+            .bind(limit)
+            .bind(chrono::Utc::now().naive_utc())
+            .fetch_all(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e),
+            Err(_) => Err(String::from("Failed to claim logout deliveries"))
+        }
+    }
+}
\ No newline at end of file
@@ -27,22 +27,32 @@ impl RepositoryInterface for OAuthSessionRepository {
             INSERT INTO {} (
                 client_id,
                 response_type,
+                response_mode,
                 scopes,
                 redirect_uri,
                 state,
                 code_challenge,
-                code_challenge_method
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id
+                code_challenge_method,
+                nonce,
+                auth_time,
+                user_id,
+                status
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING id
             "#, self.table.clone());
 
         let insert_result = sqlx::query_scalar::<_, uuid::Uuid>(&query)
             .bind(data.client_id)
             .bind(data.response_type)
+            .bind(data.response_mode)
             .bind(data.scopes)
             .bind(data.redirect_uri)
             .bind(data.state)
             .bind(data.code_challenge)
             .bind(data.code_challenge_method)
+            .bind(data.nonce)
+            .bind(data.auth_time)
+            .bind(data.user_id)
+            .bind(data.status.unwrap_or(OauthSession::STATUS_ACTIVE))
             .fetch_one(&self.db.pool)
             .await;
 
@@ -93,7 +103,7 @@ impl RepositoryInterface for OAuthSessionRepository {
 
         let mut set_clauses = query.separated(", ");
 
-        for_each_field!(data, { user_id, status, scopes, consent_granted_at }, |k: &str, v| {
+        for_each_field!(data, { user_id, status, scopes, consent_granted_at, auth_time, acr, amr, login_id }, |k: &str, v| {
             if fields.contains(&k) {
                 set_clauses.push(format!(" {} = ", k));
                 set_clauses.push_bind_unseparated(v);
@@ -134,3 +144,32 @@ impl RepositoryInterface for OAuthSessionRepository {
         }
     }
 }
+
+impl OAuthSessionRepository {
+    pub async fn end_by_client(&self, client_id: String) -> Result<Vec<uuid::Uuid>, String> {
+        let query = format!("UPDATE {} SET status = $1, updated_at = now() WHERE client_id = $2 AND status != $1 RETURNING id", self.table.clone());
+
+        match sqlx::query_scalar::<_, uuid::Uuid>(&query)
+            .bind(OauthSession::STATUS_ENDED)
+            .bind(client_id)
+            .fetch_all(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e),
+            Err(_) => Err(String::from("Failed to end sessions"))
+        }
+    }
+
+    pub async fn list_active_by_login(&self, user_id: uuid::Uuid, login_id: String) -> Result<Vec<OauthSession>, String> {
+        let query = format!("SELECT * FROM {} WHERE user_id = $1 AND login_id = $2 AND status = $3", self.table.clone());
+
+        match sqlx::query_as::<_, OauthSession>(&query)
+            .bind(user_id)
+            .bind(login_id)
+            .bind(OauthSession::STATUS_ACTIVE)
+            .fetch_all(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e),
+            Err(_) => Err(String::from("Failed to list sessions"))
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,291 @@
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use crate::adapters::spi::db::postgres_db::PostgresDB;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::domain::oauth_signing_key::OauthSigningKey;
+use crate::for_each_field;
+
+const KEY_SET_LOCK: i64 = 7_001_003;
+const ACTIVE_KEY_CACHE_TTL: Duration = Duration::from_secs(30);
+
+pub struct OAuthSigningKeyRepository {
+    db: Arc<PostgresDB>,
+    table: String,
+    active: RwLock<Option<(Instant, OauthSigningKey)>>,
+}
+
+#[allow(unused)]
+impl RepositoryInterface for OAuthSigningKeyRepository {
+    type DB = PostgresDB;
+    type Model = OauthSigningKey;
+    type Id = uuid::Uuid;
+
+    fn new(table_name: String, pool: Arc<Self::DB>) -> Self {
+        Self {
+            db: pool,
+            table: table_name,
+            active: RwLock::new(None),
+        }
+    }
+
+    async fn insert(&self, data: Self::Model) -> Result<Self::Model, String> {
+        let query = format!(r#"
+            INSERT INTO {} (
+                kid,
+                algorithm,
+                private_key,
+                status,
+                activated_at
+            ) VALUES ($1, $2, $3, $4, $5) RETURNING id
+            "#, self.table.clone());
+
+        let insert_result = sqlx::query_scalar::<_, uuid::Uuid>(&query)
+            .bind(data.kid)
+            .bind(data.algorithm)
+            .bind(data.private_key)
+            .bind(data.status)
+            .bind(data.activated_at)
+            .fetch_one(&self.db.pool)
+            .await;
+
+        let id = match insert_result {
+            Ok(id) => id,
+            Err(_) => {
+                return Err(String::from("Failed to insert signing key"))
+            }
+        };
+
+        match sqlx::query_as::<_, Self::Model>(format!("SELECT * FROM {} WHERE id = $1", self.table.clone()).as_str())
+            .bind(id)
+            .fetch_one(&self.db.pool).await {
+            Ok(e) => Ok(e),
+            Err(_) => Err(String::from("Cannot retrieve signing key"))
+        }
+    }
+
+    async fn count(&self) -> i32 {
+        let query = format!("SELECT count(*) AS TOTAL FROM {}", self.table.clone());
+
+        sqlx::query_scalar::<_, i32>(&query)
+            .fetch_one(&self.db.pool)
+            .await.unwrap()
+    }
+
+    async fn list(&self, page: i32, limit: i32) -> Vec<Self::Model> {
+        let query = format!("SELECT * FROM {} LIMIT $1 OFFSET $2", self.table.clone());
+
+        sqlx::query_as::<_, Self::Model>(&query)
+            .bind(limit)
+            .bind(page * limit)
+            .fetch_all(&self.db.pool)
+            .await.unwrap()
+    }
+
+    async fn edit(
+        &self,
+        id: Self::Id,
+        data: Self::Model,
+        fields: Vec<&str>,
+    ) -> Result<Self::Model, String> {
+        let mut query =
+            sqlx::QueryBuilder::new(format!("UPDATE {} SET", self.table));
+
+        let mut set_clauses = query.separated(", ");
+
+        for_each_field!(data, { private_key, status, activated_at, retired_at }, |k: &str, v| {
+            if fields.contains(&k) {
+                set_clauses.push(format!(" {} = ", k));
+                set_clauses.push_bind_unseparated(v);
+            }
+        });
+
+        query.push(" WHERE id = ");
+        query.push_bind(id);
+
+        let mut sql = query.build();
+
+        sql.execute(&self.db.pool)
+            .await
+            .map_err(|_| String::from("Failed to update signing key"))?;
+
+        self.get(id).await
+    }
+
+    async fn get(&self, id: Self::Id) -> Result<Self::Model, String> {
+        let query = format!("SELECT * FROM {} WHERE id = $1", self.table.clone());
+
+        match sqlx::query_as::<_, Self::Model>(&query)
+            .bind(id)
+            .fetch_one(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e),
+            Err(_) => Err(String::from("Signing key not found"))
+        }
+    }
+
+    async fn delete(&self, id: Self::Id) -> Result<Self::Id, String> {
+        match sqlx::query(&format!("DELETE FROM {} WHERE id = $1", self.table.clone()))
+            .bind(id)
+            .execute(&self.db.pool)
+            .await {
+            Ok(_) => Ok(id),
+            Err(_) => Err(String::from("Signing key not found"))
+        }
+    }
+}
+
+impl OAuthSigningKeyRepository {
+    pub async fn get_active(&self) -> Result<OauthSigningKey, String> {
+        if let Some((_, key)) = self.active.read().ok().and_then(|e| e.clone()).filter(|(at, _)| at.elapsed() < ACTIVE_KEY_CACHE_TTL) {
+            return Ok(key);
+        }
+
+        let query = format!("SELECT * FROM {} WHERE status = $1 ORDER BY activated_at DESC LIMIT 1", self.table.clone());
+
+        let key = match sqlx::query_as::<_, OauthSigningKey>(&query)
+            .bind(OauthSigningKey::STATUS_ACTIVE)
+            .fetch_one(&self.db.pool)
+            .await {
+            Ok(e) => e,
+            Err(_) => return Err(String::from("Active signing key not found"))
+        };
+
+        if let Ok(mut active) = self.active.write() {
+            *active = Some((Instant::now(), key.clone()));
+        }
+
+        Ok(key)
+    }
+
+    pub async fn prepare_key_set(
+        &self,
+        active: impl FnOnce() -> Result<OauthSigningKey, String>,
+        next: impl FnOnce() -> Result<OauthSigningKey, String>,
+    ) -> Result<(), String> {
+        let Ok(mut tx) = self.db.pool.begin().await else {
+            return Err(String::from("Failed to start signing key preparation"));
+        };
+
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(KEY_SET_LOCK)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| String::from("Failed to lock signing keys"))?;
+
+        let keys = sqlx::query_as::<_, OauthSigningKey>(&format!("SELECT * FROM {} WHERE status = ANY($1)", self.table))
+            .bind(vec![OauthSigningKey::STATUS_NEXT, OauthSigningKey::STATUS_ACTIVE])
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|_| String::from("Failed to list signing keys"))?;
+
+        let mut has_next = keys.iter().any(|e| e.status == Some(OauthSigningKey::STATUS_NEXT));
+
+        if !keys.iter().any(|e| e.status == Some(OauthSigningKey::STATUS_ACTIVE)) {
+            if has_next {
+                sqlx::query(&format!("UPDATE {} SET status = $1, activated_at = $2, updated_at = $2 WHERE status = $3", self.table))
+                    .bind(OauthSigningKey::STATUS_ACTIVE)
+                    .bind(chrono::Utc::now().naive_utc())
+                    .bind(OauthSigningKey::STATUS_NEXT)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|_| String::from("Failed to activate next signing key"))?;
+
+                has_next = false;
+            } else {
+                Self::insert_key(&mut tx, &self.table, active()?).await?;
+            }
+        }
+
+        if !has_next {
+            Self::insert_key(&mut tx, &self.table, next()?).await?;
+        }
+
+        tx.commit().await.map_err(|_| String::from("Failed to commit signing key preparation"))
+    }
+
+    async fn insert_key(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, table: &str, key: OauthSigningKey) -> Result<(), String> {
+        sqlx::query(&format!("INSERT INTO {} (kid, algorithm, private_key, status, activated_at) VALUES ($1, $2, $3, $4, $5)", table))
+            .bind(key.kid)
+            .bind(key.algorithm)
+            .bind(key.private_key)
+            .bind(key.status)
+            .bind(key.activated_at)
+            .execute(&mut **tx)
+            .await
+            .map_err(|_| String::from("Failed to insert signing key"))?;
+
+        Ok(())
+    }
+
+    pub async fn list_by_status(&self, statuses: Vec<i32>) -> Result<Vec<OauthSigningKey>, String> {
+        let query = format!("SELECT * FROM {} WHERE status = ANY($1) ORDER BY created_at DESC", self.table.clone());
+
+        match sqlx::query_as::<_, OauthSigningKey>(&query)
+            .bind(statuses)
+            .fetch_all(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e),
+            Err(_) => Err(String::from("Failed to list signing keys"))
+        }
+    }
+
+    pub async fn rotate(&self, next: OauthSigningKey, retire_before: chrono::NaiveDateTime) -> Result<Vec<String>, String> {
+        let Ok(mut tx) = self.db.pool.begin().await else {
+            return Err(String::from("Failed to start signing key rotation"));
+        };
+
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(KEY_SET_LOCK)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| String::from("Failed to lock signing keys"))?;
+
+        let now = chrono::Utc::now().naive_utc();
+
+        let revoked: Vec<String> = sqlx::query_scalar(&format!("UPDATE {} SET status = $1, updated_at = $2 WHERE status = $3 AND retired_at < $4 RETURNING kid", self.table))
+            .bind(OauthSigningKey::STATUS_REVOKED)
+            .bind(now)
+            .bind(OauthSigningKey::STATUS_RETIRING)
+            .bind(retire_before)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|_| String::from("Failed to revoke retiring signing keys"))?;
+
+        sqlx::query(&format!("UPDATE {} SET status = $1, retired_at = $2, updated_at = $2 WHERE status = $3", self.table))
+            .bind(OauthSigningKey::STATUS_RETIRING)
+            .bind(now)
+            .bind(OauthSigningKey::STATUS_ACTIVE)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| String::from("Failed to retire active signing key"))?;
+
+        let promoted = sqlx::query(&format!("UPDATE {} SET status = $1, activated_at = $2, updated_at = $2 WHERE status = $3", self.table))
+            .bind(OauthSigningKey::STATUS_ACTIVE)
+            .bind(now)
+            .bind(OauthSigningKey::STATUS_NEXT)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| String::from("Failed to activate next signing key"))?;
+
+        if promoted.rows_affected() != 1 {
+            return Err(String::from("Expected exactly one next signing key"));
+        }
+
+        sqlx::query(&format!("INSERT INTO {} (kid, algorithm, private_key, status) VALUES ($1, $2, $3, $4)", self.table))
+            .bind(next.kid)
+            .bind(next.algorithm)
+            .bind(next.private_key)
+            .bind(OauthSigningKey::STATUS_NEXT)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| String::from("Failed to insert next signing key"))?;
+
+        tx.commit().await.map_err(|_| String::from("Failed to commit signing key rotation"))?;
+
+        if let Ok(mut active) = self.active.write() {
+            *active = None;
+        }
+
+        Ok(revoked)
+    }
+}
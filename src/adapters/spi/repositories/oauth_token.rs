@@ -27,14 +27,28 @@ impl RepositoryInterface for OAuthTokenRepository {
             INSERT INTO {} (
                 session_id,
                 access_token,
-                refresh_token
-            ) VALUES ($1, $2, $3) RETURNING id
+                refresh_token,
+                refresh_token_expires_at,
+                id_token,
+                subject_token_id,
+                actor_token_id,
+                dpop_jkt,
+                x5t_s256,
+                client_id
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id
             "#, self.table.clone());
 
         let insert_result = sqlx::query_scalar::<_, uuid::Uuid>(&query)
             .bind(data.session_id)
             .bind(data.access_token)
             .bind(data.refresh_token)
+            .bind(data.refresh_token_expires_at)
+            .bind(data.id_token)
+            .bind(data.subject_token_id)
+            .bind(data.actor_token_id)
+            .bind(data.dpop_jkt)
+            .bind(data.x5t_s256)
+            .bind(data.client_id)
             .fetch_one(&self.db.pool)
             .await;
 
@@ -85,7 +99,7 @@ impl RepositoryInterface for OAuthTokenRepository {
 
         let mut set_clauses = query.separated(", ");
 
-        for_each_field!(data, { access_token, refresh_token, status, refresh_token_expires_at }, |k: &str, v| {
+        for_each_field!(data, { access_token, refresh_token, previous_refresh_token, status, refresh_token_expires_at, id_token, dpop_jkt, x5t_s256 }, |k: &str, v| {
             if fields.contains(&k) {
                 set_clauses.push(format!(" {} = ", k));
                 set_clauses.push_bind_unseparated(v);
@@ -140,6 +154,18 @@ impl OAuthTokenRepository {
         }
     }
 
+    pub async fn get_by_previous_refresh_token(&self, rf: String) -> Result<OauthToken, String> {
+        let query = format!("SELECT * FROM {} WHERE previous_refresh_token = $1", self.table.clone());
+
+        match sqlx::query_as::<_, OauthToken>(&query)
+            .bind(rf)
+            .fetch_one(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e),
+            Err(_) => Err(String::from("Token not found"))
+        }
+    }
+
     pub async fn get_by_access_token(&self, ac: String) -> Result<OauthToken, String> {
         let query = format!("SELECT * FROM {} WHERE access_token = $1", self.table.clone());
 
@@ -151,4 +177,239 @@ impl OAuthTokenRepository {
             Err(_) => Err(String::from("Token not found"))
         }
     }
+
+    pub async fn get_by_id_token(&self, it: String) -> Result<OauthToken, String> {
+        let query = format!("SELECT * FROM {} WHERE id_token = $1", self.table.clone());
+
+        match sqlx::query_as::<_, OauthToken>(&query)
+            .bind(it)
+            .fetch_one(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e),
+            Err(_) => Err(String::from("Token not found"))
+        }
+    }
+
+    /// Moves the refresh token to a new row linked to `id`, only while `id` still holds `data.previous_refresh_token`,
+    /// so concurrent refreshes cannot both rotate it and the access tokens issued before stay known.
+    pub async fn rotate_refresh_token(&self, id: uuid::Uuid, data: OauthToken) -> Result<Option<OauthToken>, String> {
+        let Ok(mut tx) = self.db.pool.begin().await else {
+            return Err(String::from("Failed to start token rotation"));
+        };
+
+        let released = sqlx::query(&format!("UPDATE {} SET refresh_token = NULL, updated_at = now() WHERE id = $1 AND refresh_token = $2 AND status = $3", self.table))
+            .bind(id)
+            .bind(data.previous_refresh_token.clone())
+            .bind(OauthToken::STATUS_ACTIVE)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| String::from("Failed to update token"))?;
+
+        if released.rows_affected() != 1 {
+            return Ok(None);
+        }
+
+        let query = format!(r#"
+            INSERT INTO {} (
+                session_id,
+                access_token,
+                refresh_token,
+                previous_refresh_token,
+                refresh_token_expires_at,
+                id_token,
+                dpop_jkt,
+                x5t_s256,
+                client_id,
+                previous_token_id
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING *
+            "#, self.table);
+
+        let token = sqlx::query_as::<_, OauthToken>(&query)
+            .bind(data.session_id)
+            .bind(data.access_token)
+            .bind(data.refresh_token)
+            .bind(data.previous_refresh_token)
+            .bind(data.refresh_token_expires_at)
+            .bind(data.id_token)
+            .bind(data.dpop_jkt)
+            .bind(data.x5t_s256)
+            .bind(data.client_id)
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|_| String::from("Failed to insert token"))?;
+
+        tx.commit().await.map_err(|_| String::from("Failed to commit token rotation"))?;
+
+        Ok(Some(token))
+    }
+
+    /// Revokes a token with the rows it was rotated from and the tokens exchanged from any of them.
+    pub async fn revoke_lineage(&self, id: uuid::Uuid) -> Result<u64, String> {
+        let query = format!(r#"
+            WITH RECURSIVE rotated AS (
+                SELECT id, previous_token_id FROM {0} WHERE id = $1
+                UNION
+                SELECT t.id, t.previous_token_id FROM {0} t INNER JOIN rotated r ON t.id = r.previous_token_id
+            ), derived AS (
+                SELECT id FROM rotated
+                UNION
+                SELECT t.id FROM {0} t INNER JOIN derived d ON t.subject_token_id = d.id
+            )
+            UPDATE {0} SET status = $2, updated_at = now() WHERE id IN (SELECT id FROM derived) AND status = $3
+            "#, self.table.clone());
+
+        match sqlx::query(&query)
+            .bind(id)
+            .bind(OauthToken::STATUS_REVOKED)
+            .bind(OauthToken::STATUS_ACTIVE)
+            .execute(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e.rows_affected()),
+            Err(_) => Err(String::from("Failed to revoke tokens"))
+        }
+    }
+
+    pub async fn revoke_by_session(&self, session_id: uuid::Uuid) -> Result<u64, String> {
+        let query = format!("UPDATE {} SET status = $1, updated_at = now() WHERE session_id = $2 AND status = $3", self.table.clone());
+
+        match sqlx::query(&query)
+            .bind(OauthToken::STATUS_REVOKED)
+            .bind(session_id)
+            .bind(OauthToken::STATUS_ACTIVE)
+            .execute(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e.rows_affected()),
+            Err(_) => Err(String::from("Failed to revoke tokens"))
+        }
+    }
+
+    /// Revokes the tokens issued to a client or within its sessions, together with the tokens exchanged from them.
+    pub async fn revoke_by_client(&self, client_id: String, session_ids: Vec<uuid::Uuid>) -> Result<u64, String> {
+        let query = format!(r#"
+            WITH RECURSIVE owned AS (
+                SELECT id FROM {0} WHERE client_id = $1 OR session_id = ANY($2)
+                UNION
+                SELECT t.id FROM {0} t INNER JOIN owned o ON t.subject_token_id = o.id
+            )
+            UPDATE {0} SET status = $3, updated_at = now() WHERE id IN (SELECT id FROM owned) AND status = $4
+            "#, self.table.clone());
+
+        match sqlx::query(&query)
+            .bind(client_id)
+            .bind(session_ids)
+            .bind(OauthToken::STATUS_REVOKED)
+            .bind(OauthToken::STATUS_ACTIVE)
+            .execute(&self.db.pool)
+            .await {
+            Ok(e) => Ok(e.rows_affected()),
+            Err(_) => Err(String::from("Failed to revoke tokens"))
+        }
+    }
+
+    pub async fn get_by_token(&self, hash: String, token_type_hint: Option<&str>) -> Result<OauthToken, String> {
+        if token_type_hint == Some("refresh_token") {
+            if let Ok(e) = self.get_by_refresh_token(hash.clone()).await {
+                return Ok(e);
+            }
+
+            return self.get_by_access_token(hash).await;
+        }
+
+        if let Ok(e) = self.get_by_access_token(hash.clone()).await {
+            return Ok(e);
+        }
+
+        self.get_by_refresh_token(hash).await
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::application::spi::repository::RepositoryInterface;
+    use crate::domain::oauth_token::OauthToken;
+    use crate::utils::test_support::{database, unique};
+    use super::OAuthTokenRepository;
+
+    fn token(access_token: &str, refresh_token: Option<&str>) -> OauthToken {
+        OauthToken {
+            id: None,
+            session_id: None,
+            access_token: Some(access_token.to_string()),
+            refresh_token: refresh_token.map(String::from),
+            previous_refresh_token: None,
+            refresh_token_expires_at: None,
+            id_token: None,
+            subject_token_id: None,
+            actor_token_id: None,
+            previous_token_id: None,
+            dpop_jkt: None,
+            x5t_s256: None,
+            client_id: None,
+            status: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn rotation_keeps_the_previous_access_token() {
+        let Some(db) = database().await else { return };
+        let repository = OAuthTokenRepository::new(String::from("oauth_token"), db);
+
+        let (access_token, refresh_token) = (unique("access"), unique("refresh"));
+        let first = repository.insert(token(&access_token, Some(&refresh_token))).await.unwrap();
+
+        let rotated = repository.rotate_refresh_token(first.id.unwrap(), OauthToken {
+            previous_refresh_token: Some(refresh_token.clone()),
+            ..token(&unique("access"), Some(&unique("refresh")))
+        }).await.unwrap().unwrap();
+
+        assert_eq!(rotated.previous_token_id, first.id);
+        assert_eq!(rotated.previous_refresh_token, Some(refresh_token.clone()));
+
+        let previous = repository.get_by_access_token(access_token).await.unwrap();
+        assert_eq!(previous.id, first.id);
+        assert!(previous.is_active());
+        assert!(repository.get_by_refresh_token(refresh_token.clone()).await.is_err());
+
+        let again = repository.rotate_refresh_token(first.id.unwrap(), OauthToken {
+            previous_refresh_token: Some(refresh_token),
+            ..token(&unique("access"), Some(&unique("refresh")))
+        }).await.unwrap();
+        assert!(again.is_none());
+
+        repository.revoke_lineage(rotated.id.unwrap()).await.unwrap();
+        assert!(!repository.get(first.id.unwrap()).await.unwrap().is_active());
+    }
+
+    #[actix_web::test]
+    async fn revoking_a_token_revokes_the_tokens_exchanged_from_its_lineage() {
+        let Some(db) = database().await else { return };
+        let repository = OAuthTokenRepository::new(String::from("oauth_token"), db);
+
+        let refresh_token = unique("refresh");
+        let first = repository.insert(token(&unique("access"), Some(&refresh_token))).await.unwrap();
+        let rotated = repository.rotate_refresh_token(first.id.unwrap(), OauthToken {
+            previous_refresh_token: Some(refresh_token),
+            ..token(&unique("access"), Some(&unique("refresh")))
+        }).await.unwrap().unwrap();
+
+        let exchanged = repository.insert(OauthToken {
+            subject_token_id: first.id,
+            ..token(&unique("access"), None)
+        }).await.unwrap();
+        let nested = repository.insert(OauthToken {
+            subject_token_id: exchanged.id,
+            ..token(&unique("access"), None)
+        }).await.unwrap();
+        let unrelated = repository.insert(token(&unique("access"), None)).await.unwrap();
+
+        assert_eq!(repository.revoke_lineage(rotated.id.unwrap()).await.unwrap(), 4);
+
+        for revoked in [first, rotated, exchanged, nested] {
+            assert!(!repository.get(revoked.id.unwrap()).await.unwrap().is_active());
+        }
+
+        assert!(repository.get(unrelated.id.unwrap()).await.unwrap().is_active());
+    }
 }
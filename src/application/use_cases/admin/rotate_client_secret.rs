@@ -0,0 +1,142 @@
+use std::env;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::auth::client_authentication::CLIENT_SECRET_JWT;
+use crate::domain::oauth_client::OauthClient;
+use crate::dto::admin::client_secret::request::RotateClientSecretRequest;
+use crate::dto::admin::client_secret::response::ClientSecretResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::encryption::{decrypt, encrypt, is_encrypted};
+use crate::utils::hasher::hash_secret;
+use crate::utils::token::generate_client_secret;
+
+pub const CLIENT_SECRET_ROTATION_OVERLAP: i64 = 24 * 60 * 60;
+
+pub struct RotateClientSecretUseCase {
+    repository: Arc<OAuthClientRepository>,
+}
+
+impl UseCaseInterface for RotateClientSecretUseCase {
+    type Request = RotateClientSecretRequest;
+    type Response = ClientSecretResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        if data.overlap.is_some_and(|e| e < 0) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid overlap"));
+        }
+
+        let Ok(mut client) = self.repository.get_by_slug(data.client_id.clone()).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Client not found").with_status(StatusCode::NOT_FOUND));
+        };
+
+        let overlap = match data.overlap {
+            Some(e) => e,
+            None if client.secret_hash.is_some() => Self::default_overlap(),
+            None => 0,
+        };
+
+        if overlap > 0 && client.secret_hash.is_none() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Client has no secret to keep during the overlap"));
+        }
+
+        let secret = generate_client_secret();
+        let secret_hash = hash_secret(secret.as_str()).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+        let now = chrono::Utc::now().naive_utc();
+        let keep_plaintext = Self::keeps_plaintext(&client);
+
+        if overlap > 0 {
+            client.previous_secret_hash = client.secret_hash.take();
+            client.previous_secret = client.secret.take().filter(|_| keep_plaintext);
+            client.previous_secret_expires_at = Some(now + chrono::Duration::seconds(overlap));
+        } else {
+            client.previous_secret_hash = None;
+            client.previous_secret = None;
+            client.previous_secret_expires_at = None;
+        }
+
+        client.secret_hash = Some(secret_hash);
+        client.secret = match keep_plaintext {
+            true => Some(encrypt(&secret).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?),
+            false => None,
+        };
+        client.secret_rotated_at = Some(now);
+
+        let Ok(client) = self.repository.edit(client.id.unwrap(), client, vec![
+            "secret",
+            "secret_hash",
+            "previous_secret",
+            "previous_secret_hash",
+            "previous_secret_expires_at",
+            "secret_rotated_at",
+        ]).await else {
+            return Err(ApiError::new(String::from("Failed to rotate client secret"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        Ok(ApiSuccess::new(ClientSecretResponse {
+            client_id: data.client_id,
+            client_secret: secret,
+            secret_rotated_at: now,
+            previous_secret_expires_at: client.previous_secret_expires_at,
+        }, StatusCode::OK))
+    }
+}
+
+impl RotateClientSecretUseCase {
+    pub fn new(repository: Arc<OAuthClientRepository>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn migrate_plaintext_secrets(&self) -> Result<(), String> {
+        for mut client in self.repository.list_with_plaintext_secret().await? {
+            let mut fields = vec![];
+
+            if client.secret_hash.is_none() && let Some(secret) = client.secret.as_ref() {
+                client.secret_hash = Some(hash_secret(&decrypt(secret)?)?);
+                fields.push("secret_hash");
+            }
+
+            if client.previous_secret_hash.is_none() && let Some(secret) = client.previous_secret.as_ref() {
+                client.previous_secret_hash = Some(hash_secret(&decrypt(secret)?)?);
+                fields.push("previous_secret_hash");
+            }
+
+            if !Self::keeps_plaintext(&client) {
+                client.secret = None;
+                client.previous_secret = None;
+                fields.extend(["secret", "previous_secret"]);
+            } else if [&client.secret, &client.previous_secret].iter().any(|e| e.as_ref().is_some_and(|e| !is_encrypted(e))) {
+                client.secret = client.secret.as_deref().map(Self::encrypt_secret).transpose()?;
+                client.previous_secret = client.previous_secret.as_deref().map(Self::encrypt_secret).transpose()?;
+                fields.extend(["secret", "previous_secret"]);
+            }
+
+            if !fields.is_empty() {
+                self.repository.edit(client.id.unwrap(), client, fields).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encrypt_secret(secret: &str) -> Result<String, String> {
+        match is_encrypted(secret) {
+            true => Ok(secret.to_string()),
+            false => encrypt(secret),
+        }
+    }
+
+    fn keeps_plaintext(client: &OauthClient) -> bool {
+        client.token_endpoint_auth_method.as_deref() == Some(CLIENT_SECRET_JWT)
+    }
+
+    fn default_overlap() -> i64 {
+        env::var("CLIENT_SECRET_ROTATION_OVERLAP")
+            .ok()
+            .and_then(|e| e.parse::<i64>().ok())
+            .unwrap_or(CLIENT_SECRET_ROTATION_OVERLAP)
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::domain::oauth_signing_key::OauthSigningKey;
+use crate::dto::admin::signing_keys::response::{SigningKeyResponse, SigningKeysResponse};
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::encryption::{encrypt, is_encrypted};
+use crate::utils::signing_key::SigningKey;
+use crate::utils::token::ACCESS_TOKEN_EXPIRES_IN;
+
+pub struct RotateSigningKeysUseCase {
+    repository: Arc<OAuthSigningKeyRepository>,
+}
+
+impl UseCaseInterface for RotateSigningKeysUseCase {
+    type Request = ();
+    type Response = SigningKeysResponse;
+
+    async fn handle(&self, _data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        if let Err(e) = self.ensure_key_set().await {
+            return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR));
+        }
+
+        let next = match SigningKey::generate().and_then(|e| e.to_model(OauthSigningKey::STATUS_NEXT)) {
+            Ok(e) => e,
+            Err(e) => return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let retire_before = chrono::Utc::now().naive_utc() - chrono::Duration::seconds(ACCESS_TOKEN_EXPIRES_IN);
+
+        match self.repository.rotate(next, retire_before).await {
+            Ok(revoked) => SigningKey::evict(&revoked),
+            Err(e) => return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        let keys = match self.repository.list_by_status(vec![
+            OauthSigningKey::STATUS_NEXT,
+            OauthSigningKey::STATUS_ACTIVE,
+            OauthSigningKey::STATUS_RETIRING,
+            OauthSigningKey::STATUS_REVOKED,
+        ]).await {
+            Ok(e) => e,
+            Err(e) => return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        Ok(ApiSuccess::new(SigningKeysResponse {
+            keys: keys.iter().map(|e| SigningKeyResponse {
+                kid: e.kid.clone().unwrap_or_default(),
+                algorithm: e.algorithm.clone().unwrap_or_default(),
+                status: e.status_name().to_string(),
+                activated_at: e.activated_at,
+                retired_at: e.retired_at,
+                created_at: e.created_at,
+            }).collect(),
+        }, StatusCode::OK))
+    }
+}
+
+impl RotateSigningKeysUseCase {
+    pub fn new(repository: Arc<OAuthSigningKeyRepository>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn ensure_key_set(&self) -> Result<(), String> {
+        self.repository.prepare_key_set(
+            || match SigningKey::from_env() {
+                Ok(e) => e,
+                Err(_) => SigningKey::generate()?
+            }.to_model(OauthSigningKey::STATUS_ACTIVE),
+            || SigningKey::generate()?.to_model(OauthSigningKey::STATUS_NEXT),
+        ).await?;
+
+        self.encrypt_private_keys().await
+    }
+
+    async fn encrypt_private_keys(&self) -> Result<(), String> {
+        let keys = self.repository.list_by_status(vec![
+            OauthSigningKey::STATUS_NEXT,
+            OauthSigningKey::STATUS_ACTIVE,
+            OauthSigningKey::STATUS_RETIRING,
+            OauthSigningKey::STATUS_REVOKED,
+        ]).await?;
+
+        for mut key in keys {
+            let Some(private_key) = key.private_key.as_ref().filter(|e| !is_encrypted(e)) else {
+                continue;
+            };
+
+            key.private_key = Some(encrypt(private_key)?);
+
+            self.repository.edit(key.id.unwrap(), key, vec!["private_key"]).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use std::env;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::dto::auth::authorize::authorization_response::AuthorizationResponseRequest;
+use crate::dto::auth::authorize::response::AuthorizeResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::response_mode::{authorization_response, parse_response_mode};
+use crate::utils::signing_key::SigningKey;
+use crate::utils::token::generate_authorization_response_token;
+
+pub struct AuthorizationResponseUseCase {
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+}
+
+impl UseCaseInterface for AuthorizationResponseUseCase {
+    type Request = AuthorizationResponseRequest;
+    type Response = AuthorizeResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let (delivery, jwt) = parse_response_mode(data.response_mode.as_deref())
+            .map_err(|e| ApiError::oauth(OAuthErrorCode::InvalidRequest, e))?;
+
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(ApiError::new("JWT_ISSUER not found".to_string(), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let params = match jwt {
+            true => vec![("response", self.sign(data.params, data.client_id, jwt_iss).await?)],
+            false => [data.params, vec![("iss", jwt_iss)]].concat(),
+        };
+
+        let response = authorization_response(&data.redirect_uri, delivery, params)
+            .map_err(|e| ApiError::oauth(OAuthErrorCode::InvalidRequest, e))?;
+
+        Ok(ApiSuccess::new(response, StatusCode::SEE_OTHER))
+    }
+}
+
+impl AuthorizationResponseUseCase {
+    pub fn new(signing_key_repository: Arc<OAuthSigningKeyRepository>) -> Self {
+        Self { signing_key_repository }
+    }
+
+    async fn sign(&self, params: Vec<(&str, String)>, client_id: String, jwt_iss: String) -> Result<String, ApiError> {
+        let Ok(key) = self.signing_key_repository.get_active().await else {
+            return Err(ApiError::new("Active signing key not found".to_string(), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let signing_key = SigningKey::from_model(&key).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        generate_authorization_response_token(params, chrono::Utc::now(), jwt_iss, client_id, &signing_key)
+            .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
+    }
+}
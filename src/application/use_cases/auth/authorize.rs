@@ -2,29 +2,64 @@ use std::sync::Arc;
 use actix_web::http::StatusCode;
 use redis::AsyncCommands;
 use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::gateways::request_object::RequestObjectGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
 use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
 use crate::application::api::use_case::UseCaseInterface;
 use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::auth::authorization_response::AuthorizationResponseUseCase;
+use crate::application::use_cases::auth::par::ParUseCase;
+use crate::application::use_cases::auth::request_object::RequestObjectUseCase;
+use crate::domain::oauth_client::OauthClient;
 use crate::domain::oauth_session::OauthSession;
+use crate::dto::auth::authorize::authorization_response::AuthorizationResponseRequest;
 use crate::dto::auth::authorize::request::AuthorizeRequest;
+use crate::dto::auth::authorize::response::AuthorizeResponse;
 use crate::dto::auth::par::request::ParRequest;
+use crate::dto::auth::par::request_object::RequestObjectRequest;
 use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+
+const PAR_REQUEST_URI_PREFIX: &str = "urn:ietf:params:oauth:request_uri:";
 
 pub struct AuthorizeUseCase {
     cache: Arc<RedisCache>,
     repository: Arc<OAuthSessionRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    jwks_gateway: Arc<JwksGateway>,
+    request_object_gateway: Arc<RequestObjectGateway>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
 }
 
 impl UseCaseInterface for AuthorizeUseCase {
     type Request = AuthorizeRequest;
-    type Response = String;
+    type Response = AuthorizeResponse;
 
     async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
-        let request = match self.get_request_from_par_uri(&data).await {
-            Ok(e) => e,
-            Err(e) => {
-                return Err(ApiError::new(e.0, e.1));
-            }
+        if data.client_id.is_none() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Missing client id"));
+        }
+
+        let request = match data.uri.as_ref() {
+            Some(uri) if uri.starts_with(PAR_REQUEST_URI_PREFIX) => self.get_request_from_par_uri(&data).await?,
+            _ => {
+                let (client, request) = self.get_request_from_request_object(&data).await?;
+
+                if let Err(e) = ParUseCase::new(self.cache.clone(), self.client_repository.clone(), self.jwks_gateway.clone(), self.request_object_gateway.clone())
+                    .validate_parameters(request.clone(), &client) {
+                    return AuthorizationResponseUseCase::new(self.signing_key_repository.clone()).handle(AuthorizationResponseRequest::error(
+                        client.slug.clone().unwrap_or_default(),
+                        request.redirect_uri.clone(),
+                        request.response_mode.clone(),
+                        &request.state,
+                        &e,
+                    )).await
+                }
+
+                Arc::unwrap_or_clone(request)
+            },
         };
 
         let requested_scopes = request.scope.split(" ").collect::<Vec<&str>>().iter().map(|e| e.to_string()).collect::<Vec<String>>();
@@ -33,9 +68,15 @@ impl UseCaseInterface for AuthorizeUseCase {
             id: None,
             client_id: Some(data.client_id.unwrap()),
             response_type: Some(request.response_type),
+            response_mode: request.response_mode,
+            nonce: request.nonce,
             code_challenge_method: Some(request.code_challenge_method),
             status: None,
             consent_granted_at: None,
+            auth_time: None,
+            acr: None,
+            amr: None,
+            login_id: None,
             created_at: None,
             code_challenge: Some(request.code_challenge),
             state: Some(request.state),
@@ -51,45 +92,70 @@ impl UseCaseInterface for AuthorizeUseCase {
         };
 
         let url = std::env::var("LOGIN_PAGE_URL").unwrap_or("http://localhost:3001/".to_string()) + "?session_id=" + result.id.unwrap().to_string().as_str();
-        Ok(ApiSuccess::new(url, StatusCode::SEE_OTHER))
+        Ok(ApiSuccess::new(AuthorizeResponse::Redirect(url), StatusCode::SEE_OTHER))
     }
 }
 
 impl AuthorizeUseCase {
-    pub fn new(cache: Arc<RedisCache>, repository: Arc<OAuthSessionRepository>) -> Self {
-        Self { cache, repository }
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        jwks_gateway: Arc<JwksGateway>,
+        request_object_gateway: Arc<RequestObjectGateway>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    ) -> Self {
+        Self { cache, repository, client_repository, jwks_gateway, request_object_gateway, signing_key_repository }
     }
 
-    async fn get_request_from_par_uri(&self, data: &AuthorizeRequest) -> Result<ParRequest, (String, StatusCode)> {
+    async fn get_request_from_request_object(&self, data: &AuthorizeRequest) -> Result<(OauthClient, Arc<ParRequest>), ApiError> {
+        if data.request.is_none() && data.uri.is_none() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Authorization requests must be pushed or signed"));
+        }
+
+        let Ok(client) = self.client_repository.get_by_slug(data.client_id.clone().unwrap()).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid client id"));
+        };
+
+        let request = RequestObjectUseCase::new(self.cache.clone(), self.jwks_gateway.clone(), self.request_object_gateway.clone()).handle(RequestObjectRequest {
+            client: client.clone(),
+            request: ParRequest::from(data),
+        }).await?.data;
+
+        let request = Arc::new(request);
+
+        ParUseCase::new(self.cache.clone(), self.client_repository.clone(), self.jwks_gateway.clone(), self.request_object_gateway.clone())
+            .validate_redirect(request.clone(), &client)?;
+
+        Ok((client, request))
+    }
+
+    async fn get_request_from_par_uri(&self, data: &AuthorizeRequest) -> Result<ParRequest, ApiError> {
         let uri = data.uri.as_ref().unwrap();
         let client_id = data.client_id.as_ref().unwrap();
 
-        if !uri.starts_with("urn:ietf:params:oauth:request_uri:") {
-            return Err(("Invalid URI".to_string(), StatusCode::BAD_REQUEST))
-        }
-
         let mut conn = match self.cache.get_pool().await {
             Ok(conn) => conn,
             Err(e) => {
-                return Err((e, StatusCode::INTERNAL_SERVER_ERROR))
+                return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
             }
         };
 
         let value = match conn.get::<String, String>(uri.clone()).await {
             Ok(value) => value,
             Err(_) => {
-                return Err(("URI not found".to_string(), StatusCode::BAD_REQUEST))
+                return Err(ApiError::oauth(OAuthErrorCode::InvalidRequestUri, "URI not found"))
             }
         };
 
         let request = serde_json::from_str::<ParRequest>(&value).unwrap();
 
         if request.client_id != *client_id {
-            return Err(("Invalid client id".to_string(), StatusCode::BAD_REQUEST))
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequestUri, "Invalid client id"))
         }
 
-        if let Err(_) = conn.del::<String, String>(uri.clone()).await {
-            return Err(("Failed to delete URI".to_string(), StatusCode::BAD_REQUEST))
+        if conn.del::<String, String>(uri.clone()).await.is_err() {
+            return Err(ApiError::new("Failed to delete URI".to_string(), StatusCode::INTERNAL_SERVER_ERROR))
         }
 
         Ok(request)
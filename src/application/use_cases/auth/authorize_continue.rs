@@ -2,40 +2,51 @@ use std::sync::Arc;
 use actix_web::http::StatusCode;
 use redis::AsyncCommands;
 use uuid::Uuid;
+use deadpool_redis::Connection;
 use crate::adapters::spi::cache::redis::RedisCache;
 use crate::adapters::spi::gateways::idp::IdpGateway;
 use crate::adapters::spi::repositories::oauth_consent::OAuthConsentRepository;
 use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
 use crate::application::api::use_case::UseCaseInterface;
 use crate::application::spi::repository::RepositoryInterface;
-use crate::domain::idp::IdpVerifyCredentialRequest;
+use crate::application::use_cases::auth::authorization_response::AuthorizationResponseUseCase;
+use crate::application::use_cases::auth::device_authorization::{load_device_data, store_device_data};
+use crate::application::use_cases::auth::device_verify::device_page_url;
+use crate::domain::idp::{IdpVerifyCredentialRequest, IdpVerifyCredentialResponse};
 use crate::domain::oauth_session::OauthSession;
+use crate::dto::auth::authorize::authorization_response::AuthorizationResponseRequest;
 use crate::dto::auth::authorize::request::AuthorizeRequest;
+use crate::dto::auth::authorize::response::AuthorizeResponse;
 use crate::dto::auth::authorize::token_data::TokenData;
+use crate::dto::auth::device_authorization::device_data::DeviceData;
 use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::hasher::hash_sha256;
+use crate::utils::oauth_error::OAuthErrorCode;
 
 pub struct AuthorizeContinueUseCase {
     cache: Arc<RedisCache>,
     repository: Arc<OAuthSessionRepository>,
     consent_repository: Arc<OAuthConsentRepository>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
     idp_gateway: Arc<IdpGateway>
 }
 
 impl UseCaseInterface for AuthorizeContinueUseCase {
     type Request = AuthorizeRequest;
-    type Response = String;
+    type Response = AuthorizeResponse;
 
     async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
         let arc_data = Arc::new(data);
 
         if let Err(e) = self.validate_query(arc_data.clone()).await {
-            return Err(ApiError::new(e, StatusCode::UNPROCESSABLE_ENTITY))
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, e).with_status(StatusCode::UNPROCESSABLE_ENTITY))
         }
 
         let result = self.get_session_and_user_uuid(arc_data.clone());
 
         if let Err(e) = result {
-            return Err(ApiError::new(e.0, e.1))
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, e.0).with_status(e.1))
         }
 
         let (session_uuid, user_uuid) = result.unwrap();
@@ -49,21 +60,31 @@ impl UseCaseInterface for AuthorizeContinueUseCase {
         let token = Uuid::new_v4();
 
         if arc_data.consent_id.is_some() {
-            if let Err(e) = self.validate_consent(&mut session, arc_data.clone()).await {
-                return Err(e)
-            }
-        } else {
-            if let Err(e) = self.check_auth_token(arc_data.auth_token.clone().unwrap()).await {
-                return Err(e)
+            if session.user_id != Some(user_uuid) {
+                return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "Session does not belong to the user").with_status(StatusCode::UNPROCESSABLE_ENTITY))
             }
 
-            if let Err(e) = self.save_user_and_consent(&mut session, user_uuid).await {
-                if e.status_code == StatusCode::SEE_OTHER {
-                    return Ok(ApiSuccess::new(e.error, StatusCode::SEE_OTHER))
-                }
-
-                return Err(e)
+            self.validate_consent(&mut session, arc_data.clone()).await?;
+        } else if let Some(auth_token) = arc_data.auth_token.clone() {
+            let login_id = hash_sha256(auth_token.as_str());
+            let verification = self.check_auth_token(auth_token).await?;
+
+            match self.save_user_and_consent(&mut session, user_uuid, login_id, verification).await? {
+                Some(url) if !url.is_empty() => return Ok(ApiSuccess::new(AuthorizeResponse::Redirect(url), StatusCode::SEE_OTHER)),
+                Some(_) => {
+                    let error = ApiError::oauth(OAuthErrorCode::ConsentRequired, "Consent is required but no consent page is configured");
+                    return self.respond(AuthorizationResponseRequest::error(
+                        session.client_id.clone().unwrap_or_default(),
+                        session.redirect_uri.clone().unwrap_or_default(),
+                        session.response_mode.clone(),
+                        session.state.as_deref().unwrap_or_default(),
+                        &error,
+                    )).await
+                },
+                None => {}
             }
+        } else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Missing auth token or consent id").with_status(StatusCode::UNPROCESSABLE_ENTITY))
         }
 
         let mut conn = self
@@ -71,6 +92,10 @@ impl UseCaseInterface for AuthorizeContinueUseCase {
             .get_pool()
             .await
             .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        if let Some(url) = self.approve_device(&mut conn, &session).await? {
+            return Ok(ApiSuccess::new(AuthorizeResponse::Redirect(url), StatusCode::SEE_OTHER))
+        }
         
         let token_data = TokenData {
             user_id: user_uuid,
@@ -79,26 +104,27 @@ impl UseCaseInterface for AuthorizeContinueUseCase {
 
         let value = serde_json::to_string(&token_data).unwrap();
 
-        if let Err(_) = conn.set_ex::<String, String, ()>(token.to_string(), value, 60 * 2).await {
+        if conn.set_ex::<String, String, ()>(token.to_string(), value, 60 * 2).await.is_err() {
             return Err(ApiError::new("Failed to store authorization code".to_string(), StatusCode::INTERNAL_SERVER_ERROR))
         }
 
-        let url = format!("{}?code={}&state={}", session.redirect_uri.unwrap(), token.to_string(), session.state.unwrap());
-        Ok(ApiSuccess::new(url, StatusCode::SEE_OTHER))
+        self.respond(AuthorizationResponseRequest::from_session(&session, vec![("code", token.to_string())])).await
     }
 }
 
 #[allow(unused)]
 impl AuthorizeContinueUseCase {
-    pub fn new(cache: Arc<RedisCache>, repository: Arc<OAuthSessionRepository>, consent_repository: Arc<OAuthConsentRepository>, idp_gateway: Arc<IdpGateway>) -> Self {
-        Self { cache, repository, consent_repository, idp_gateway }
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        consent_repository: Arc<OAuthConsentRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
+        idp_gateway: Arc<IdpGateway>,
+    ) -> Self {
+        Self { cache, repository, consent_repository, signing_key_repository, idp_gateway }
     }
 
     async fn validate_query(&self, data: Arc<AuthorizeRequest>) -> Result<(), String> {
-        if data.auth_token.is_none() && data.consent_id.is_none() {
-            return Err("Missing auth token or consent id".to_string())
-        }
-
         if data.session_id.is_none() {
             return Err("Missing session id".to_string())
         }
@@ -124,9 +150,9 @@ impl AuthorizeContinueUseCase {
     async fn check_is_consent_is_granted(&self, client_id: String, user_id: Uuid) -> Result<bool, ApiError> {
         if let Err(e) = self.consent_repository.get_by_client_and_user_id(
             client_id.clone(),
-            user_id.clone()
+            user_id
         ).await {
-            if e == String::from("Consent not found") {
+            if e == "Consent not found" {
                 return Ok(false)
             }
 
@@ -136,15 +162,27 @@ impl AuthorizeContinueUseCase {
         Ok(true)
     }
 
-    async fn save_user_and_consent(&self, session: &mut OauthSession, user_uuid: Uuid) -> Result<(), ApiError> {
+    async fn save_user_and_consent(&self, session: &mut OauthSession, user_uuid: Uuid, login_id: String, verification: IdpVerifyCredentialResponse) -> Result<Option<String>, ApiError> {
         if session.user_id.is_some() {
-            return Err(ApiError::new("User already set for this session".to_string(), StatusCode::UNPROCESSABLE_ENTITY))
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "User already set for this session").with_status(StatusCode::UNPROCESSABLE_ENTITY))
+        }
+
+        if verification.user_id != Some(user_uuid) {
+            return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "Auth token does not belong to the user").with_status(StatusCode::UNPROCESSABLE_ENTITY))
         }
 
         session.user_id = Some(user_uuid);
+        session.auth_time = Some(verification.authenticated_at(chrono::Utc::now()));
+        session.acr = verification.acr;
+        session.amr = verification.amr;
+        session.login_id = Some(login_id);
 
         if let Err(e) =  self.repository.edit(session.id.unwrap(), session.clone(), vec![
             "user_id",
+            "auth_time",
+            "acr",
+            "amr",
+            "login_id",
         ]).await {
             return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
         }
@@ -153,35 +191,68 @@ impl AuthorizeContinueUseCase {
             if e {
                 session.consent_granted_at = Some(chrono::Utc::now().naive_utc());
             } else {
-                let url = std::env::var("CONSENT_PAGE_URL").unwrap_or_default();
-                return Err(ApiError::new(String::from(url), StatusCode::SEE_OTHER))
+                return Ok(Some(std::env::var("CONSENT_PAGE_URL").unwrap_or_default()))
             }
         } else {
             return Err(ApiError::new("Failed to check consent".to_string(), StatusCode::INTERNAL_SERVER_ERROR))
         }
         
-        Ok(())
+        Ok(None)
     }
 
     async fn validate_consent(&self, session: &mut OauthSession, data: Arc<AuthorizeRequest>) -> Result<(), ApiError> {
-        if let Ok(e) = self.consent_repository.get(data.consent_id.unwrap()).await {
-            if e.user_id.unwrap() != session.user_id.clone().unwrap() || e.client_id.unwrap() != session.client_id.clone().unwrap() {
-                return Err(ApiError::new("Invalid consent ID".to_string(), StatusCode::UNPROCESSABLE_ENTITY))
-            }
+        if let Ok(e) = self.consent_repository.get(data.consent_id.unwrap()).await
+            && (e.user_id.unwrap() != session.user_id.unwrap() || e.client_id.unwrap() != session.client_id.clone().unwrap()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid consent ID").with_status(StatusCode::UNPROCESSABLE_ENTITY))
         }
 
         Ok(())
     }
 
-    async fn check_auth_token(&self, auth_token: String) -> Result<(), ApiError> {
+    async fn approve_device(&self, conn: &mut Connection, session: &OauthSession) -> Result<Option<String>, ApiError> {
+        // The device is bound to the user verified for the session, never to the query.
+        let (Some(session_uuid), Some(user_uuid)) = (session.id, session.user_id) else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Session is not authenticated").with_status(StatusCode::UNPROCESSABLE_ENTITY))
+        };
+
+        let Ok(device_code_hash) = conn.get::<String, Option<String>>(format!("device_session:{}", session_uuid)).await else {
+            return Err(ApiError::new("Failed to read device session".to_string(), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let Some(device_code_hash) = device_code_hash else {
+            return Ok(None)
+        };
+
+        let Ok(Some(mut device_data)) = load_device_data(conn, &device_code_hash).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::ExpiredToken, "Device code expired"))
+        };
+
+        device_data.status = DeviceData::STATUS_APPROVED;
+        device_data.user_id = Some(user_uuid);
+
+        store_device_data(conn, &device_code_hash, &device_data).await
+            .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        if conn.del::<String, ()>(format!("device_session:{}", session_uuid)).await.is_err() {
+            return Err(ApiError::new("Failed to delete device session".to_string(), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        Ok(Some(format!("{}?status=approved", device_page_url())))
+    }
+
+    async fn check_auth_token(&self, auth_token: String) -> Result<IdpVerifyCredentialResponse, ApiError> {
         let Ok(result) = self.idp_gateway.verify_auth_token_v1(IdpVerifyCredentialRequest{ token: auth_token }).await else {
-            return Err(ApiError::new("Invalid auth token".to_string(), StatusCode::UNPROCESSABLE_ENTITY))
+            return Err(ApiError::oauth(OAuthErrorCode::LoginRequired, "Invalid auth token").with_status(StatusCode::UNPROCESSABLE_ENTITY))
         };
 
-        if !result {
-            return Err(ApiError::new("Invalid auth token".to_string(), StatusCode::UNPROCESSABLE_ENTITY))
+        if !result.verified {
+            return Err(ApiError::oauth(OAuthErrorCode::LoginRequired, "Invalid auth token").with_status(StatusCode::UNPROCESSABLE_ENTITY))
         }
 
-        Ok(())
+        Ok(result)
+    }
+
+    async fn respond(&self, request: AuthorizationResponseRequest) -> Result<ApiSuccess<AuthorizeResponse>, ApiError> {
+        AuthorizationResponseUseCase::new(self.signing_key_repository.clone()).handle(request).await
     }
 }
\ No newline at end of file
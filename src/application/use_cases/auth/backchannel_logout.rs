@@ -0,0 +1,256 @@
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::gateways::logout::LogoutGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_logout_delivery::OAuthLogoutDeliveryRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::domain::oauth_logout_delivery::OauthLogoutDelivery;
+use crate::domain::oauth_session::OauthSession;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::signing_key::SigningKey;
+use crate::utils::token::generate_logout_token;
+
+pub const MAX_ATTEMPTS: i32 = 3;
+pub const RETRY_DELAY_SECONDS: u64 = 2;
+pub const RESUME_INTERVAL_SECONDS: u64 = 60;
+pub const PENDING_STALE_SECONDS: i64 = 60;
+pub const RESUME_BATCH_SIZE: i64 = 100;
+
+pub struct BackchannelLogoutUseCase {
+    client_repository: Arc<OAuthClientRepository>,
+    session_repository: Arc<OAuthSessionRepository>,
+    delivery_repository: Arc<OAuthLogoutDeliveryRepository>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    logout_gateway: Arc<LogoutGateway>,
+}
+
+impl UseCaseInterface for BackchannelLogoutUseCase {
+    type Request = Vec<OauthSession>;
+    type Response = Vec<OauthLogoutDelivery>;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let Ok(key) = self.signing_key_repository.get_active().await else {
+            return Err(ApiError::new(String::from("Active signing key not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let signing_key = SigningKey::from_model(&key).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        let mut deliveries = vec![];
+
+        for session in data {
+            let (Some(session_id), Some(user_id), Some(client_id)) = (session.id, session.user_id, session.client_id) else {
+                continue;
+            };
+
+            let client = match self.client_repository.get_by_slug(client_id.clone()).await {
+                Ok(e) => e,
+                Err(e) => {
+                    log::warn!("Skipping back-channel logout of session {} for client {}: {}", session_id, client_id, e);
+                    continue;
+                }
+            };
+
+            let Some(logout_uri) = client.backchannel_logout_uri.filter(|e| !e.is_empty()) else {
+                continue;
+            };
+
+            let logout_token = generate_logout_token(
+                chrono::Utc::now(),
+                jwt_iss.clone(),
+                session_id.to_string(),
+                user_id,
+                client_id.clone(),
+                &signing_key
+            );
+
+            let delivery = match self.delivery_repository.insert(OauthLogoutDelivery {
+                id: None,
+                session_id: Some(session_id),
+                client_id: Some(client_id.clone()),
+                logout_uri: Some(logout_uri),
+                status: Some(OauthLogoutDelivery::STATUS_PENDING),
+                attempts: None,
+                response_status: None,
+                last_error: None,
+                delivered_at: None,
+                created_at: None,
+                updated_at: None,
+            }).await {
+                Ok(e) => e,
+                Err(e) => {
+                    log::warn!("Failed to record back-channel logout of session {} for client {}: {}", session_id, client_id, e);
+                    continue;
+                }
+            };
+
+            match logout_token {
+                Ok(logout_token) => {
+                    actix_web::rt::spawn(Self::deliver(
+                        self.delivery_repository.clone(),
+                        self.logout_gateway.clone(),
+                        delivery.clone(),
+                        logout_token,
+                    ));
+                }
+                Err(e) => Self::fail(&self.delivery_repository, delivery.clone(), format!("Failed to generate logout token: {}", e)).await,
+            }
+
+            deliveries.push(delivery);
+        }
+
+        Ok(ApiSuccess::new(deliveries, StatusCode::ACCEPTED))
+    }
+}
+
+impl BackchannelLogoutUseCase {
+    pub fn new(
+        client_repository: Arc<OAuthClientRepository>,
+        session_repository: Arc<OAuthSessionRepository>,
+        delivery_repository: Arc<OAuthLogoutDeliveryRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
+        logout_gateway: Arc<LogoutGateway>,
+    ) -> Self {
+        Self { client_repository, session_repository, delivery_repository, signing_key_repository, logout_gateway }
+    }
+
+    /// Picks up deliveries left pending by a previous process and sends them again with a fresh logout token.
+    pub async fn resume_pending(&self) -> Result<usize, ApiError> {
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let stale_before = chrono::Utc::now().naive_utc() - chrono::Duration::seconds(PENDING_STALE_SECONDS);
+
+        let deliveries = self.delivery_repository.claim_pending(stale_before, RESUME_BATCH_SIZE).await
+            .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        if deliveries.is_empty() {
+            return Ok(0);
+        }
+
+        let Ok(key) = self.signing_key_repository.get_active().await else {
+            return Err(ApiError::new(String::from("Active signing key not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let signing_key = SigningKey::from_model(&key).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        let total = deliveries.len();
+
+        for delivery in deliveries {
+            let (Some(session_id), Some(client_id)) = (delivery.session_id, delivery.client_id.clone()) else {
+                continue;
+            };
+
+            let user_id = match self.session_repository.get(session_id).await {
+                Ok(e) => e.user_id,
+                Err(e) => {
+                    Self::fail(&self.delivery_repository, delivery, e).await;
+                    continue;
+                }
+            };
+
+            let Some(user_id) = user_id else {
+                Self::fail(&self.delivery_repository, delivery, String::from("Session has no user")).await;
+                continue;
+            };
+
+            match generate_logout_token(chrono::Utc::now(), jwt_iss.clone(), session_id.to_string(), user_id, client_id, &signing_key) {
+                Ok(logout_token) => {
+                    actix_web::rt::spawn(Self::deliver(
+                        self.delivery_repository.clone(),
+                        self.logout_gateway.clone(),
+                        delivery,
+                        logout_token,
+                    ));
+                }
+                Err(e) => Self::fail(&self.delivery_repository, delivery, format!("Failed to generate logout token: {}", e)).await,
+            }
+        }
+
+        Ok(total)
+    }
+
+    async fn fail(repository: &OAuthLogoutDeliveryRepository, mut delivery: OauthLogoutDelivery, error: String) {
+        log::warn!("Back-channel logout delivery {} failed: {}", delivery.id.unwrap_or_default(), error);
+
+        delivery.status = Some(OauthLogoutDelivery::STATUS_FAILED);
+        delivery.last_error = Some(error);
+        delivery.updated_at = Some(chrono::Utc::now().naive_utc());
+
+        if let Err(e) = repository.edit(delivery.id.unwrap(), delivery, vec!["status", "last_error", "updated_at"]).await {
+            log::warn!("Failed to record back-channel logout failure: {}", e);
+        }
+    }
+
+    async fn deliver(
+        repository: Arc<OAuthLogoutDeliveryRepository>,
+        gateway: Arc<LogoutGateway>,
+        mut delivery: OauthLogoutDelivery,
+        logout_token: String,
+    ) {
+        let first_attempt = delivery.attempts.unwrap_or_default() + 1;
+
+        for attempt in first_attempt..=MAX_ATTEMPTS.max(first_attempt) {
+            let result = gateway.post_backchannel_logout_v1(delivery.logout_uri.clone().unwrap(), logout_token.clone()).await;
+
+            delivery.attempts = Some(attempt);
+            delivery.updated_at = Some(chrono::Utc::now().naive_utc());
+
+            match result {
+                Ok(code) if (200..300).contains(&code) => {
+                    delivery.status = Some(OauthLogoutDelivery::STATUS_DELIVERED);
+                    delivery.response_status = Some(code as i32);
+                    delivery.last_error = None;
+                    delivery.delivered_at = delivery.updated_at;
+                }
+                Ok(code) => {
+                    delivery.response_status = Some(code as i32);
+                    delivery.last_error = Some(format!("Unexpected status code {}", code));
+                }
+                Err(e) => {
+                    delivery.response_status = None;
+                    delivery.last_error = Some(e);
+                }
+            }
+
+            let delivered = delivery.status == Some(OauthLogoutDelivery::STATUS_DELIVERED);
+            let last_attempt = attempt >= MAX_ATTEMPTS;
+
+            if !delivered && last_attempt {
+                delivery.status = Some(OauthLogoutDelivery::STATUS_FAILED);
+
+                log::warn!(
+                    "Back-channel logout delivery {} to {} failed after {} attempts: {}",
+                    delivery.id.unwrap_or_default(),
+                    delivery.logout_uri.clone().unwrap_or_default(),
+                    attempt,
+                    delivery.last_error.clone().unwrap_or_default()
+                );
+            }
+
+            if let Err(e) = repository.edit(
+                delivery.id.unwrap(),
+                delivery.clone(),
+                vec!["status", "attempts", "response_status", "last_error", "delivered_at", "updated_at"]
+            ).await {
+                log::warn!("Failed to record back-channel logout delivery {}: {}", delivery.id.unwrap_or_default(), e);
+                return;
+            }
+
+            if delivered || last_attempt {
+                return;
+            }
+
+            actix_web::rt::time::sleep(Duration::from_secs(RETRY_DELAY_SECONDS << (attempt - 1))).await;
+        }
+    }
+}
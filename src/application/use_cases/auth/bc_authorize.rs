@@ -0,0 +1,252 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use deadpool_redis::Connection;
+use redis::AsyncCommands;
+use uuid::Uuid;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::idp::IdpGateway;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
+use crate::application::spi::repository::RepositoryInterface;
+use crate::domain::idp::IdpBackchannelAuthenticationRequest;
+use crate::domain::oauth_client::OauthClient;
+use crate::domain::oauth_session::OauthSession;
+use crate::dto::auth::bc_authorize::ciba_data::{CibaData, CibaPing};
+use crate::dto::auth::bc_authorize::request::BcAuthorizeRequest;
+use crate::dto::auth::bc_authorize::response::BcAuthorizeResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::hasher::hash_sha256;
+use crate::utils::token::generate_auth_req_id;
+
+pub const GRANT_TYPE: &str = "urn:openid:params:grant-type:ciba";
+pub const DELIVERY_MODE_POLL: &str = "poll";
+pub const DELIVERY_MODE_PING: &str = "ping";
+pub const DELIVERY_MODES_SUPPORTED: [&str; 2] = [DELIVERY_MODE_POLL, DELIVERY_MODE_PING];
+pub const AUTH_REQ_ID_EXPIRES_IN: i64 = 10 * 60;
+pub const POLLING_INTERVAL: i64 = 5;
+
+pub struct BcAuthorizeUseCase {
+    cache: Arc<RedisCache>,
+    repository: Arc<OAuthSessionRepository>,
+    token_repository: Arc<OAuthTokenRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    idp_gateway: Arc<IdpGateway>,
+    jwks_gateway: Arc<JwksGateway>,
+}
+
+impl UseCaseInterface for BcAuthorizeUseCase {
+    type Request = BcAuthorizeRequest;
+    type Response = BcAuthorizeResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Ok(client) = self.client_repository.get_by_slug(data.client_id.clone()).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClient, "Invalid client"));
+        };
+
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(&data),
+            allow_public: false,
+        }).await?;
+
+        if !client.allows_grant_type(GRANT_TYPE) {
+            return Err(ApiError::oauth(OAuthErrorCode::UnauthorizedClient, "Grant type not allowed for this client"));
+        }
+
+        let is_ping = self.validate_delivery_mode(&data, &client)?;
+        let scopes = self.get_scopes(&data, &client)?;
+        let user_id = self.get_user_id(&data).await?;
+
+        let expires_in = data.requested_expiry
+            .filter(|e| *e > 0)
+            .map_or(AUTH_REQ_ID_EXPIRES_IN, |e| e.min(AUTH_REQ_ID_EXPIRES_IN));
+
+        let mut session = match self.repository.insert(OauthSession {
+            id: None,
+            client_id: Some(data.client_id.clone()),
+            user_id: Some(user_id),
+            scopes: Some(scopes.clone()),
+            redirect_uri: None,
+            state: None,
+            response_type: None,
+            response_mode: None,
+            nonce: None,
+            code_challenge: None,
+            code_challenge_method: None,
+            status: Some(OauthSession::STATUS_PENDING),
+            consent_granted_at: None,
+            auth_time: None,
+            acr: None,
+            amr: None,
+            login_id: None,
+            created_at: None,
+            updated_at: None,
+        }).await {
+            Ok(e) => e,
+            Err(_) => return Err(ApiError::new(String::from("Failed to create OAuth session"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let Some(session_id) = session.id else {
+            return Err(ApiError::new(String::from("Failed to create OAuth session"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let auth_req_id = generate_auth_req_id();
+        let auth_req_id_hash = hash_sha256(auth_req_id.as_str());
+
+        let ciba_data = CibaData {
+            client_id: data.client_id.clone(),
+            session_id,
+            user_id,
+            status: CibaData::STATUS_PENDING,
+            interval: POLLING_INTERVAL,
+            expires_at: chrono::Utc::now().timestamp() + expires_in,
+            last_polled_at: None,
+            ping: data.client_notification_token.clone().filter(|_| is_ping).map(|e| CibaPing {
+                auth_req_id: auth_req_id.clone(),
+                client_notification_token: e,
+            }),
+        };
+
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        store_ciba_data(&mut conn, &auth_req_id_hash, &ciba_data).await
+            .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        if conn.set_ex::<String, String, ()>(format!("ciba_session:{}", session_id), auth_req_id_hash, expires_in as u64).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to store backchannel session"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        if let Err(e) = self.idp_gateway.request_backchannel_authentication_v1(IdpBackchannelAuthenticationRequest {
+            session_id,
+            user_id,
+            client_id: data.client_id.clone(),
+            scopes,
+            binding_message: data.binding_message.clone(),
+            expires_in,
+        }).await {
+            session.status = Some(OauthSession::STATUS_ENDED);
+            let _ = self.repository.edit(session_id, session, vec!["status"]).await;
+
+            return Err(e);
+        }
+
+        Ok(ApiSuccess::new(BcAuthorizeResponse {
+            auth_req_id,
+            expires_in,
+            interval: (!is_ping).then_some(POLLING_INTERVAL),
+        }, StatusCode::OK))
+    }
+}
+
+impl BcAuthorizeUseCase {
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        token_repository: Arc<OAuthTokenRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        idp_gateway: Arc<IdpGateway>,
+        jwks_gateway: Arc<JwksGateway>,
+    ) -> Self {
+        Self { cache, repository, token_repository, client_repository, idp_gateway, jwks_gateway }
+    }
+
+    fn validate_delivery_mode(&self, data: &BcAuthorizeRequest, client: &OauthClient) -> Result<bool, ApiError> {
+        let mode = client.backchannel_token_delivery_mode.clone().unwrap_or(String::from(DELIVERY_MODE_POLL));
+
+        if mode == DELIVERY_MODE_POLL {
+            return Ok(false);
+        }
+
+        if mode != DELIVERY_MODE_PING || client.backchannel_client_notification_endpoint.as_ref().is_none_or(|e| e.is_empty()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Client backchannel token delivery is misconfigured"));
+        }
+
+        if data.client_notification_token.as_ref().is_none_or(|e| e.is_empty()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Missing client_notification_token"));
+        }
+
+        Ok(true)
+    }
+
+    fn get_scopes(&self, data: &BcAuthorizeRequest, client: &OauthClient) -> Result<Vec<String>, ApiError> {
+        let allowed_scopes = client.scopes.clone().unwrap_or_default();
+        let scopes = data.scope.split_whitespace().map(|e| e.to_string()).collect::<Vec<String>>();
+
+        if !scopes.iter().any(|e| e == "openid") {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidScope, "Scope openid is required"));
+        }
+
+        if let Some(e) = scopes.iter().find(|e| !allowed_scopes.contains(e)) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidScope, format!("Scope {} is not allowed for this client", e)));
+        }
+
+        Ok(scopes)
+    }
+
+    async fn get_user_id(&self, data: &BcAuthorizeRequest) -> Result<Uuid, ApiError> {
+        match (data.login_hint.as_ref(), data.id_token_hint.as_ref()) {
+            (Some(login_hint), None) => {
+                let Ok(user_id) = Uuid::parse_str(login_hint) else {
+                    return Err(ApiError::oauth(OAuthErrorCode::UnknownUserId, "Unknown user"));
+                };
+
+                let Ok(user) = self.idp_gateway.get_user_by_id_v1(user_id).await else {
+                    return Err(ApiError::oauth(OAuthErrorCode::UnknownUserId, "Unknown user"));
+                };
+
+                Ok(user.id)
+            },
+            (None, Some(id_token_hint)) => {
+                let Ok(token) = self.token_repository.get_by_id_token(hash_sha256(id_token_hint.as_str())).await else {
+                    return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid id_token_hint"));
+                };
+
+                let Some(session_id) = token.session_id else {
+                    return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid id_token_hint"));
+                };
+
+                let Ok(session) = self.repository.get(session_id).await else {
+                    return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid id_token_hint"));
+                };
+
+                session.user_id.ok_or(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid id_token_hint"))
+            },
+            _ => Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Exactly one of login_hint or id_token_hint is required"))
+        }
+    }
+}
+
+pub async fn load_ciba_data(conn: &mut Connection, auth_req_id_hash: &str) -> Result<Option<CibaData>, String> {
+    let Ok(value) = conn.get::<String, Option<String>>(format!("ciba:{}", auth_req_id_hash)).await else {
+        return Err(String::from("Failed to read authentication request"));
+    };
+
+    let Some(value) = value else {
+        return Ok(None);
+    };
+
+    match serde_json::from_str::<CibaData>(&value) {
+        Ok(e) => Ok(Some(e)),
+        Err(_) => Err(String::from("Failed to parse authentication request data"))
+    }
+}
+
+pub async fn store_ciba_data(conn: &mut Connection, auth_req_id_hash: &str, data: &CibaData) -> Result<(), String> {
+    let Ok(value) = serde_json::to_string(data) else {
+        return Err(String::from("Failed to serialize authentication request data"));
+    };
+
+    if conn.set_ex::<String, String, ()>(format!("ciba:{}", auth_req_id_hash), value, data.expires_in().max(1) as u64).await.is_err() {
+        return Err(String::from("Failed to store authentication request"));
+    }
+
+    Ok(())
+}
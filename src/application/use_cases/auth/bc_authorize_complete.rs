@@ -0,0 +1,135 @@
+use std::sync::Arc;
+use std::time::Duration;
+use actix_web::http::StatusCode;
+use redis::AsyncCommands;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::ciba::CibaGateway;
+use crate::adapters::spi::gateways::idp::IdpGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::auth::bc_authorize::{load_ciba_data, store_ciba_data};
+use crate::domain::idp::IdpVerifyCredentialRequest;
+use crate::domain::oauth_session::OauthSession;
+use crate::dto::auth::bc_authorize::ciba_data::{CibaData, CibaPing};
+use crate::dto::auth::bc_authorize_complete::request::BcAuthorizeCompleteRequest;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+
+pub const PING_MAX_ATTEMPTS: u32 = 3;
+pub const PING_RETRY_DELAY_SECONDS: u64 = 2;
+
+pub struct BcAuthorizeCompleteUseCase {
+    cache: Arc<RedisCache>,
+    repository: Arc<OAuthSessionRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    idp_gateway: Arc<IdpGateway>,
+    ciba_gateway: Arc<CibaGateway>,
+}
+
+impl UseCaseInterface for BcAuthorizeCompleteUseCase {
+    type Request = BcAuthorizeCompleteRequest;
+    type Response = ();
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Ok(verification) = self.idp_gateway.verify_auth_token_v1(IdpVerifyCredentialRequest { token: data.auth_token.clone() }).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "Invalid auth token").with_status(StatusCode::UNPROCESSABLE_ENTITY))
+        };
+
+        if !verification.verified {
+            return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "Invalid auth token").with_status(StatusCode::UNPROCESSABLE_ENTITY))
+        }
+
+        if verification.user_id != Some(data.user_id) {
+            return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "Auth token does not belong to the user").with_status(StatusCode::UNPROCESSABLE_ENTITY))
+        }
+
+        let Ok(mut session) = self.repository.get(data.session_id).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Session not found"))
+        };
+
+        if session.status != Some(OauthSession::STATUS_PENDING) || session.user_id != Some(data.user_id) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Authentication request already handled"));
+        }
+
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let Ok(Some(auth_req_id_hash)) = conn.get::<String, Option<String>>(format!("ciba_session:{}", data.session_id)).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::ExpiredToken, "Authentication request expired"))
+        };
+
+        let Ok(Some(mut ciba_data)) = load_ciba_data(&mut conn, &auth_req_id_hash).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::ExpiredToken, "Authentication request expired"))
+        };
+
+        if data.approved {
+            ciba_data.status = CibaData::STATUS_APPROVED;
+            session.status = Some(OauthSession::STATUS_ACTIVE);
+            session.consent_granted_at = Some(chrono::Utc::now().naive_utc());
+            session.auth_time = Some(verification.authenticated_at(chrono::Utc::now()));
+            session.acr = verification.acr;
+            session.amr = verification.amr;
+        } else {
+            ciba_data.status = CibaData::STATUS_DENIED;
+            session.status = Some(OauthSession::STATUS_ENDED);
+        }
+
+        if let Err(e) = self.repository.edit(data.session_id, session, vec!["status", "consent_granted_at", "auth_time", "acr", "amr"]).await {
+            return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR));
+        }
+
+        store_ciba_data(&mut conn, &auth_req_id_hash, &ciba_data).await
+            .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        if conn.del::<String, ()>(format!("ciba_session:{}", data.session_id)).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to delete backchannel session"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        if let Some(ping) = ciba_data.ping {
+            self.ping(ciba_data.client_id, ping).await;
+        }
+
+        Ok(ApiSuccess::new((), StatusCode::OK))
+    }
+}
+
+impl BcAuthorizeCompleteUseCase {
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        idp_gateway: Arc<IdpGateway>,
+        ciba_gateway: Arc<CibaGateway>,
+    ) -> Self {
+        Self { cache, repository, client_repository, idp_gateway, ciba_gateway }
+    }
+
+    async fn ping(&self, client_id: String, ping: CibaPing) {
+        let Ok(client) = self.client_repository.get_by_slug(client_id).await else {
+            return;
+        };
+
+        let Some(endpoint) = client.backchannel_client_notification_endpoint.filter(|e| !e.is_empty()) else {
+            return;
+        };
+
+        let ciba_gateway = self.ciba_gateway.clone();
+
+        actix_web::rt::spawn(async move {
+            for attempt in 1..=PING_MAX_ATTEMPTS {
+                match ciba_gateway.post_ping_notification_v1(endpoint.clone(), ping.client_notification_token.clone(), ping.auth_req_id.clone()).await {
+                    Ok(e) if (200..300).contains(&e) => return,
+                    Ok(e) => log::warn!("Ping notification to {} failed with status {} (attempt {})", endpoint, e, attempt),
+                    Err(e) => log::warn!("Ping notification to {} failed: {} (attempt {})", endpoint, e, attempt),
+                }
+
+                if attempt < PING_MAX_ATTEMPTS {
+                    actix_web::rt::time::sleep(Duration::from_secs(PING_RETRY_DELAY_SECONDS << (attempt - 1))).await;
+                }
+            }
+        });
+    }
+}
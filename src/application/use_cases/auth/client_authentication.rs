@@ -0,0 +1,372 @@
+use std::env;
+use std::net::IpAddr;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use actix_web::web;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use jsonwebtoken::jwk::JwkSet;
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::domain::oauth_client::OauthClient;
+use crate::dto::auth::client_authentication::client_assertion::{ClientAssertionClaims, CLIENT_ASSERTION_TYPE};
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::encryption::decrypt;
+use crate::utils::hasher::{hash_sha256, verify_secret};
+use crate::utils::mtls::{distinguished_name_matches, SELF_SIGNED_TLS_CLIENT_AUTH, TLS_CLIENT_AUTH};
+
+pub const CLIENT_SECRET_BASIC: &str = "client_secret_basic";
+pub const CLIENT_SECRET_POST: &str = "client_secret_post";
+pub const CLIENT_SECRET_JWT: &str = "client_secret_jwt";
+pub const PRIVATE_KEY_JWT: &str = "private_key_jwt";
+pub const AUTH_METHOD_NONE: &str = "none";
+pub const TOKEN_ENDPOINT_AUTH_METHODS_SUPPORTED: [&str; 6] = [CLIENT_SECRET_BASIC, CLIENT_SECRET_POST, CLIENT_SECRET_JWT, PRIVATE_KEY_JWT, TLS_CLIENT_AUTH, SELF_SIGNED_TLS_CLIENT_AUTH];
+pub const CLIENT_SECRET_JWT_ALGORITHMS: [Algorithm; 3] = [Algorithm::HS256, Algorithm::HS384, Algorithm::HS512];
+pub const PRIVATE_KEY_JWT_ALGORITHMS: [Algorithm; 9] = [
+    Algorithm::RS256, Algorithm::RS384, Algorithm::RS512,
+    Algorithm::PS256, Algorithm::PS384, Algorithm::PS512,
+    Algorithm::ES256, Algorithm::ES384, Algorithm::EdDSA,
+];
+pub const CLIENT_JWKS_EXPIRES_IN: u64 = 5 * 60;
+pub const CLIENT_JWKS_REFRESH_INTERVAL: u64 = 60;
+const CLIENT_ASSERTION_LEEWAY: i64 = 60;
+
+pub struct ClientAuthenticationUseCase {
+    cache: Arc<RedisCache>,
+    jwks_gateway: Arc<JwksGateway>,
+}
+
+impl UseCaseInterface for ClientAuthenticationUseCase {
+    type Request = ClientAuthenticationRequest;
+    type Response = ();
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let client = &data.client;
+        let credentials = &data.credentials;
+
+        let Ok(basic) = credentials.basic() else {
+            return Err(Self::invalid_client());
+        };
+
+        let presented = [basic.is_some(), credentials.client_secret.is_some(), credentials.client_assertion.is_some()];
+
+        if presented.iter().filter(|e| **e).count() > 1 {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Multiple client authentication methods"));
+        }
+
+        if client.is_public() {
+            if !data.allow_public || presented.iter().any(|e| *e) {
+                return Err(Self::invalid_client());
+            }
+
+            return Ok(ApiSuccess::new((), StatusCode::OK));
+        }
+
+        let authenticated = match client.token_endpoint_auth_method.as_deref().unwrap_or(CLIENT_SECRET_POST) {
+            CLIENT_SECRET_BASIC => match basic {
+                Some((client_id, client_secret)) => client.slug.as_ref() == Some(&client_id) && Self::secret_matches(client, Some(client_secret)).await,
+                None => false,
+            },
+            CLIENT_SECRET_POST => Self::secret_matches(client, credentials.client_secret.clone()).await,
+            CLIENT_SECRET_JWT => self.validate_client_assertion(client, credentials, &CLIENT_SECRET_JWT_ALGORITHMS).await.is_ok(),
+            PRIVATE_KEY_JWT => self.validate_client_assertion(client, credentials, &PRIVATE_KEY_JWT_ALGORITHMS).await.is_ok(),
+            TLS_CLIENT_AUTH => Self::validate_tls_client_auth(client, credentials),
+            SELF_SIGNED_TLS_CLIENT_AUTH => Self::validate_self_signed_tls_client_auth(client, credentials),
+            _ => false,
+        };
+
+        if !authenticated {
+            return Err(Self::invalid_client());
+        }
+
+        Ok(ApiSuccess::new((), StatusCode::OK))
+    }
+}
+
+impl ClientAuthenticationUseCase {
+    pub fn new(cache: Arc<RedisCache>, jwks_gateway: Arc<JwksGateway>) -> Self {
+        Self { cache, jwks_gateway }
+    }
+
+    fn invalid_client() -> ApiError {
+        ApiError::oauth(OAuthErrorCode::InvalidClient, "Invalid client")
+    }
+
+    async fn secret_matches(client: &OauthClient, client_secret: Option<String>) -> bool {
+        let Some(client_secret) = client_secret else {
+            return false;
+        };
+
+        let hashes = [Some(&client.secret_hash), client.in_secret_overlap().then_some(&client.previous_secret_hash)]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .cloned()
+            .collect::<Vec<String>>();
+
+        web::block(move || hashes.iter().any(|e| verify_secret(&client_secret, e))).await.unwrap_or(false)
+    }
+
+    async fn validate_client_assertion(&self, client: &OauthClient, credentials: &ClientCredentials, algorithms: &[Algorithm]) -> Result<(), String> {
+        if credentials.client_assertion_type.as_deref() != Some(CLIENT_ASSERTION_TYPE) {
+            return Err(String::from("Invalid client assertion type"));
+        }
+
+        let Some(assertion) = credentials.client_assertion.as_ref() else {
+            return Err(String::from("Missing client assertion"));
+        };
+
+        let Ok(header) = decode_header(assertion) else {
+            return Err(String::from("Invalid client assertion header"));
+        };
+
+        if !algorithms.contains(&header.alg) {
+            return Err(String::from("Unsupported client assertion algorithm"));
+        }
+
+        let decoding_keys = self.get_decoding_keys(client, header.alg, header.kid).await?;
+
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(String::from("JWT_ISSUER not found"));
+        };
+
+        let base_url = jwt_iss.trim_end_matches('/');
+        let audience = std::iter::once(jwt_iss.clone())
+            .chain(credentials.context.endpoint_paths.iter().map(|e| format!("{}{}", base_url, e)))
+            .collect::<Vec<String>>();
+
+        let client_id = client.slug.clone().unwrap_or_default();
+
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = CLIENT_ASSERTION_LEEWAY as u64;
+        validation.set_audience(&audience);
+        validation.set_issuer(&[&client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "sub", "aud"]);
+        validation.sub = Some(client_id.clone());
+
+        let Some(claims) = decoding_keys.iter().find_map(|e| decode::<ClientAssertionClaims>(assertion, e, &validation).ok().map(|e| e.claims)) else {
+            return Err(String::from("Invalid client assertion"));
+        };
+
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(String::from("Getting cache connection"));
+        };
+
+        let expires_in = (claims.exp - chrono::Utc::now().timestamp() + CLIENT_ASSERTION_LEEWAY).max(1) as u64;
+        let replay_key = format!("client_assertion_jti:{}", hash_sha256(format!("{}:{}", client_id, claims.jti).as_str()));
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(expires_in));
+
+        match conn.set_options::<String, i32, Option<String>>(replay_key, 1, options).await {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err(String::from("Client assertion has already been used")),
+            Err(_) => Err(String::from("Failed to store client assertion")),
+        }
+    }
+
+    pub async fn get_decoding_keys(&self, client: &OauthClient, alg: Algorithm, kid: Option<String>) -> Result<Vec<DecodingKey>, String> {
+        if CLIENT_SECRET_JWT_ALGORITHMS.contains(&alg) {
+            return [Some(&client.secret), client.in_secret_overlap().then_some(&client.previous_secret)]
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|e| decrypt(e).map(|e| DecodingKey::from_secret(e.as_bytes())))
+                .collect();
+        }
+
+        Ok(vec![self.get_client_key(client, kid).await?])
+    }
+
+    async fn get_client_key(&self, client: &OauthClient, kid: Option<String>) -> Result<DecodingKey, String> {
+        let find_key = |jwks: &JwkSet| match kid.as_ref() {
+            Some(kid) => jwks.find(kid).cloned(),
+            None if jwks.keys.len() == 1 => jwks.keys.first().cloned(),
+            None => None,
+        };
+
+        let jwk = if let Some(jwks) = client.jwks.as_ref() {
+            find_key(&jwks.0)
+        } else if let Some(jwks_uri) = client.jwks_uri.as_ref() {
+            match find_key(&self.get_remote_jwks(jwks_uri, false).await?) {
+                Some(e) => Some(e),
+                None => find_key(&self.get_remote_jwks(jwks_uri, true).await?),
+            }
+        } else {
+            return Err(String::from("Client has no registered keys"));
+        };
+
+        let Some(jwk) = jwk else {
+            return Err(String::from("Client key not found"));
+        };
+
+        DecodingKey::from_jwk(&jwk).map_err(|_| String::from("Invalid client key"))
+    }
+
+    async fn get_remote_jwks(&self, jwks_uri: &str, refresh: bool) -> Result<JwkSet, String> {
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(String::from("Getting cache connection"));
+        };
+
+        let cache_key = format!("client_jwks:{}", hash_sha256(jwks_uri));
+
+        if !refresh && let Ok(Some(value)) = conn.get::<&str, Option<String>>(&cache_key).await
+            && let Ok(jwks) = serde_json::from_str::<JwkSet>(&value) {
+            return Ok(jwks);
+        }
+
+        // Unknown kids force a refetch, so at most one fetch per interval goes out to each jwks_uri.
+        let refresh_key = format!("client_jwks_refresh:{}", hash_sha256(jwks_uri));
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(CLIENT_JWKS_REFRESH_INTERVAL));
+
+        match conn.set_options::<&str, i32, Option<String>>(&refresh_key, 1, options).await {
+            Ok(Some(_)) => {},
+            Ok(None) => return Err(String::from("Client JWKS was fetched recently")),
+            Err(_) => return Err(String::from("Failed to store client JWKS refresh")),
+        }
+
+        let jwks = self.jwks_gateway.get_jwks_v1(jwks_uri.to_string()).await?;
+
+        if let Ok(value) = serde_json::to_string(&jwks) {
+            let _ = conn.set_ex::<&str, String, ()>(&cache_key, value, CLIENT_JWKS_EXPIRES_IN).await;
+        }
+
+        Ok(jwks)
+    }
+
+    fn validate_tls_client_auth(client: &OauthClient, credentials: &ClientCredentials) -> bool {
+        let Some(certificate) = credentials.context.client_certificate.as_ref().filter(|e| e.trusted) else {
+            return false;
+        };
+
+        let Ok(identity) = certificate.identity() else {
+            return false;
+        };
+
+        if let Some(subject_dn) = client.tls_client_auth_subject_dn.as_ref() {
+            return distinguished_name_matches(subject_dn, &identity.subject);
+        }
+
+        if let Some(san_dns) = client.tls_client_auth_san_dns.as_ref() {
+            return identity.san_dns.iter().any(|e| e.eq_ignore_ascii_case(san_dns));
+        }
+
+        if let Some(san_uri) = client.tls_client_auth_san_uri.as_ref() {
+            return identity.san_uri.contains(san_uri);
+        }
+
+        if let Some(san_ip) = client.tls_client_auth_san_ip.as_ref() {
+            return san_ip.parse::<IpAddr>().is_ok_and(|e| identity.san_ip.contains(&e.to_string()));
+        }
+
+        if let Some(san_email) = client.tls_client_auth_san_email.as_ref() {
+            return identity.san_email.contains(san_email);
+        }
+
+        false
+    }
+
+    fn validate_self_signed_tls_client_auth(client: &OauthClient, credentials: &ClientCredentials) -> bool {
+        let Some(certificate) = credentials.context.client_certificate.as_ref() else {
+            return false;
+        };
+
+        client.tls_client_certificate_thumbprint.as_ref().is_some_and(|e| *e == certificate.thumbprint())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use crate::adapters::spi::cache::redis::RedisCache;
+    use crate::adapters::spi::gateways::jwks::JwksGateway;
+    use crate::application::api::use_case::UseCaseInterface;
+    use crate::application::spi::cache::CacheInterface;
+    use crate::domain::oauth_client::OauthClient;
+    use crate::dto::auth::client_authentication::request::{ClientAuthenticationContext, ClientAuthenticationRequest, ClientCredentials};
+    use crate::utils::hasher::hash_secret;
+    use crate::utils::oauth_error::OAuthErrorCode;
+    use super::{ClientAuthenticationUseCase, CLIENT_SECRET_BASIC, CLIENT_SECRET_POST};
+
+    const SECRET: &str = "s3cret";
+
+    // The pool connects lazily, and the secret methods never ask it for a connection.
+    fn use_case() -> ClientAuthenticationUseCase {
+        let cache = RedisCache { pool: RedisCache::connect(String::from("127.0.0.1"), String::from("6379"), String::from("0")) };
+
+        ClientAuthenticationUseCase::new(Arc::new(cache), Arc::new(JwksGateway::new()))
+    }
+
+    fn client(auth_method: Option<&str>) -> OauthClient {
+        OauthClient {
+            slug: Some(String::from("amaterasu")),
+            secret_hash: Some(hash_secret(SECRET).unwrap()),
+            token_endpoint_auth_method: auth_method.map(String::from),
+            ..OauthClient::default()
+        }
+    }
+
+    fn credentials(client_secret: Option<&str>, basic: Option<(&str, &str)>) -> ClientCredentials {
+        ClientCredentials {
+            client_id: String::from("amaterasu"),
+            client_secret: client_secret.map(String::from),
+            client_assertion_type: None,
+            client_assertion: None,
+            context: ClientAuthenticationContext {
+                authorization: basic.map(|(id, secret)| format!("Basic {}", STANDARD.encode(format!("{}:{}", id, secret)))),
+                ..ClientAuthenticationContext::default()
+            },
+        }
+    }
+
+    async fn authenticate(client: OauthClient, credentials: ClientCredentials, allow_public: bool) -> Result<(), OAuthErrorCode> {
+        use_case().handle(ClientAuthenticationRequest { client, credentials, allow_public }).await
+            .map(|_| ())
+            .map_err(|e| e.error)
+    }
+
+    #[actix_web::test]
+    async fn uses_the_registered_secret_method_only() {
+        let basic = client(Some(CLIENT_SECRET_BASIC));
+        assert_eq!(authenticate(basic.clone(), credentials(None, Some(("amaterasu", SECRET))), false).await, Ok(()));
+        assert_eq!(authenticate(basic.clone(), credentials(Some(SECRET), None), false).await, Err(OAuthErrorCode::InvalidClient));
+        assert_eq!(authenticate(basic, credentials(None, Some(("susanoo", SECRET))), false).await, Err(OAuthErrorCode::InvalidClient));
+
+        let post = client(Some(CLIENT_SECRET_POST));
+        assert_eq!(authenticate(post.clone(), credentials(Some(SECRET), None), false).await, Ok(()));
+        assert_eq!(authenticate(post.clone(), credentials(None, Some(("amaterasu", SECRET))), false).await, Err(OAuthErrorCode::InvalidClient));
+        assert_eq!(authenticate(post, credentials(Some("wrong"), None), false).await, Err(OAuthErrorCode::InvalidClient));
+    }
+
+    #[actix_web::test]
+    async fn defaults_to_client_secret_post_and_rejects_unknown_methods() {
+        assert_eq!(authenticate(client(None), credentials(Some(SECRET), None), false).await, Ok(()));
+        assert_eq!(authenticate(client(Some("unknown")), credentials(Some(SECRET), None), false).await, Err(OAuthErrorCode::InvalidClient));
+    }
+
+    #[actix_web::test]
+    async fn rejects_more_than_one_method() {
+        let result = authenticate(client(Some(CLIENT_SECRET_BASIC)), credentials(Some(SECRET), Some(("amaterasu", SECRET))), false).await;
+
+        assert_eq!(result, Err(OAuthErrorCode::InvalidRequest));
+    }
+
+    #[actix_web::test]
+    async fn public_clients_present_no_credentials_where_allowed() {
+        let public = OauthClient {
+            client_type: Some(String::from(OauthClient::TYPE_PUBLIC)),
+            ..client(None)
+        };
+
+        assert_eq!(authenticate(public.clone(), credentials(None, None), true).await, Ok(()));
+        assert_eq!(authenticate(public.clone(), credentials(None, None), false).await, Err(OAuthErrorCode::InvalidClient));
+        assert_eq!(authenticate(public, credentials(Some(SECRET), None), true).await, Err(OAuthErrorCode::InvalidClient));
+    }
+}
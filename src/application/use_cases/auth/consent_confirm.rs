@@ -8,6 +8,7 @@ use crate::domain::oauth_consent::OauthConsent;
 use crate::dto::auth::consent_confirm::request::ConsentConfirmRequest;
 use crate::dto::auth::consent_confirm::response::ConsentConfirmResponse;
 use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
 
 pub struct ConsentConfirmUseCase {
     repository: Arc<OAuthConsentRepository>,
@@ -21,27 +22,25 @@ impl UseCaseInterface for ConsentConfirmUseCase {
 
     async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
         let Ok(mut session) = self.session_repository.get(data.session_id).await else {
-            return Err(ApiError::new(String::from("Session not found"), actix_web::http::StatusCode::BAD_REQUEST))
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Session not found"))
         };
 
         if session.consent_granted_at.is_some() {
-            return Err(ApiError::new(String::from("Consent already granted for this session"), actix_web::http::StatusCode::BAD_REQUEST));
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Consent already granted for this session"));
         }
 
         let Ok(client) = self.client_repository.get_by_slug(session.client_id.clone().unwrap()).await else {
-            return Err(ApiError::new(String::from("Client not found"), actix_web::http::StatusCode::BAD_REQUEST))
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Client not found"))
         };
         
-        if let Err(e) = self.check_scopes(
+        self.check_scopes(
             client.scopes.clone().unwrap_or_default(),
             client.mandatory_scopes.clone().unwrap_or_default(),
             data.scopes.clone()
-        ) {
-            return Err(e);
-        }
+        )?;
         
         if session.user_id.is_none() || session.client_id.is_none() {
-            return Err(ApiError::new(String::from("Session is not authenticated"), actix_web::http::StatusCode::BAD_REQUEST));
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Session is not authenticated"));
         }
 
         let Ok(consent) = self.repository.insert(OauthConsent{
@@ -50,7 +49,7 @@ impl UseCaseInterface for ConsentConfirmUseCase {
             status: None,
             created_at: None,
             client_id: Some(session.client_id.clone().unwrap()),
-            user_id: Some(session.user_id.clone().unwrap()),
+            user_id: Some(session.user_id.unwrap()),
             updated_at: None,
         }).await else {
             return Err(ApiError::new(String::from("Failed to create consent"), actix_web::http::StatusCode::INTERNAL_SERVER_ERROR));
@@ -66,9 +65,9 @@ impl UseCaseInterface for ConsentConfirmUseCase {
         Ok(ApiSuccess::new(ConsentConfirmResponse {
             redirect_url: format!(
                 "/api/v1/auth/authorize?user_id={}&session_id={}&consent_id={}",
-                session.user_id.clone().unwrap(),
-                session.id.clone().unwrap(),
-                consent.id.clone().unwrap()
+                session.user_id.unwrap(),
+                session.id.unwrap(),
+                consent.id.unwrap()
             )
         }, actix_web::http::StatusCode::OK))
     }
@@ -82,13 +81,13 @@ impl ConsentConfirmUseCase {
     fn check_scopes(&self, scopes: Vec<String>, mandatory_scopes: Vec<String>, data_scopes: Vec<String>) -> Result<(), ApiError> {
         for scope in data_scopes.iter() {
             if !scopes.contains(scope) {
-                return Err(ApiError::new(format!("Scope '{}' is not allowed for this client", scope), actix_web::http::StatusCode::BAD_REQUEST));
+                return Err(ApiError::oauth(OAuthErrorCode::InvalidScope, format!("Scope '{}' is not allowed for this client", scope)));
             }
         }
 
         for scope in mandatory_scopes.iter() {
             if !data_scopes.contains(scope) {
-                return Err(ApiError::new(format!("Mandatory scope '{}' is missing", scope), actix_web::http::StatusCode::BAD_REQUEST));
+                return Err(ApiError::oauth(OAuthErrorCode::InvalidScope, format!("Mandatory scope '{}' is missing", scope)));
             }
         }
 
@@ -7,6 +7,7 @@ use crate::application::spi::repository::RepositoryInterface;
 use crate::dto::auth::consent_info::request::ConsentInfoRequest;
 use crate::dto::auth::consent_info::response::ConsentInfoResponse;
 use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
 
 pub struct ConsentInfoUseCase {
     repository: Arc<OAuthClientRepository>,
@@ -19,15 +20,15 @@ impl UseCaseInterface for ConsentInfoUseCase {
 
     async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
         let Ok(session) = self.session_repository.get(data.session_id).await else {
-            return Err(ApiError::new(String::from("Session not found"), StatusCode::BAD_REQUEST))
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Session not found"))
         };
 
         if session.consent_granted_at.is_some() {
-            return Err(ApiError::new(String::from("Consent already granted for this session"), StatusCode::BAD_REQUEST));
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Consent already granted for this session"));
         }
         
         let Ok(client) = self.repository.get_by_slug(session.client_id.clone().unwrap()).await else {
-            return Err(ApiError::new(String::from("Client not found"), StatusCode::BAD_REQUEST))
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Client not found"))
         };
         
         Ok(ApiSuccess::new(ConsentInfoResponse {
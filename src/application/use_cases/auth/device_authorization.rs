@@ -0,0 +1,150 @@
+use std::env;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use deadpool_redis::Connection;
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
+use crate::domain::oauth_client::OauthClient;
+use crate::dto::auth::device_authorization::device_data::DeviceData;
+use crate::dto::auth::device_authorization::request::DeviceAuthorizationRequest;
+use crate::dto::auth::device_authorization::response::DeviceAuthorizationResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::hasher::hash_sha256;
+use crate::utils::token::{generate_device_code, generate_user_code, USER_CODE_LENGTH};
+
+pub const GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+pub const DEVICE_CODE_EXPIRES_IN: i64 = 10 * 60;
+pub const POLLING_INTERVAL: i64 = 5;
+
+pub struct DeviceAuthorizationUseCase {
+    cache: Arc<RedisCache>,
+    client_repository: Arc<OAuthClientRepository>,
+    jwks_gateway: Arc<JwksGateway>,
+    verification_path: String,
+}
+
+impl UseCaseInterface for DeviceAuthorizationUseCase {
+    type Request = DeviceAuthorizationRequest;
+    type Response = DeviceAuthorizationResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let Ok(client) = self.client_repository.get_by_slug(data.client_id.clone()).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClient, "Invalid client"));
+        };
+
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(&data),
+            allow_public: true,
+        }).await?;
+
+        if !client.allows_grant_type(GRANT_TYPE) {
+            return Err(ApiError::oauth(OAuthErrorCode::UnauthorizedClient, "Grant type not allowed for this client"));
+        }
+
+        let scopes = self.get_scopes(&data, &client)?;
+
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let device_code = generate_device_code();
+        let user_code = generate_user_code();
+
+        let device_data = DeviceData {
+            client_id: data.client_id.clone(),
+            scopes,
+            user_code: user_code.clone(),
+            status: DeviceData::STATUS_PENDING,
+            session_id: None,
+            user_id: None,
+            interval: POLLING_INTERVAL,
+            expires_at: chrono::Utc::now().timestamp() + DEVICE_CODE_EXPIRES_IN,
+            last_polled_at: None,
+        };
+
+        let device_code_hash = hash_sha256(device_code.as_str());
+
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(DEVICE_CODE_EXPIRES_IN as u64));
+
+        let Ok(Some(_)) = conn.set_options::<String, String, Option<String>>(format!("user_code:{}", user_code), device_code_hash.clone(), options).await else {
+            return Err(ApiError::new(String::from("Failed to store user code"), StatusCode::SERVICE_UNAVAILABLE))
+        };
+
+        store_device_data(&mut conn, &device_code_hash, &device_data).await
+            .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        let verification_uri = format!("{}{}", jwt_iss.trim_end_matches('/'), self.verification_path);
+        let display_user_code = format!("{}-{}", &user_code[..USER_CODE_LENGTH / 2], &user_code[USER_CODE_LENGTH / 2..]);
+
+        Ok(ApiSuccess::new(DeviceAuthorizationResponse {
+            device_code,
+            verification_uri_complete: format!("{}?user_code={}", verification_uri, display_user_code),
+            user_code: display_user_code,
+            verification_uri,
+            expires_in: DEVICE_CODE_EXPIRES_IN,
+            interval: POLLING_INTERVAL,
+        }, StatusCode::OK))
+    }
+}
+
+impl DeviceAuthorizationUseCase {
+    pub fn new(cache: Arc<RedisCache>, client_repository: Arc<OAuthClientRepository>, jwks_gateway: Arc<JwksGateway>, verification_path: String) -> Self {
+        Self { cache, client_repository, jwks_gateway, verification_path }
+    }
+
+    fn get_scopes(&self, data: &DeviceAuthorizationRequest, client: &OauthClient) -> Result<Vec<String>, ApiError> {
+        let allowed_scopes = client.scopes.clone().unwrap_or_default();
+
+        let Some(scope) = data.scope.as_ref() else {
+            return Ok(allowed_scopes);
+        };
+
+        let scopes = scope.split_whitespace().map(|e| e.to_string()).collect::<Vec<String>>();
+
+        if let Some(e) = scopes.iter().find(|e| !allowed_scopes.contains(e)) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidScope, format!("Scope {} is not allowed for this client", e)));
+        }
+
+        Ok(scopes)
+    }
+}
+
+pub async fn load_device_data(conn: &mut Connection, device_code_hash: &str) -> Result<Option<DeviceData>, String> {
+    let Ok(value) = conn.get::<String, Option<String>>(format!("device_code:{}", device_code_hash)).await else {
+        return Err(String::from("Failed to read device code"));
+    };
+
+    let Some(value) = value else {
+        return Ok(None);
+    };
+
+    match serde_json::from_str::<DeviceData>(&value) {
+        Ok(e) => Ok(Some(e)),
+        Err(_) => Err(String::from("Failed to parse device code data"))
+    }
+}
+
+pub async fn store_device_data(conn: &mut Connection, device_code_hash: &str, data: &DeviceData) -> Result<(), String> {
+    let Ok(value) = serde_json::to_string(data) else {
+        return Err(String::from("Failed to serialize device code data"));
+    };
+
+    if conn.set_ex::<String, String, ()>(format!("device_code:{}", device_code_hash), value, data.expires_in().max(1) as u64).await.is_err() {
+        return Err(String::from("Failed to store device code"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use redis::AsyncCommands;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::auth::device_authorization::store_device_data;
+use crate::application::use_cases::auth::device_verify::{device_page_url, pending_device_data};
+use crate::domain::oauth_session::OauthSession;
+use crate::dto::auth::consent_confirm::response::ConsentConfirmResponse;
+use crate::dto::auth::device_authorization::device_data::DeviceData;
+use crate::dto::auth::device_confirm::request::DeviceConfirmRequest;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::token::normalize_user_code;
+
+pub struct DeviceConfirmUseCase {
+    cache: Arc<RedisCache>,
+    repository: Arc<OAuthSessionRepository>,
+}
+
+impl UseCaseInterface for DeviceConfirmUseCase {
+    type Request = DeviceConfirmRequest;
+    type Response = ConsentConfirmResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let user_code = normalize_user_code(data.user_code.as_str());
+
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let (device_code_hash, mut device_data) = pending_device_data(&mut conn, &user_code).await?;
+
+        if !data.approve {
+            device_data.status = DeviceData::STATUS_DENIED;
+
+            store_device_data(&mut conn, &device_code_hash, &device_data).await
+                .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+            let redirect_url = format!("{}?status=denied", device_page_url());
+            return Ok(ApiSuccess::new(ConsentConfirmResponse { redirect_url }, StatusCode::OK));
+        }
+
+        let session = match self.repository.insert(OauthSession {
+            id: None,
+            client_id: Some(device_data.client_id.clone()),
+            user_id: None,
+            scopes: Some(device_data.scopes.clone()),
+            redirect_uri: None,
+            state: None,
+            response_type: None,
+            response_mode: None,
+            nonce: None,
+            code_challenge: None,
+            code_challenge_method: None,
+            status: None,
+            consent_granted_at: None,
+            auth_time: None,
+            acr: None,
+            amr: None,
+            login_id: None,
+            created_at: None,
+            updated_at: None,
+        }).await {
+            Ok(e) => e,
+            Err(_) => return Err(ApiError::new(String::from("Failed to create OAuth session"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let Some(session_id) = session.id else {
+            return Err(ApiError::new(String::from("Failed to create OAuth session"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        device_data.session_id = Some(session_id);
+
+        store_device_data(&mut conn, &device_code_hash, &device_data).await
+            .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        if conn.set_ex::<String, String, ()>(format!("device_session:{}", session_id), device_code_hash, device_data.expires_in().max(1) as u64).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to store device session"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        let redirect_url = std::env::var("LOGIN_PAGE_URL").unwrap_or("http://localhost:3001/".to_string()) + "?session_id=" + session_id.to_string().as_str();
+        Ok(ApiSuccess::new(ConsentConfirmResponse { redirect_url }, StatusCode::OK))
+    }
+}
+
+impl DeviceConfirmUseCase {
+    pub fn new(cache: Arc<RedisCache>, repository: Arc<OAuthSessionRepository>) -> Self {
+        Self { cache, repository }
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use deadpool_redis::Connection;
+use redis::AsyncCommands;
+use reqwest::Url;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::device_authorization::load_device_data;
+use crate::dto::auth::device_authorization::device_data::DeviceData;
+use crate::dto::auth::device_verify::request::DeviceVerifyRequest;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::token::normalize_user_code;
+
+pub struct DeviceVerifyUseCase {
+    cache: Arc<RedisCache>,
+}
+
+impl UseCaseInterface for DeviceVerifyUseCase {
+    type Request = DeviceVerifyRequest;
+    type Response = String;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Some(user_code) = data.user_code.map(|e| normalize_user_code(e.as_str())).filter(|e| !e.is_empty()) else {
+            return Ok(ApiSuccess::new(device_page_url(), StatusCode::SEE_OTHER));
+        };
+
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let (_, device_data) = pending_device_data(&mut conn, &user_code).await?;
+
+        let Ok(mut url) = Url::parse(device_page_url().as_str()) else {
+            return Err(ApiError::new(String::from("Invalid DEVICE_PAGE_URL"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        // The user confirms the requesting client and scopes on the device page before the code is bound to a login (RFC 8628 section 5.4).
+        url.query_pairs_mut()
+            .append_pair("user_code", user_code.as_str())
+            .append_pair("client_id", device_data.client_id.as_str())
+            .append_pair("scope", device_data.scopes.join(" ").as_str());
+
+        Ok(ApiSuccess::new(url.to_string(), StatusCode::SEE_OTHER))
+    }
+}
+
+impl DeviceVerifyUseCase {
+    pub fn new(cache: Arc<RedisCache>) -> Self {
+        Self { cache }
+    }
+}
+
+pub fn device_page_url() -> String {
+    std::env::var("DEVICE_PAGE_URL").unwrap_or("http://localhost:3001/device".to_string())
+}
+
+/// Looks up a user code that has not been confirmed or denied yet and returns its device code hash with the device data.
+pub async fn pending_device_data(conn: &mut Connection, user_code: &str) -> Result<(String, DeviceData), ApiError> {
+    let Ok(Some(device_code_hash)) = conn.get::<String, Option<String>>(format!("user_code:{}", user_code)).await else {
+        return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid user code"));
+    };
+
+    let Ok(Some(device_data)) = load_device_data(conn, &device_code_hash).await else {
+        return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid user code"));
+    };
+
+    if device_data.session_id.is_some() || device_data.status != DeviceData::STATUS_PENDING {
+        return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "User code already used"));
+    }
+
+    Ok((device_code_hash, device_data))
+}
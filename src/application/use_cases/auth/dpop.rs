@@ -0,0 +1,100 @@
+use std::env;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::dto::auth::dpop::request::DpopRequest;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::dpop::{decode_dpop_proof, validate_dpop_claims, DPOP_PROOF_MAX_AGE};
+use crate::utils::hasher::hash_sha256;
+use crate::utils::token::generate_dpop_nonce;
+
+pub const DPOP_NONCE_EXPIRES_IN: u64 = 5 * 60;
+
+pub struct DpopUseCase {
+    cache: Arc<RedisCache>,
+}
+
+impl UseCaseInterface for DpopUseCase {
+    type Request = DpopRequest;
+    type Response = Option<String>;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Some(proof) = data.proof.as_ref() else {
+            return Ok(ApiSuccess::new(None, StatusCode::OK));
+        };
+
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let Ok(proof) = decode_dpop_proof(proof) else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidDpopProof, "Invalid DPoP proof"));
+        };
+
+        let url = format!("{}{}", jwt_iss.trim_end_matches('/'), data.path);
+
+        if let Err(e) = validate_dpop_claims(&proof.claims, data.method.as_str(), url.as_str(), data.access_token.as_deref(), chrono::Utc::now().timestamp()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidDpopProof, e));
+        }
+
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        match proof.claims.nonce.as_ref() {
+            Some(nonce) => {
+                let Ok(true) = conn.exists::<String, bool>(nonce_key(data.client_id.as_deref(), nonce)).await else {
+                    return Err(ApiError::oauth(OAuthErrorCode::UseDpopNonce, "Invalid DPoP nonce"));
+                };
+            },
+            None if Self::nonce_required() => {
+                return Err(ApiError::oauth(OAuthErrorCode::UseDpopNonce, "DPoP nonce is required"));
+            },
+            None => {}
+        }
+
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX((DPOP_PROOF_MAX_AGE * 2) as u64));
+
+        let replay_key = format!("dpop_jti:{}", hash_sha256(format!("{}:{}", proof.jkt, proof.claims.jti).as_str()));
+
+        let Ok(Some(_)) = conn.set_options::<String, String, Option<String>>(replay_key, String::from("1"), options).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidDpopProof, "DPoP proof has already been used"));
+        };
+
+        Ok(ApiSuccess::new(Some(proof.jkt), StatusCode::OK))
+    }
+}
+
+impl DpopUseCase {
+    pub fn new(cache: Arc<RedisCache>) -> Self {
+        Self { cache }
+    }
+
+    pub fn nonce_required() -> bool {
+        env::var("DPOP_NONCE_REQUIRED").is_ok_and(|e| e == "true")
+    }
+
+    /// Issues a nonce that is only accepted in proofs sent by the given client.
+    pub async fn issue_nonce(&self, client_id: Option<&str>) -> Result<String, ApiError> {
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let nonce = generate_dpop_nonce();
+
+        if conn.set_ex::<String, String, ()>(nonce_key(client_id, nonce.as_str()), String::from("1"), DPOP_NONCE_EXPIRES_IN).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to store DPoP nonce"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        Ok(nonce)
+    }
+}
+
+fn nonce_key(client_id: Option<&str>, nonce: &str) -> String {
+    format!("dpop_nonce:{}", hash_sha256(format!("{}:{}", client_id.unwrap_or_default(), nonce).as_str()))
+}
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use redis::AsyncCommands;
+use reqwest::Url;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::logout::LogoutGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_logout_delivery::OAuthLogoutDeliveryRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::auth::backchannel_logout::BackchannelLogoutUseCase;
+use crate::domain::oauth_client::OauthClient;
+use crate::application::use_cases::auth::id_token::{id_token_source, ID_TOKEN_SOURCE_IDP};
+use crate::domain::oauth_session::OauthSession;
+use crate::domain::oauth_signing_key::OauthSigningKey;
+use crate::dto::auth::end_session::request::EndSessionRequest;
+use crate::dto::auth::end_session::response::EndSessionResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::hasher::hash_sha256;
+use crate::utils::signing_key::SigningKey;
+use crate::utils::token::decode_id_token_hint;
+
+pub struct EndSessionUseCase {
+    cache: Arc<RedisCache>,
+    repository: Arc<OAuthSessionRepository>,
+    token_repository: Arc<OAuthTokenRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    delivery_repository: Arc<OAuthLogoutDeliveryRepository>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    logout_gateway: Arc<LogoutGateway>,
+}
+
+impl UseCaseInterface for EndSessionUseCase {
+    type Request = EndSessionRequest;
+    type Response = EndSessionResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Some(id_token_hint) = data.id_token_hint.clone() else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Missing id_token_hint"));
+        };
+
+        let session = self.get_hinted_session(&id_token_hint).await?;
+
+        let client_id = session.client_id.clone().unwrap();
+
+        if data.client_id.as_ref().is_some_and(|e| *e != client_id) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid client"));
+        }
+
+        let Ok(client) = self.client_repository.get_by_slug(client_id).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Client not found"));
+        };
+
+        let redirect_uri = self.validate_redirect_uri(&data, &client)?;
+        let mut frontchannel_logout_uris = vec![];
+
+        if session.is_active() {
+            let sessions = self.end_sessions(session).await?;
+
+            frontchannel_logout_uris = self.get_frontchannel_logout_uris(&sessions).await?;
+
+            if let Err(e) = BackchannelLogoutUseCase::new(
+                self.client_repository.clone(),
+                self.repository.clone(),
+                self.delivery_repository.clone(),
+                self.signing_key_repository.clone(),
+                self.logout_gateway.clone(),
+            ).handle(sessions).await {
+                log::warn!("Back-channel logout failed: {}", e.error_description);
+            }
+        }
+
+        Ok(ApiSuccess::new(EndSessionResponse {
+            redirect_uri,
+            frontchannel_logout_uris,
+        }, StatusCode::SEE_OTHER))
+    }
+}
+
+impl EndSessionUseCase {
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        token_repository: Arc<OAuthTokenRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        delivery_repository: Arc<OAuthLogoutDeliveryRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
+        logout_gateway: Arc<LogoutGateway>,
+    ) -> Self {
+        Self { cache, repository, token_repository, client_repository, delivery_repository, signing_key_repository, logout_gateway }
+    }
+
+    async fn get_hinted_session(&self, id_token_hint: &str) -> Result<OauthSession, ApiError> {
+        let invalid_hint = || ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid id_token_hint");
+
+        // ID tokens issued by the IdP cannot be verified here, so they are matched against the one stored with the token.
+        if id_token_source() == ID_TOKEN_SOURCE_IDP {
+            let Ok(token) = self.token_repository.get_by_id_token(hash_sha256(id_token_hint)).await else {
+                return Err(invalid_hint());
+            };
+
+            let Some(session_id) = token.session_id else {
+                return Err(invalid_hint());
+            };
+
+            return self.repository.get(session_id).await.map_err(|_| invalid_hint());
+        }
+
+        let Ok(jwt_iss) = std::env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let Ok(keys) = self.signing_key_repository.list_by_status(vec![
+            OauthSigningKey::STATUS_ACTIVE,
+            OauthSigningKey::STATUS_RETIRING,
+        ]).await else {
+            return Err(ApiError::new(String::from("Failed to load signing keys"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let signing_keys = keys.iter()
+            .filter_map(|e| SigningKey::from_model(e).ok())
+            .collect::<Vec<Arc<SigningKey>>>();
+
+        let Ok(claims) = decode_id_token_hint(id_token_hint, &jwt_iss, &signing_keys) else {
+            return Err(invalid_hint());
+        };
+
+        let Ok(session_id) = uuid::Uuid::parse_str(&claims.sid) else {
+            return Err(invalid_hint());
+        };
+
+        let Ok(session) = self.repository.get(session_id).await else {
+            return Err(invalid_hint());
+        };
+
+        if session.client_id.as_ref() != Some(&claims.aud) || session.user_id.map(|e| e.to_string()) != Some(claims.sub) {
+            return Err(invalid_hint());
+        }
+
+        Ok(session)
+    }
+
+    fn validate_redirect_uri(&self, data: &EndSessionRequest, client: &OauthClient) -> Result<Option<String>, ApiError> {
+        let Some(uri) = data.post_logout_redirect_uri.clone() else {
+            return Ok(std::env::var("LOGOUT_PAGE_URL").ok().filter(|e| !e.is_empty()));
+        };
+
+        if !client.post_logout_redirect_uris.clone().unwrap_or_default().contains(&uri) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid post_logout_redirect_uri"));
+        }
+
+        let Ok(mut url) = Url::parse(uri.as_str()) else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid post_logout_redirect_uri"));
+        };
+
+        if let Some(state) = data.state.as_ref() {
+            url.query_pairs_mut().append_pair("state", state);
+        }
+
+        Ok(Some(url.to_string()))
+    }
+
+    /// Builds one front-channel logout URI per ended session, so each client only learns about its own `sid`.
+    async fn get_frontchannel_logout_uris(&self, sessions: &[OauthSession]) -> Result<Vec<String>, ApiError> {
+        let Ok(jwt_iss) = std::env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let mut uris = vec![];
+
+        for session in sessions {
+            let Ok(client) = self.client_repository.get_by_slug(session.client_id.clone().unwrap()).await else {
+                continue;
+            };
+
+            let Some(Ok(mut url)) = client.frontchannel_logout_uri
+                .filter(|e| !e.is_empty())
+                .map(|e| Url::parse(e.as_str())) else {
+                continue;
+            };
+
+            url.query_pairs_mut()
+                .append_pair("iss", jwt_iss.as_str())
+                .append_pair("sid", session.id.unwrap().to_string().as_str());
+
+            uris.push(url.to_string());
+        }
+
+        Ok(uris)
+    }
+
+    /// Ends the hinted session together with the sessions established by the same login.
+    async fn end_sessions(&self, session: OauthSession) -> Result<Vec<OauthSession>, ApiError> {
+        let sessions = match (session.user_id, session.login_id.clone()) {
+            (Some(user_id), Some(login_id)) => match self.repository.list_active_by_login(user_id, login_id).await {
+                Ok(e) => e,
+                Err(e) => return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
+            },
+            _ => vec![session.clone()],
+        };
+
+        for e in sessions.iter() {
+            self.end_session(e.clone()).await?;
+        }
+
+        let Some(user_id) = session.user_id else {
+            return Ok(sessions);
+        };
+
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        if conn.del::<String, ()>(format!("sub:{}", user_id)).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to evict userinfo cache"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        Ok(sessions)
+    }
+
+    async fn end_session(&self, mut session: OauthSession) -> Result<(), ApiError> {
+        session.status = Some(OauthSession::STATUS_ENDED);
+
+        if let Err(e) = self.repository.edit(session.id.unwrap(), session.clone(), vec!["status"]).await {
+            return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR));
+        }
+
+        if let Err(e) = self.token_repository.revoke_by_session(session.id.unwrap()).await {
+            return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR));
+        }
+
+        Ok(())
+    }
+}
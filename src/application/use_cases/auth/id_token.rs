@@ -0,0 +1,84 @@
+use std::env;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::gateways::idp::IdpGateway;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::domain::idp::IdPIdTokenRequest;
+use crate::dto::auth::token::id_token::{IdTokenRequest, IdTokenUserClaims};
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::signing_key::SigningKey;
+use crate::utils::token::{generate_id_token, IdTokenParams};
+
+pub const ID_TOKEN_SOURCE_LOCAL: &str = "local";
+pub const ID_TOKEN_SOURCE_IDP: &str = "idp";
+
+pub struct IdTokenUseCase {
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    idp_gateway: Arc<IdpGateway>,
+}
+
+impl UseCaseInterface for IdTokenUseCase {
+    type Request = IdTokenRequest;
+    type Response = String;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let session = &data.session;
+
+        let (Some(user_id), Some(client_id)) = (session.user_id, session.client_id.clone()) else {
+            return Err(ApiError::new(String::from("Session is not authenticated"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let scopes = session.scopes.clone().unwrap_or_default();
+
+        if id_token_source() == ID_TOKEN_SOURCE_IDP {
+            let id_token = self.idp_gateway.get_id_token_v1(IdPIdTokenRequest {
+                user_id: user_id.to_string(),
+                client_id,
+                scopes,
+            }).await?;
+
+            return Ok(ApiSuccess::new(id_token, StatusCode::OK))
+        }
+
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let Ok(key) = self.signing_key_repository.get_active().await else {
+            return Err(ApiError::new(String::from("Active signing key not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let signing_key = SigningKey::from_model(&key).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+        let user = self.idp_gateway.get_user_by_id_v1(user_id).await?;
+
+        let id_token = generate_id_token(
+            IdTokenParams {
+                user: IdTokenUserClaims::from_user(user, &scopes),
+                session,
+                jwt_iss,
+                access_token: &data.access_token,
+                code: data.code.as_deref(),
+                nonce: data.nonce.clone(),
+            },
+            chrono::Utc::now(),
+            &signing_key
+        ).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        Ok(ApiSuccess::new(id_token, StatusCode::OK))
+    }
+}
+
+impl IdTokenUseCase {
+    pub fn new(signing_key_repository: Arc<OAuthSigningKeyRepository>, idp_gateway: Arc<IdpGateway>) -> Self {
+        Self { signing_key_repository, idp_gateway }
+    }
+}
+
+/// Where ID tokens come from: signed here (`local`, the default) or issued by the IdP (`idp`).
+pub fn id_token_source() -> String {
+    env::var("ID_TOKEN_SOURCE")
+        .ok()
+        .filter(|e| !e.is_empty())
+        .unwrap_or(ID_TOKEN_SOURCE_LOCAL.to_string())
+}
@@ -0,0 +1,153 @@
+use std::env;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
+use crate::application::spi::repository::RepositoryInterface;
+use crate::domain::oauth_session::OauthSession;
+use crate::domain::oauth_signing_key::OauthSigningKey;
+use crate::domain::oauth_token::OauthToken;
+use crate::dto::auth::introspect::request::IntrospectRequest;
+use crate::dto::auth::introspect::response::IntrospectResponse;
+use crate::dto::auth::token::access_token::Confirmation;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::dpop::token_type;
+use crate::utils::hasher::hash_sha256;
+use crate::utils::signing_key::SigningKey;
+use crate::utils::token::decode_access_token;
+
+pub struct IntrospectUseCase {
+    cache: Arc<RedisCache>,
+    repository: Arc<OAuthSessionRepository>,
+    token_repository: Arc<OAuthTokenRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    jwks_gateway: Arc<JwksGateway>,
+}
+
+impl UseCaseInterface for IntrospectUseCase {
+    type Request = IntrospectRequest;
+    type Response = IntrospectResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Ok(client) = self.client_repository.get_by_slug(data.client_id.clone()).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClient, "Invalid client"));
+        };
+
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(&data),
+            allow_public: false,
+        }).await?;
+
+        let hash = hash_sha256(data.token.as_str());
+
+        let Ok(token) = self.token_repository.get_by_token(hash.clone(), data.token_type_hint.as_deref()).await else {
+            return Ok(ApiSuccess::new(IntrospectResponse::default(), StatusCode::OK));
+        };
+
+        if !token.is_active() {
+            return Ok(ApiSuccess::new(IntrospectResponse::default(), StatusCode::OK));
+        }
+
+        let session = match token.session_id {
+            Some(session_id) => match self.repository.get(session_id).await {
+                Ok(e) if e.is_active() => Some(e),
+                _ => return Ok(ApiSuccess::new(IntrospectResponse::default(), StatusCode::OK)),
+            },
+            None => None,
+        };
+
+        let response = if token.access_token == Some(hash) {
+            self.introspect_access_token(&data.token).await?
+        } else if let Some(session) = session {
+            self.introspect_refresh_token(&token, &session)
+        } else {
+            IntrospectResponse::default()
+        };
+
+        Ok(ApiSuccess::new(response, StatusCode::OK))
+    }
+}
+
+impl IntrospectUseCase {
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        token_repository: Arc<OAuthTokenRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
+        jwks_gateway: Arc<JwksGateway>,
+    ) -> Self {
+        Self { cache, repository, token_repository, client_repository, signing_key_repository, jwks_gateway }
+    }
+
+    async fn introspect_access_token(&self, access_token: &str) -> Result<IntrospectResponse, ApiError> {
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let Ok(keys) = self.signing_key_repository.list_by_status(vec![
+            OauthSigningKey::STATUS_ACTIVE,
+            OauthSigningKey::STATUS_RETIRING,
+        ]).await else {
+            return Err(ApiError::new(String::from("Failed to load signing keys"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let signing_keys = keys.iter()
+            .filter_map(|e| SigningKey::from_model(e).ok())
+            .collect::<Vec<Arc<SigningKey>>>();
+
+        let Ok(claims) = decode_access_token(access_token, &jwt_iss, &signing_keys) else {
+            return Ok(IntrospectResponse::default());
+        };
+
+        Ok(IntrospectResponse {
+            active: true,
+            scope: Some(claims.scopes.join(" ")),
+            client_id: Some(claims.client_id),
+            token_type: Some(token_type(&claims.cnf.as_ref().and_then(|e| e.jkt.clone()))),
+            sub: Some(claims.sub),
+            exp: Some(claims.exp),
+            iat: Some(claims.iat),
+            iss: Some(claims.iss),
+            sid: Some(claims.sid).filter(|e| !e.is_empty()),
+            jti: Some(claims.jti),
+            aud: Some(claims.aud).filter(|e| !e.is_empty()),
+            act: claims.act,
+            cnf: claims.cnf,
+        })
+    }
+
+    fn introspect_refresh_token(&self, token: &OauthToken, session: &OauthSession) -> IntrospectResponse {
+        let now = chrono::Utc::now().naive_utc();
+
+        if token.refresh_token_expires_at.is_some_and(|e| e < now) {
+            return IntrospectResponse::default();
+        }
+
+        IntrospectResponse {
+            active: true,
+            scope: session.scopes.clone().map(|e| e.join(" ")),
+            client_id: session.client_id.clone(),
+            token_type: Some(String::from("refresh_token")),
+            sub: session.user_id.map(|e| e.to_string()),
+            exp: token.refresh_token_expires_at.map(|e| e.and_utc().timestamp() as usize),
+            iat: None,
+            iss: env::var("JWT_ISSUER").ok(),
+            sid: session.id.map(|e| e.to_string()),
+            jti: None,
+            aud: None,
+            act: None,
+            cnf: Confirmation::new(token.dpop_jkt.clone(), token.x5t_s256.clone()),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::env;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::introspect::IntrospectUseCase;
+use crate::dto::auth::introspect::request::IntrospectRequest;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::signing_key::SigningKey;
+use crate::utils::token::generate_introspection_token;
+
+pub const CONTENT_TYPE: &str = "application/token-introspection+jwt";
+
+pub struct IntrospectJwtUseCase {
+    cache: Arc<RedisCache>,
+    repository: Arc<OAuthSessionRepository>,
+    token_repository: Arc<OAuthTokenRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    jwks_gateway: Arc<JwksGateway>,
+}
+
+impl UseCaseInterface for IntrospectJwtUseCase {
+    type Request = IntrospectRequest;
+    type Response = String;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let audience = data.client_id.clone();
+
+        let introspection = IntrospectUseCase::new(
+            self.cache.clone(),
+            self.repository.clone(),
+            self.token_repository.clone(),
+            self.client_repository.clone(),
+            self.signing_key_repository.clone(),
+            self.jwks_gateway.clone(),
+        ).handle(data).await?;
+
+        let Ok(key) = self.signing_key_repository.get_active().await else {
+            return Err(ApiError::new(String::from("Active signing key not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let signing_key = SigningKey::from_model(&key).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        let Ok(token) = generate_introspection_token(
+            introspection.data,
+            chrono::Utc::now(),
+            jwt_iss,
+            audience,
+            &signing_key
+        ) else {
+            return Err(ApiError::new(String::from("Failed to sign introspection response"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        Ok(ApiSuccess::new(token, StatusCode::OK))
+    }
+}
+
+impl IntrospectJwtUseCase {
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        token_repository: Arc<OAuthTokenRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
+        jwks_gateway: Arc<JwksGateway>,
+    ) -> Self {
+        Self { cache, repository, token_repository, client_repository, signing_key_repository, jwks_gateway }
+    }
+}
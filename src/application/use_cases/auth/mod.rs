@@ -3,6 +3,26 @@ pub mod authorize;
 pub mod authorize_continue;
 pub mod token_authorization_code;
 pub mod token_refresh;
+pub mod token_client_credentials;
+pub mod token_device_code;
+pub mod token_exchange;
 pub mod consent_info;
 pub mod consent_confirm;
-pub mod userinfo;
\ No newline at end of file
+pub mod userinfo;
+pub mod revoke;
+pub mod introspect;
+pub mod introspect_jwt;
+pub mod end_session;
+pub mod backchannel_logout;
+pub mod device_authorization;
+pub mod device_verify;
+pub mod device_confirm;
+pub mod token_ciba;
+pub mod bc_authorize;
+pub mod bc_authorize_complete;
+pub mod dpop;
+pub mod client_authentication;
+pub mod request_object;
+pub mod authorization_response;
+pub mod id_token;
+pub mod token_common;
\ No newline at end of file
@@ -2,17 +2,31 @@ use std::sync::Arc;
 use actix_web::http::StatusCode;
 use deadpool_redis::redis::{AsyncCommands};
 use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::gateways::request_object::RequestObjectGateway;
 use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
 use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::application::use_cases::auth::request_object::RequestObjectUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
 use crate::domain::oauth_client::OauthClient;
 use crate::dto::auth::par::request::ParRequest;
+use crate::dto::auth::par::request_object::RequestObjectRequest;
 use crate::dto::auth::par::response::ParResponse;
 use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
 use crate::utils::entropy::entropy_total_bits;
+use crate::utils::redirect_uri::{match_native_redirect_uri, validate_web_redirect_uri};
+use crate::utils::response_mode::parse_response_mode;
+
+pub const RESPONSE_TYPES_SUPPORTED: [&str; 1] = ["code"];
+pub const CODE_CHALLENGE_METHODS_SUPPORTED: [&str; 1] = ["S256"];
 
 pub struct ParUseCase {
     cache: Arc<RedisCache>,
-    repository: Arc<OAuthClientRepository>
+    repository: Arc<OAuthClientRepository>,
+    jwks_gateway: Arc<JwksGateway>,
+    request_object_gateway: Arc<RequestObjectGateway>,
 }
 
 impl UseCaseInterface for ParUseCase {
@@ -20,28 +34,29 @@ impl UseCaseInterface for ParUseCase {
     type Response = ParResponse;
 
     async fn handle(&self, data: ParRequest) -> Result<ApiSuccess<Self::Response>, ApiError> {
-        let arc_data = Arc::new(data);
-
-        if let Err(e) = self.validate_state(arc_data.clone()) {
-            return Err(ApiError::new(e, StatusCode::BAD_REQUEST))
-        }
-
-        if let Err(e) = self.validate_pkce(arc_data.clone()) {
-            return Err(ApiError::new(e, StatusCode::BAD_REQUEST))
+        if data.request_uri.is_some() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "request_uri is not allowed in pushed authorization requests"))
         }
 
-        let client = match self.get_client(Arc::clone(&arc_data)).await {
+        let client = match self.repository.get_by_slug(data.client_id.clone()).await {
             Ok(e) => e,
-            Err(err) => return Err(ApiError::new(format!("Getting client: {}", err), StatusCode::BAD_REQUEST))
+            Err(err) => return Err(ApiError::oauth(OAuthErrorCode::InvalidClient, format!("Getting client: {}", err)))
         };
 
-        if let Err(e) = self.validate_uris(Arc::clone(&arc_data), &client) {
-            return Err(ApiError::new(format!("Error validating URIs: {}", e), StatusCode::BAD_REQUEST))
-        }
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(&data),
+            allow_public: true,
+        }).await?;
 
-        if let Err(e) = self.validate_scopes(Arc::clone(&arc_data), &client) {
-            return Err(ApiError::new(format!("Error validating scopes: {}", e), StatusCode::BAD_REQUEST))
-        }
+        let data = RequestObjectUseCase::new(self.cache.clone(), self.jwks_gateway.clone(), self.request_object_gateway.clone()).handle(RequestObjectRequest {
+            client: client.clone(),
+            request: data,
+        }).await?.data;
+
+        let arc_data = Arc::new(data);
+
+        self.validate_request(arc_data.clone(), &client)?;
 
         let exp = 60;
         let request_uri = String::from("urn:ietf:params:oauth:request_uri:") + &uuid::Uuid::new_v4().to_string();
@@ -57,8 +72,8 @@ impl UseCaseInterface for ParUseCase {
 
         let value = serde_json::to_string(&arc_data).unwrap();
 
-        if let Err(_) = conn.set_ex::<String, String, ()>(request_uri, value, exp)
-            .await{
+        if conn.set_ex::<String, String, ()>(request_uri, value, exp)
+            .await.is_err() {
             return Err(ApiError::new("Failed to store PAR request".to_string(), StatusCode::INTERNAL_SERVER_ERROR))
         }
 
@@ -67,12 +82,45 @@ impl UseCaseInterface for ParUseCase {
 }
 
 impl ParUseCase {
-    pub fn new(cache: Arc<RedisCache>, repository: Arc<OAuthClientRepository>) -> Self {
-        Self { cache, repository }
+    pub fn new(cache: Arc<RedisCache>, repository: Arc<OAuthClientRepository>, jwks_gateway: Arc<JwksGateway>, request_object_gateway: Arc<RequestObjectGateway>) -> Self {
+        Self { cache, repository, jwks_gateway, request_object_gateway }
+    }
+
+    pub fn validate_request(&self, data: Arc<ParRequest>, client: &OauthClient) -> Result<(), ApiError> {
+        self.validate_redirect(data.clone(), client)?;
+        self.validate_parameters(data, client)
+    }
+
+    pub fn validate_redirect(&self, data: Arc<ParRequest>, client: &OauthClient) -> Result<(), ApiError> {
+        if let Err(e) = self.validate_uris(data.clone(), client) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, format!("Error validating URIs: {}", e)))
+        }
+
+        if let Err(e) = parse_response_mode(data.response_mode.as_deref()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, e))
+        }
+
+        Ok(())
     }
 
-    async fn get_client(&self, data: Arc<ParRequest>) -> Result<OauthClient, String> {
-        self.repository.get_by_slug_secret(data.client_id.clone(), data.client_secret.clone()).await
+    pub fn validate_parameters(&self, data: Arc<ParRequest>, client: &OauthClient) -> Result<(), ApiError> {
+        if !RESPONSE_TYPES_SUPPORTED.contains(&data.response_type.as_str()) {
+            return Err(ApiError::oauth(OAuthErrorCode::UnsupportedResponseType, "Invalid response type"))
+        }
+
+        if let Err(e) = self.validate_state(data.clone()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, e))
+        }
+
+        if let Err(e) = self.validate_pkce(data.clone()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, e))
+        }
+
+        if let Err(e) = self.validate_scopes(data, client) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidScope, format!("Error validating scopes: {}", e)))
+        }
+
+        Ok(())
     }
 
     fn validate_uris(&self, data: Arc<ParRequest>, client: &OauthClient) -> Result<(), String> {
@@ -86,10 +134,18 @@ impl ParUseCase {
 
         let urls = client.urls.clone().unwrap();
 
+        if client.is_native() {
+            return match_native_redirect_uri(&urls, &data.redirect_uri);
+        }
+
         if !urls.contains(&data.redirect_uri) {
             return Err(String::from("Invalid redirect URI"));
         }
 
+        if client.is_public() {
+            validate_web_redirect_uri(&data.redirect_uri)?;
+        }
+
         Ok(())
     }
 
@@ -127,11 +183,7 @@ impl ParUseCase {
     }
 
     fn validate_pkce(&self, data: Arc<ParRequest>) -> Result<(), String> {
-        if data.response_type != "code" {
-            return Err(String::from("Invalid response type"));
-        }
-
-        if data.code_challenge_method != "S256" {
+        if !CODE_CHALLENGE_METHODS_SUPPORTED.contains(&data.code_challenge_method.as_str()) {
             return Err(String::from("Invalid code challenge method"));
         }
 
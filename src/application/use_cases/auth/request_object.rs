@@ -0,0 +1,220 @@
+use std::env;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use jsonwebtoken::{decode, decode_header, Validation};
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+use reqwest::Url;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::gateways::request_object::RequestObjectGateway;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::client_authentication::{ClientAuthenticationUseCase, CLIENT_SECRET_JWT_ALGORITHMS, PRIVATE_KEY_JWT_ALGORITHMS};
+use crate::domain::oauth_client::OauthClient;
+use crate::dto::auth::par::request::ParRequest;
+use crate::dto::auth::par::request_object::{RequestObjectClaims, RequestObjectRequest};
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::hasher::hash_sha256;
+use crate::utils::oauth_error::OAuthErrorCode;
+
+const REQUEST_OBJECT_LEEWAY: u64 = 60;
+const REQUEST_OBJECT_MAX_LIFETIME: i64 = 60 * 60;
+
+pub struct RequestObjectUseCase {
+    cache: Arc<RedisCache>,
+    jwks_gateway: Arc<JwksGateway>,
+    request_object_gateway: Arc<RequestObjectGateway>,
+}
+
+impl UseCaseInterface for RequestObjectUseCase {
+    type Request = RequestObjectRequest;
+    type Response = ParRequest;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let client = data.client;
+        let mut request = data.request;
+
+        let request_object = match (request.request.take(), request.request_uri.take()) {
+            (Some(_), Some(_)) => return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "request and request_uri cannot be used together")),
+            (Some(e), None) => e,
+            (None, Some(e)) => self.fetch_request_object(&client, e).await.map_err(|e| ApiError::oauth(OAuthErrorCode::InvalidRequestUri, e))?,
+            (None, None) => return Ok(ApiSuccess::new(request, StatusCode::OK)),
+        };
+
+        let claims = self.verify_request_object(&client, &request_object).await.map_err(|e| ApiError::oauth(OAuthErrorCode::InvalidRequestObject, e))?;
+        let request = Self::merge_parameters(request, claims).map_err(|e| ApiError::oauth(OAuthErrorCode::InvalidRequestObject, e))?;
+
+        Ok(ApiSuccess::new(request, StatusCode::OK))
+    }
+}
+
+impl RequestObjectUseCase {
+    pub fn new(cache: Arc<RedisCache>, jwks_gateway: Arc<JwksGateway>, request_object_gateway: Arc<RequestObjectGateway>) -> Self {
+        Self { cache, jwks_gateway, request_object_gateway }
+    }
+
+    async fn fetch_request_object(&self, client: &OauthClient, request_uri: String) -> Result<String, String> {
+        if !client.request_uris.as_ref().is_some_and(|e| e.contains(&request_uri)) {
+            return Err(String::from("Request URI is not registered for this client"));
+        }
+
+        let Ok(mut url) = Url::parse(&request_uri) else {
+            return Err(String::from("Invalid request URI"));
+        };
+
+        if url.scheme() != "https" {
+            return Err(String::from("Request URI must use https"));
+        }
+
+        url.set_fragment(None);
+
+        self.request_object_gateway.get_request_object_v1(url.to_string()).await
+    }
+
+    async fn verify_request_object(&self, client: &OauthClient, request_object: &str) -> Result<RequestObjectClaims, String> {
+        let Ok(header) = decode_header(request_object) else {
+            return Err(String::from("Invalid request object"));
+        };
+
+        if !CLIENT_SECRET_JWT_ALGORITHMS.contains(&header.alg) && !PRIVATE_KEY_JWT_ALGORITHMS.contains(&header.alg) {
+            return Err(String::from("Unsupported request object algorithm"));
+        }
+
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(String::from("JWT_ISSUER not found"));
+        };
+
+        let decoding_keys = ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone())
+            .get_decoding_keys(client, header.alg, header.kid)
+            .await?;
+
+        let client_id = client.slug.clone().unwrap_or_default();
+
+        let mut validation = Validation::new(header.alg);
+        validation.leeway = REQUEST_OBJECT_LEEWAY;
+        validation.set_audience(&[&jwt_iss]);
+        validation.set_issuer(&[&client_id]);
+        validation.validate_nbf = true;
+        validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+
+        let Some(claims) = decoding_keys.iter().find_map(|e| decode::<RequestObjectClaims>(request_object, e, &validation).ok().map(|e| e.claims)) else {
+            return Err(String::from("Invalid request object signature or claims"));
+        };
+
+        if claims.client_id.as_ref() != Some(&client_id) {
+            return Err(String::from("Request object client_id does not match the client"));
+        }
+
+        if claims.request.is_some() || claims.request_uri.is_some() {
+            return Err(String::from("Request object must not contain request or request_uri"));
+        }
+
+        let now = chrono::Utc::now().timestamp();
+
+        if claims.iat > now + REQUEST_OBJECT_LEEWAY as i64 {
+            return Err(String::from("Request object is issued in the future"));
+        }
+
+        if claims.exp - claims.iat > REQUEST_OBJECT_MAX_LIFETIME {
+            return Err(String::from("Request object lifetime is too long"));
+        }
+
+        self.check_replay(&client_id, &claims).await?;
+
+        Ok(claims)
+    }
+
+    async fn check_replay(&self, client_id: &str, claims: &RequestObjectClaims) -> Result<(), String> {
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(String::from("Getting cache connection"));
+        };
+
+        let expires_in = (claims.exp - chrono::Utc::now().timestamp() + REQUEST_OBJECT_LEEWAY as i64).max(1) as u64;
+        let replay_key = format!("request_object_jti:{}", hash_sha256(format!("{}:{}", client_id, claims.jti).as_str()));
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(expires_in));
+
+        match conn.set_options::<String, i32, Option<String>>(replay_key, 1, options).await {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err(String::from("Request object has already been used")),
+            Err(_) => Err(String::from("Failed to store request object")),
+        }
+    }
+
+    fn merge_parameters(request: ParRequest, claims: RequestObjectClaims) -> Result<ParRequest, String> {
+        let merge = |name: &str, outer: &str, inner: Option<String>| {
+            let inner = inner.unwrap_or_default();
+
+            if !outer.is_empty() && outer != inner {
+                return Err(format!("Parameter {} does not match the request object", name));
+            }
+
+            Ok(inner)
+        };
+
+        Ok(ParRequest {
+            scope: merge("scope", &request.scope, claims.scope)?,
+            redirect_uri: merge("redirect_uri", &request.redirect_uri, claims.redirect_uri)?,
+            response_type: merge("response_type", &request.response_type, claims.response_type)?,
+            state: merge("state", &request.state, claims.state)?,
+            code_challenge: merge("code_challenge", &request.code_challenge, claims.code_challenge)?,
+            code_challenge_method: merge("code_challenge_method", &request.code_challenge_method, claims.code_challenge_method)?,
+            response_mode: Some(merge("response_mode", request.response_mode.as_deref().unwrap_or_default(), claims.response_mode)?).filter(|e| !e.is_empty()),
+            nonce: Some(merge("nonce", request.nonce.as_deref().unwrap_or_default(), claims.nonce)?).filter(|e| !e.is_empty()),
+            ..request
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use crate::adapters::spi::gateways::jwks::JwksGateway;
+    use crate::adapters::spi::gateways::request_object::RequestObjectGateway;
+    use crate::application::api::use_case::UseCaseInterface;
+    use crate::domain::oauth_client::OauthClient;
+    use crate::dto::auth::par::request::ParRequest;
+    use crate::dto::auth::par::request_object::RequestObjectRequest;
+    use crate::utils::oauth_error::OAuthErrorCode;
+    use crate::utils::test_support::{cache, unique};
+    use super::RequestObjectUseCase;
+
+    #[actix_web::test]
+    async fn request_object_jti_cannot_be_replayed() {
+        let (Some(cache), Ok(jwt_iss)) = (cache(), std::env::var("JWT_ISSUER")) else { return };
+        let use_case = RequestObjectUseCase::new(cache, Arc::new(JwksGateway::new()), Arc::new(RequestObjectGateway::new()));
+
+        let (slug, secret) = (unique("client"), unique("secret"));
+        let client = OauthClient {
+            slug: Some(slug.clone()),
+            secret: Some(secret.clone()),
+            ..OauthClient::default()
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        let request_object = encode(&Header::default(), &serde_json::json!({
+            "iss": slug,
+            "aud": jwt_iss,
+            "client_id": slug,
+            "jti": unique("jti"),
+            "iat": now,
+            "exp": now + 60,
+            "scope": "openid",
+        }), &EncodingKey::from_secret(secret.as_bytes())).unwrap();
+
+        let request = |request_object: &str| RequestObjectRequest {
+            client: client.clone(),
+            request: serde_json::from_value::<ParRequest>(serde_json::json!({ "request": request_object })).unwrap(),
+        };
+
+        let accepted = use_case.handle(request(&request_object)).await.unwrap();
+        assert_eq!(accepted.data.scope, "openid");
+
+        let Err(error) = use_case.handle(request(&request_object)).await else {
+            panic!("A replayed request object was accepted");
+        };
+        assert_eq!(error.error, OAuthErrorCode::InvalidRequestObject);
+        assert_eq!(error.error_description, "Request object has already been used");
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
+use crate::application::spi::repository::RepositoryInterface;
+use crate::dto::auth::revoke::request::RevokeRequest;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::hasher::hash_sha256;
+
+const TOKEN_TYPE_HINTS_SUPPORTED: [&str; 2] = ["access_token", "refresh_token"];
+
+pub struct RevokeUseCase {
+    cache: Arc<RedisCache>,
+    repository: Arc<OAuthSessionRepository>,
+    token_repository: Arc<OAuthTokenRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    jwks_gateway: Arc<JwksGateway>,
+}
+
+impl UseCaseInterface for RevokeUseCase {
+    type Request = RevokeRequest;
+    type Response = ();
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Ok(client) = self.client_repository.get_by_slug(data.client_id.clone()).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClient, "Invalid client"));
+        };
+
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(&data),
+            allow_public: true,
+        }).await?;
+
+        if data.token_type_hint.as_deref().is_some_and(|e| !TOKEN_TYPE_HINTS_SUPPORTED.contains(&e)) {
+            return Err(ApiError::oauth(OAuthErrorCode::UnsupportedTokenType, "Unsupported token type hint"));
+        }
+
+        let Ok(token) = self.token_repository.get_by_token(hash_sha256(data.token.as_str()), data.token_type_hint.as_deref()).await else {
+            return Ok(ApiSuccess::new((), StatusCode::OK));
+        };
+
+        let client_id = match token.session_id {
+            Some(session_id) => match self.repository.get(session_id).await {
+                Ok(e) => e.client_id,
+                Err(_) => return Ok(ApiSuccess::new((), StatusCode::OK)),
+            },
+            None => token.client_id.clone(),
+        };
+
+        if client_id != client.slug {
+            return Err(ApiError::oauth(OAuthErrorCode::UnauthorizedClient, "Token was not issued to this client"));
+        }
+
+        if !token.is_active() {
+            return Ok(ApiSuccess::new((), StatusCode::OK));
+        }
+
+        if self.token_repository.revoke_lineage(token.id.unwrap()).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to revoke token"), StatusCode::SERVICE_UNAVAILABLE));
+        }
+
+        Ok(ApiSuccess::new((), StatusCode::OK))
+    }
+}
+
+impl RevokeUseCase {
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        token_repository: Arc<OAuthTokenRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        jwks_gateway: Arc<JwksGateway>,
+    ) -> Self {
+        Self { cache, repository, token_repository, client_repository, jwks_gateway }
+    }
+}
@@ -1,35 +1,46 @@
-use std::env;
 use std::ops::Add;
 use std::sync::Arc;
 use actix_web::http::StatusCode;
-use jsonwebtoken::EncodingKey;
 use redis::AsyncCommands;
 use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
 use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
 use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::token_common::{active_signing_key, jwt_issuer};
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::application::use_cases::auth::id_token::IdTokenUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
 use crate::application::spi::repository::RepositoryInterface;
 use crate::dto::auth::authorize::token_data::TokenData;
+use crate::dto::auth::token::access_token::Confirmation;
+use crate::dto::auth::token::id_token::IdTokenRequest;
 use crate::dto::auth::token::request::TokenRequest;
 use crate::dto::auth::token::response::TokenResponse;
 use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 use sha2::{Digest, Sha256};
 use crate::adapters::spi::gateways::idp::IdpGateway;
 use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
 use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
-use crate::domain::idp::IdPIdTokenRequest;
 use crate::domain::oauth_client::OauthClient;
 use crate::domain::oauth_session::OauthSession;
 use crate::domain::oauth_token::OauthToken;
+use crate::utils::dpop::token_type;
 use crate::utils::hasher::hash_sha256;
-use crate::utils::token::{generate_access_token, generate_refresh_token};
+use crate::utils::token::{generate_access_token, AccessTokenParams, generate_refresh_token};
+
+pub const GRANT_TYPE: &str = "authorization_code";
 
 pub struct TokenAuthorizationCodeUseCase {
     cache: Arc<RedisCache>,
     repository: Arc<OAuthSessionRepository>,
     token_repository: Arc<OAuthTokenRepository>,
     client_repository: Arc<OAuthClientRepository>,
-    idp_gateway: Arc<IdpGateway>
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    idp_gateway: Arc<IdpGateway>,
+    jwks_gateway: Arc<JwksGateway>,
 }
 
 impl UseCaseInterface for TokenAuthorizationCodeUseCase {
@@ -39,21 +50,16 @@ impl UseCaseInterface for TokenAuthorizationCodeUseCase {
     async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
         let arc_data = Arc::new(data);
 
-        if let Err(e) = self.validate_request(arc_data.clone()) {
-            return Err(e);
-        }
-
-        let (jwt_iss, encoding_key) = match self.validate_envs() {
-            Ok(e) => e,
-            Err(e) => return Err(e)
-        };
+        let jwt_iss = jwt_issuer()?;
+        let signing_key = active_signing_key(&self.signing_key_repository).await?;
 
         let Ok(mut conn) = self.cache.get_pool().await else {
             return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
         };
 
-        let Ok(value) = conn.get::<String, String>(arc_data.code.clone()).await else  {
-            return Err(ApiError::new(String::from("Invalid authorization code"), StatusCode::BAD_REQUEST));
+        // Taking the code out in one step keeps concurrent requests from redeeming it twice.
+        let Ok(Some(value)) = conn.get_del::<String, Option<String>>(arc_data.code.clone()).await else  {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Invalid authorization code"));
         };
 
         let Ok(session) = serde_json::from_str::<TokenData>(&value) else {
@@ -61,70 +67,79 @@ impl UseCaseInterface for TokenAuthorizationCodeUseCase {
         };
 
         let Ok(repo_session) = self.repository.get(session.session_id).await else {
-            return Err(ApiError::new(String::from("Session not found"), StatusCode::BAD_REQUEST));
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Session not found"));
         };
 
         let s256_code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(arc_data.code_verifier.clone().as_bytes()));
         
         if s256_code_challenge != repo_session.code_challenge.clone().unwrap_or(String::new()) {
-            return Err(ApiError::new(String::from("Invalid code challenge"), StatusCode::BAD_REQUEST));
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Invalid code challenge"));
         }
 
         let Ok(repo_client) = self.client_repository.get_by_slug(repo_session.client_id.clone().unwrap()).await else {
-            return Err(ApiError::new(String::from("Client not found"), StatusCode::BAD_REQUEST));
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Client not found"));
         };
 
-        if let Err(e) = self.validate_client(arc_data.clone(), repo_session.clone(), repo_client.clone()) {
-            return Err(e);
-        }
+        self.validate_client(arc_data.clone(), repo_session.clone(), repo_client.clone()).await?;
+        self.validate_request(&repo_session, &repo_client)?;
 
-        let id_token = match self.idp_gateway.get_id_token_v1(IdPIdTokenRequest {
-            user_id: session.user_id.clone().to_string(),
-            client_id: repo_session.client_id.clone().unwrap().to_string(),
-            scopes: repo_session.scopes.clone().unwrap_or(vec![])
-        }).await {
-            Ok(e) => e,
-            Err(e) => return Err(e)
-        };
+        let x5t_s256 = arc_data.client_context.client_certificate.as_ref().map(|e| e.thumbprint());
 
         let Ok(access_token) = generate_access_token(
-            repo_session.scopes.unwrap_or(vec![]),
+            AccessTokenParams {
+                scopes: repo_session.scopes.clone().unwrap_or(vec![]),
+                jwt_iss,
+                session_id: session.session_id.to_string(),
+                sub: session.user_id.to_string(),
+                client_id: repo_session.client_id.clone().unwrap().to_string(),
+                auth_time: repo_session.auth_time,
+                cnf: Confirmation::new(arc_data.dpop_jkt.clone(), x5t_s256.clone()),
+            },
             chrono::Utc::now(),
-            jwt_iss,
-            session.session_id.to_string(),
-            session.user_id.clone(),
-            repo_session.client_id.unwrap().to_string(),
-            encoding_key
+            &signing_key
         ) else {
             return Err(ApiError::new(String::from("Failed to generate access token"), StatusCode::INTERNAL_SERVER_ERROR));
         };
 
+        let id_token = IdTokenUseCase::new(self.signing_key_repository.clone(), self.idp_gateway.clone()).handle(IdTokenRequest {
+            session: repo_session.clone(),
+            access_token: access_token.clone(),
+            code: Some(arc_data.code.clone()),
+            nonce: repo_session.nonce.clone(),
+        }).await?.data;
+
         let refresh_token = generate_refresh_token();
 
-        if let Err(_) = conn.del::<String, String>(arc_data.code.clone()).await {
-            return Err(ApiError::new(String::from("Failed to delete code"), StatusCode::INTERNAL_SERVER_ERROR))
-        }
-        
         let exp = chrono::Utc::now().add(chrono::Duration::days(7));
 
-        if let Err(_) = self.token_repository.insert(OauthToken {
+        if self.token_repository.insert(OauthToken {
             id: None,
-            session_id: Some(session.session_id.clone()),
+            session_id: Some(session.session_id),
             access_token: Some(hash_sha256(access_token.clone().as_str())),
             refresh_token: Some(hash_sha256(refresh_token.clone().as_str())),
+            previous_refresh_token: None,
             refresh_token_expires_at: Some(exp.naive_utc()),
+            id_token: Some(hash_sha256(id_token.clone().as_str())),
+            subject_token_id: None,
+            actor_token_id: None,
+            previous_token_id: None,
+            dpop_jkt: arc_data.dpop_jkt.clone(),
+            x5t_s256,
+            client_id: None,
             status: None,
             created_at: None,
             updated_at: None,
-        }).await {
+        }).await.is_err() {
             return Err(ApiError::new(String::from("Failed to create token"), StatusCode::INTERNAL_SERVER_ERROR))
         }
 
         Ok(ApiSuccess::new(
             TokenResponse {
                 access_token,
-                refresh_token,
-                id_token,
+                token_type: token_type(&arc_data.dpop_jkt),
+                refresh_token: Some(refresh_token),
+                id_token: Some(id_token),
+                issued_token_type: None,
             },
             StatusCode::OK
         ))
@@ -137,45 +152,38 @@ impl TokenAuthorizationCodeUseCase {
         repository: Arc<OAuthSessionRepository>,
         token_repository: Arc<OAuthTokenRepository>,
         client_repository: Arc<OAuthClientRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
         idp_gateway: Arc<IdpGateway>,
+        jwks_gateway: Arc<JwksGateway>,
     ) -> Self {
-        Self { cache, repository, token_repository, client_repository, idp_gateway  }
+        Self { cache, repository, token_repository, client_repository, signing_key_repository, idp_gateway, jwks_gateway }
     }
 
-    fn validate_request(&self, data: Arc<TokenRequest>) -> Result<(), ApiError> {
-        if data.grant_type != "authorization_code" {
-            return Err(ApiError::new(String::from("Invalid grant type"), StatusCode::BAD_REQUEST));
-        }
-
-        Ok(())
-    }
-
-    fn validate_client(&self, data: Arc<TokenRequest>, session: OauthSession, client: OauthClient) -> Result<(), ApiError> {
+    async fn validate_client(&self, data: Arc<TokenRequest>, session: OauthSession, client: OauthClient) -> Result<(), ApiError> {
         if session.client_id.unwrap() != data.client_id {
-            return Err(ApiError::new(String::from("Invalid client"), StatusCode::BAD_REQUEST));
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Invalid client"));
         };
 
-        if data.client_secret != client.secret.unwrap() {
-            return Err(ApiError::new(String::from("Invalid client"), StatusCode::BAD_REQUEST));
-        }
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(&*data),
+            allow_public: true,
+        }).await?;
 
         Ok(())
     }
 
+    fn validate_request(&self, session: &OauthSession, client: &OauthClient) -> Result<(), ApiError> {
+        if !client.allows_grant_type(GRANT_TYPE) {
+            return Err(ApiError::oauth(OAuthErrorCode::UnauthorizedClient, "Grant type not allowed for this client"));
+        }
 
-    fn validate_envs(&self) -> Result<(String, EncodingKey), ApiError> {
-        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
-            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
-        };
-
-        let Ok(jwt_pk) = env::var("JWT_PRIVATE_KEY") else {
-            return Err(ApiError::new(String::from("JWT_PRIVATE_KEY not found"), StatusCode::INTERNAL_SERVER_ERROR));
-        };
+        let allowed_scopes = client.scopes.clone().unwrap_or_default();
 
-        let Ok(encoding_key) = EncodingKey::from_rsa_pem(jwt_pk.replace("\\n", "\n").as_bytes()) else {
-            return Err(ApiError::new(String::from("Failed to parse JWT_PRIVATE_KEY"), StatusCode::INTERNAL_SERVER_ERROR));
-        };
+        if let Some(e) = session.scopes.clone().unwrap_or_default().iter().find(|e| !allowed_scopes.contains(e)) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidScope, format!("Scope {} is not allowed for this client", e)));
+        }
 
-        Ok((jwt_iss, encoding_key))
+        Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,202 @@
+use std::ops::Add;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use redis::AsyncCommands;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::idp::IdpGateway;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::token_common::{active_signing_key, jwt_issuer, PolledGrant};
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
+use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::auth::bc_authorize::{load_ciba_data, store_ciba_data, GRANT_TYPE};
+use crate::application::use_cases::auth::id_token::IdTokenUseCase;
+use crate::domain::oauth_client::OauthClient;
+use crate::domain::oauth_token::OauthToken;
+use crate::dto::auth::bc_authorize::ciba_data::CibaData;
+use crate::dto::auth::token::access_token::Confirmation;
+use crate::dto::auth::token::id_token::IdTokenRequest;
+use crate::dto::auth::token::request::TokenCibaRequest;
+use crate::dto::auth::token::response::TokenResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::dpop::token_type;
+use crate::utils::hasher::hash_sha256;
+use crate::utils::token::{generate_access_token, AccessTokenParams, generate_refresh_token};
+
+pub struct TokenCibaUseCase {
+    cache: Arc<RedisCache>,
+    repository: Arc<OAuthSessionRepository>,
+    token_repository: Arc<OAuthTokenRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    idp_gateway: Arc<IdpGateway>,
+    jwks_gateway: Arc<JwksGateway>,
+}
+
+impl UseCaseInterface for TokenCibaUseCase {
+    type Request = TokenCibaRequest;
+    type Response = TokenResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let jwt_iss = jwt_issuer()?;
+
+        let Ok(client) = self.client_repository.get_by_slug(data.client_id.clone()).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClient, "Invalid client"));
+        };
+
+        self.validate_client(&data, &client).await?;
+
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let auth_req_id_hash = hash_sha256(data.auth_req_id.as_str());
+
+        let ciba_data = match load_ciba_data(&mut conn, &auth_req_id_hash).await {
+            Ok(Some(e)) => e,
+            Ok(None) => return Err(ApiError::oauth(OAuthErrorCode::ExpiredToken, "The auth_req_id has expired")),
+            Err(e) => return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        if ciba_data.client_id != data.client_id {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Invalid auth_req_id"));
+        }
+
+        if ciba_data.expires_in() <= 0 {
+            return Err(ApiError::oauth(OAuthErrorCode::ExpiredToken, "The auth_req_id has expired"));
+        }
+
+        let ciba_data = self.poll(&mut conn, &auth_req_id_hash, ciba_data).await?;
+
+        if ciba_data.status == CibaData::STATUS_PENDING {
+            return Err(ApiError::oauth(OAuthErrorCode::AuthorizationPending, "The authorization request is still pending"));
+        }
+
+        if conn.del::<String, ()>(format!("ciba:{}", auth_req_id_hash)).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to delete authentication request"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        if ciba_data.status == CibaData::STATUS_DENIED {
+            return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "The authorization request was denied"));
+        }
+
+        let (session_id, user_id) = (ciba_data.session_id, ciba_data.user_id);
+
+        let Ok(session) = self.repository.get(session_id).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Session not found"));
+        };
+
+        if !session.is_active() {
+            return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "The authorization request was denied"));
+        }
+
+        let signing_key = active_signing_key(&self.signing_key_repository).await?;
+
+        let x5t_s256 = data.client_context.client_certificate.as_ref().map(|e| e.thumbprint());
+
+        let Ok(access_token) = generate_access_token(
+            AccessTokenParams {
+                scopes: session.scopes.clone().unwrap_or(vec![]),
+                jwt_iss,
+                session_id: session_id.to_string(),
+                sub: user_id.to_string(),
+                client_id: data.client_id.clone(),
+                auth_time: session.auth_time,
+                cnf: Confirmation::new(data.dpop_jkt.clone(), x5t_s256.clone()),
+            },
+            chrono::Utc::now(),
+            &signing_key
+        ) else {
+            return Err(ApiError::new(String::from("Failed to generate access token"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let id_token = IdTokenUseCase::new(self.signing_key_repository.clone(), self.idp_gateway.clone()).handle(IdTokenRequest {
+            session,
+            access_token: access_token.clone(),
+            code: None,
+            nonce: None,
+        }).await?.data;
+
+        let refresh_token = generate_refresh_token();
+        let exp = chrono::Utc::now().add(chrono::Duration::days(7));
+
+        if self.token_repository.insert(OauthToken {
+            id: None,
+            session_id: Some(session_id),
+            access_token: Some(hash_sha256(access_token.clone().as_str())),
+            refresh_token: Some(hash_sha256(refresh_token.clone().as_str())),
+            previous_refresh_token: None,
+            refresh_token_expires_at: Some(exp.naive_utc()),
+            id_token: Some(hash_sha256(id_token.clone().as_str())),
+            subject_token_id: None,
+            actor_token_id: None,
+            previous_token_id: None,
+            dpop_jkt: data.dpop_jkt.clone(),
+            x5t_s256,
+            client_id: None,
+            status: None,
+            created_at: None,
+            updated_at: None,
+        }).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to create token"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        Ok(ApiSuccess::new(
+            TokenResponse {
+                access_token,
+                token_type: token_type(&data.dpop_jkt),
+                refresh_token: Some(refresh_token),
+                id_token: Some(id_token),
+                issued_token_type: None,
+            },
+            StatusCode::OK
+        ))
+    }
+}
+
+impl TokenCibaUseCase {
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        token_repository: Arc<OAuthTokenRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
+        idp_gateway: Arc<IdpGateway>,
+        jwks_gateway: Arc<JwksGateway>,
+    ) -> Self {
+        Self { cache, repository, token_repository, client_repository, signing_key_repository, idp_gateway, jwks_gateway }
+    }
+
+    async fn validate_client(&self, data: &TokenCibaRequest, client: &OauthClient) -> Result<(), ApiError> {
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(data),
+            allow_public: false,
+        }).await?;
+
+        if !client.allows_grant_type(GRANT_TYPE) {
+            return Err(ApiError::oauth(OAuthErrorCode::UnauthorizedClient, "Grant type not allowed for this client"));
+        }
+
+        Ok(())
+    }
+
+    async fn poll(&self, conn: &mut deadpool_redis::Connection, auth_req_id_hash: &str, mut ciba_data: CibaData) -> Result<CibaData, ApiError> {
+        let too_fast = ciba_data.record_poll();
+
+        store_ciba_data(conn, auth_req_id_hash, &ciba_data).await
+            .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        if too_fast {
+            return Err(ApiError::oauth(OAuthErrorCode::SlowDown, "Polling too frequently"));
+        }
+
+        Ok(ciba_data)
+    }
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::token_common::{active_signing_key, jwt_issuer};
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
+use crate::application::spi::repository::RepositoryInterface;
+use crate::domain::oauth_client::OauthClient;
+use crate::domain::oauth_token::OauthToken;
+use crate::dto::auth::token::access_token::Confirmation;
+use crate::dto::auth::token::request::TokenClientCredentialsRequest;
+use crate::dto::auth::token::response::TokenResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::dpop::token_type;
+use crate::utils::hasher::hash_sha256;
+use crate::utils::token::{generate_access_token, AccessTokenParams};
+
+pub const GRANT_TYPE: &str = "client_credentials";
+
+pub struct TokenClientCredentialsUseCase {
+    cache: Arc<RedisCache>,
+    token_repository: Arc<OAuthTokenRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    jwks_gateway: Arc<JwksGateway>,
+}
+
+impl UseCaseInterface for TokenClientCredentialsUseCase {
+    type Request = TokenClientCredentialsRequest;
+    type Response = TokenResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let jwt_iss = jwt_issuer()?;
+
+        let Ok(client) = self.client_repository.get_by_slug(data.client_id.clone()).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClient, "Invalid client"));
+        };
+
+        self.validate_client(&data, &client).await?;
+
+        let scopes = self.get_scopes(&data, &client)?;
+        let signing_key = active_signing_key(&self.signing_key_repository).await?;
+
+        let x5t_s256 = data.client_context.client_certificate.as_ref().map(|e| e.thumbprint());
+
+        let Ok(access_token) = generate_access_token(
+            AccessTokenParams {
+                scopes,
+                jwt_iss,
+                session_id: String::new(),
+                sub: data.client_id.clone(),
+                client_id: data.client_id.clone(),
+                auth_time: None,
+                cnf: Confirmation::new(data.dpop_jkt.clone(), x5t_s256.clone()),
+            },
+            chrono::Utc::now(),
+            &signing_key
+        ) else {
+            return Err(ApiError::new(String::from("Failed to generate access token"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        if self.token_repository.insert(OauthToken {
+            id: None,
+            session_id: None,
+            access_token: Some(hash_sha256(access_token.clone().as_str())),
+            refresh_token: None,
+            previous_refresh_token: None,
+            refresh_token_expires_at: None,
+            id_token: None,
+            subject_token_id: None,
+            actor_token_id: None,
+            previous_token_id: None,
+            dpop_jkt: data.dpop_jkt.clone(),
+            x5t_s256,
+            client_id: Some(data.client_id.clone()),
+            status: None,
+            created_at: None,
+            updated_at: None,
+        }).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to create token"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        Ok(ApiSuccess::new(
+            TokenResponse {
+                access_token,
+                token_type: token_type(&data.dpop_jkt),
+                refresh_token: None,
+                id_token: None,
+                issued_token_type: None,
+            },
+            StatusCode::OK
+        ))
+    }
+}
+
+impl TokenClientCredentialsUseCase {
+    pub fn new(
+        cache: Arc<RedisCache>,
+        token_repository: Arc<OAuthTokenRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
+        jwks_gateway: Arc<JwksGateway>,
+    ) -> Self {
+        Self { cache, token_repository, client_repository, signing_key_repository, jwks_gateway }
+    }
+
+    async fn validate_client(&self, data: &TokenClientCredentialsRequest, client: &OauthClient) -> Result<(), ApiError> {
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(data),
+            allow_public: false,
+        }).await?;
+
+        if !client.allows_grant_type(GRANT_TYPE) {
+            return Err(ApiError::oauth(OAuthErrorCode::UnauthorizedClient, "Grant type not allowed for this client"));
+        }
+
+        Ok(())
+    }
+
+    fn get_scopes(&self, data: &TokenClientCredentialsRequest, client: &OauthClient) -> Result<Vec<String>, ApiError> {
+        let allowed_scopes = client.scopes.clone().unwrap_or_default();
+
+        let Some(scope) = data.scope.as_ref() else {
+            return Ok(allowed_scopes);
+        };
+
+        let scopes = scope.split_whitespace().map(|e| e.to_string()).collect::<Vec<String>>();
+
+        if let Some(e) = scopes.iter().find(|e| !allowed_scopes.contains(e)) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidScope, format!("Scope {} is not allowed for this client", e)));
+        }
+
+        Ok(scopes)
+    }
+}
@@ -0,0 +1,58 @@
+use std::env;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::dto::auth::bc_authorize::ciba_data::CibaData;
+use crate::dto::auth::device_authorization::device_data::DeviceData;
+use crate::utils::api_response::ApiError;
+use crate::utils::signing_key::SigningKey;
+
+pub const SLOW_DOWN_INCREMENT: i64 = 5;
+
+pub fn jwt_issuer() -> Result<String, ApiError> {
+    let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+        return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+    };
+
+    Ok(jwt_iss)
+}
+
+pub async fn active_signing_key(repository: &OAuthSigningKeyRepository) -> Result<Arc<SigningKey>, ApiError> {
+    let Ok(key) = repository.get_active().await else {
+        return Err(ApiError::new(String::from("Active signing key not found"), StatusCode::INTERNAL_SERVER_ERROR));
+    };
+
+    SigningKey::from_model(&key).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
+}
+
+/// Grants the client polls the token endpoint for until the user decides.
+pub trait PolledGrant {
+    fn polling(&mut self) -> (&mut Option<i64>, &mut i64);
+
+    /// Records a poll and returns whether it came before the interval elapsed, in which case the interval is widened.
+    fn record_poll(&mut self) -> bool {
+        let now = chrono::Utc::now().timestamp();
+        let (last_polled_at, interval) = self.polling();
+        let too_fast = last_polled_at.is_some_and(|e| now - e < *interval);
+
+        if too_fast {
+            *interval += SLOW_DOWN_INCREMENT;
+        }
+
+        *last_polled_at = Some(now);
+
+        too_fast
+    }
+}
+
+impl PolledGrant for DeviceData {
+    fn polling(&mut self) -> (&mut Option<i64>, &mut i64) {
+        (&mut self.last_polled_at, &mut self.interval)
+    }
+}
+
+impl PolledGrant for CibaData {
+    fn polling(&mut self) -> (&mut Option<i64>, &mut i64) {
+        (&mut self.last_polled_at, &mut self.interval)
+    }
+}
@@ -0,0 +1,250 @@
+use std::ops::Add;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::idp::IdpGateway;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::token_common::{active_signing_key, jwt_issuer, PolledGrant};
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
+use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::auth::device_authorization::{load_device_data, store_device_data, GRANT_TYPE};
+use crate::application::use_cases::auth::id_token::IdTokenUseCase;
+use crate::domain::oauth_client::OauthClient;
+use crate::domain::oauth_token::OauthToken;
+use crate::dto::auth::device_authorization::device_data::DeviceData;
+use crate::dto::auth::token::access_token::Confirmation;
+use crate::dto::auth::token::id_token::IdTokenRequest;
+use crate::dto::auth::token::request::TokenDeviceCodeRequest;
+use crate::dto::auth::token::response::TokenResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::dpop::token_type;
+use crate::utils::hasher::hash_sha256;
+use crate::utils::token::{generate_access_token, AccessTokenParams, generate_refresh_token};
+
+pub struct TokenDeviceCodeUseCase {
+    cache: Arc<RedisCache>,
+    repository: Arc<OAuthSessionRepository>,
+    token_repository: Arc<OAuthTokenRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    idp_gateway: Arc<IdpGateway>,
+    jwks_gateway: Arc<JwksGateway>,
+}
+
+impl UseCaseInterface for TokenDeviceCodeUseCase {
+    type Request = TokenDeviceCodeRequest;
+    type Response = TokenResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let jwt_iss = jwt_issuer()?;
+
+        let Ok(client) = self.client_repository.get_by_slug(data.client_id.clone()).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClient, "Invalid client"));
+        };
+
+        self.validate_client(&data, &client).await?;
+
+        let Ok(mut conn) = self.cache.get_pool().await else {
+            return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let device_code_hash = hash_sha256(data.device_code.as_str());
+
+        let device_data = match load_device_data(&mut conn, &device_code_hash).await {
+            Ok(Some(e)) => e,
+            Ok(None) => return Err(ApiError::oauth(OAuthErrorCode::ExpiredToken, "The device code has expired")),
+            Err(e) => return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        if device_data.client_id != data.client_id {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Invalid device code"));
+        }
+
+        if device_data.expires_in() <= 0 {
+            return Err(ApiError::oauth(OAuthErrorCode::ExpiredToken, "The device code has expired"));
+        }
+
+        let device_data = self.poll(&mut conn, &device_code_hash, device_data).await?;
+
+        if device_data.status == DeviceData::STATUS_PENDING {
+            return Err(ApiError::oauth(OAuthErrorCode::AuthorizationPending, "The authorization request is still pending"));
+        }
+
+        Self::redeem(&mut conn, &device_code_hash, &device_data).await?;
+
+        if conn.del::<Vec<String>, ()>(vec![
+            format!("device_code:{}", device_code_hash),
+            format!("user_code:{}", device_data.user_code),
+        ]).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to delete device code"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        if device_data.status == DeviceData::STATUS_DENIED {
+            return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "The authorization request was denied"));
+        }
+
+        let (Some(session_id), Some(user_id)) = (device_data.session_id, device_data.user_id) else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Invalid device code"));
+        };
+
+        let Ok(session) = self.repository.get(session_id).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Session not found"));
+        };
+
+        let signing_key = active_signing_key(&self.signing_key_repository).await?;
+
+        let x5t_s256 = data.client_context.client_certificate.as_ref().map(|e| e.thumbprint());
+
+        let Ok(access_token) = generate_access_token(
+            AccessTokenParams {
+                scopes: session.scopes.clone().unwrap_or(vec![]),
+                jwt_iss,
+                session_id: session_id.to_string(),
+                sub: user_id.to_string(),
+                client_id: data.client_id.clone(),
+                auth_time: session.auth_time,
+                cnf: Confirmation::new(data.dpop_jkt.clone(), x5t_s256.clone()),
+            },
+            chrono::Utc::now(),
+            &signing_key
+        ) else {
+            return Err(ApiError::new(String::from("Failed to generate access token"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let id_token = IdTokenUseCase::new(self.signing_key_repository.clone(), self.idp_gateway.clone()).handle(IdTokenRequest {
+            session,
+            access_token: access_token.clone(),
+            code: None,
+            nonce: None,
+        }).await?.data;
+
+        let refresh_token = generate_refresh_token();
+        let exp = chrono::Utc::now().add(chrono::Duration::days(7));
+
+        if self.token_repository.insert(OauthToken {
+            id: None,
+            session_id: Some(session_id),
+            access_token: Some(hash_sha256(access_token.clone().as_str())),
+            refresh_token: Some(hash_sha256(refresh_token.clone().as_str())),
+            previous_refresh_token: None,
+            refresh_token_expires_at: Some(exp.naive_utc()),
+            id_token: Some(hash_sha256(id_token.clone().as_str())),
+            subject_token_id: None,
+            actor_token_id: None,
+            previous_token_id: None,
+            dpop_jkt: data.dpop_jkt.clone(),
+            x5t_s256,
+            client_id: None,
+            status: None,
+            created_at: None,
+            updated_at: None,
+        }).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to create token"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        Ok(ApiSuccess::new(
+            TokenResponse {
+                access_token,
+                token_type: token_type(&data.dpop_jkt),
+                refresh_token: Some(refresh_token),
+                id_token: Some(id_token),
+                issued_token_type: None,
+            },
+            StatusCode::OK
+        ))
+    }
+}
+
+impl TokenDeviceCodeUseCase {
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        token_repository: Arc<OAuthTokenRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
+        idp_gateway: Arc<IdpGateway>,
+        jwks_gateway: Arc<JwksGateway>,
+    ) -> Self {
+        Self { cache, repository, token_repository, client_repository, signing_key_repository, idp_gateway, jwks_gateway }
+    }
+
+    async fn validate_client(&self, data: &TokenDeviceCodeRequest, client: &OauthClient) -> Result<(), ApiError> {
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(data),
+            allow_public: true,
+        }).await?;
+
+        if !client.allows_grant_type(GRANT_TYPE) {
+            return Err(ApiError::oauth(OAuthErrorCode::UnauthorizedClient, "Grant type not allowed for this client"));
+        }
+
+        Ok(())
+    }
+
+    async fn poll(&self, conn: &mut deadpool_redis::Connection, device_code_hash: &str, mut device_data: DeviceData) -> Result<DeviceData, ApiError> {
+        let too_fast = device_data.record_poll();
+
+        store_device_data(conn, device_code_hash, &device_data).await
+            .map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?;
+
+        if too_fast {
+            return Err(ApiError::oauth(OAuthErrorCode::SlowDown, "Polling too frequently"));
+        }
+
+        Ok(device_data)
+    }
+
+    /// Claims the device code once, so concurrent polls cannot both redeem it.
+    async fn redeem(conn: &mut deadpool_redis::Connection, device_code_hash: &str, device_data: &DeviceData) -> Result<(), ApiError> {
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(device_data.expires_in().max(1) as u64));
+
+        match conn.set_options::<String, i32, Option<String>>(format!("device_code_redeemed:{}", device_code_hash), 1, options).await {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "The device code has already been used")),
+            Err(_) => Err(ApiError::new(String::from("Failed to redeem device code"), StatusCode::INTERNAL_SERVER_ERROR)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::auth::device_authorization::device_data::DeviceData;
+    use crate::utils::oauth_error::OAuthErrorCode;
+    use crate::utils::test_support::{cache, unique};
+    use super::TokenDeviceCodeUseCase;
+
+    #[actix_web::test]
+    async fn device_code_is_redeemed_once() {
+        let Some(cache) = cache() else { return };
+        let mut conn = cache.get_pool().await.unwrap();
+
+        let device_code_hash = unique("device-code");
+        let device_data = DeviceData {
+            client_id: unique("client"),
+            scopes: vec![String::from("openid")],
+            user_code: String::from("ABCD-EFGH"),
+            status: DeviceData::STATUS_APPROVED,
+            session_id: None,
+            user_id: None,
+            interval: 5,
+            expires_at: chrono::Utc::now().timestamp() + 60,
+            last_polled_at: None,
+        };
+
+        assert!(TokenDeviceCodeUseCase::redeem(&mut conn, &device_code_hash, &device_data).await.is_ok());
+
+        let error = TokenDeviceCodeUseCase::redeem(&mut conn, &device_code_hash, &device_data).await.unwrap_err();
+        assert_eq!(error.error, OAuthErrorCode::InvalidGrant);
+    }
+}
@@ -0,0 +1,265 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::token_common::{active_signing_key, jwt_issuer};
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
+use crate::application::spi::repository::RepositoryInterface;
+use crate::domain::oauth_client::OauthClient;
+use crate::domain::oauth_signing_key::OauthSigningKey;
+use crate::domain::oauth_token::OauthToken;
+use crate::dto::auth::token::access_token::{AccessToken, Actor, Confirmation};
+use crate::dto::auth::token::request::TokenExchangeRequest;
+use crate::dto::auth::token::response::TokenResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::dpop::token_type;
+use crate::utils::hasher::hash_sha256;
+use crate::utils::signing_key::SigningKey;
+use crate::utils::token::{decode_access_token, generate_exchanged_access_token, ExchangedAccessTokenParams};
+
+pub const GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+pub const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+pub struct TokenExchangeUseCase {
+    cache: Arc<RedisCache>,
+    repository: Arc<OAuthSessionRepository>,
+    token_repository: Arc<OAuthTokenRepository>,
+    client_repository: Arc<OAuthClientRepository>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
+    jwks_gateway: Arc<JwksGateway>,
+}
+
+impl UseCaseInterface for TokenExchangeUseCase {
+    type Request = TokenExchangeRequest;
+    type Response = TokenResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let jwt_iss = jwt_issuer()?;
+
+        let Ok(client) = self.client_repository.get_by_slug(data.client_id.clone()).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClient, "Invalid client"));
+        };
+
+        self.validate_client(&data, &client).await?;
+        self.validate_token_types(&data)?;
+
+        let signing_keys = self.get_verification_keys().await?;
+
+        let (subject_token, subject) = self.validate_token(&data, &data.subject_token, &jwt_iss, &signing_keys, "subject_token").await?;
+
+        if subject_token.session_id.is_none() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "subject_token does not belong to a user session"));
+        }
+
+        let actor = match data.actor_token.as_ref() {
+            Some(e) => Some(self.validate_token(&data, e, &jwt_iss, &signing_keys, "actor_token").await?),
+            None => None
+        };
+
+        let scopes = self.get_scopes(&data, &subject)?;
+        let audience = self.get_audience(&data, &client, &subject)?;
+
+        let act = match actor.as_ref() {
+            Some((_, e)) => Actor {
+                sub: e.sub.clone(),
+                client_id: Some(e.client_id.clone()),
+                act: subject.act.clone().map(Box::new),
+            },
+            None => Actor {
+                sub: data.client_id.clone(),
+                client_id: Some(data.client_id.clone()),
+                act: subject.act.clone().map(Box::new),
+            }
+        };
+
+        let signing_key = active_signing_key(&self.signing_key_repository).await?;
+
+        let x5t_s256 = data.client_context.client_certificate.as_ref().map(|e| e.thumbprint());
+
+        let Ok(access_token) = generate_exchanged_access_token(
+            ExchangedAccessTokenParams {
+                subject: &subject,
+                scopes,
+                client_id: data.client_id.clone(),
+                audience,
+                act,
+                cnf: Confirmation::new(data.dpop_jkt.clone(), x5t_s256.clone()),
+            },
+            chrono::Utc::now(),
+            &signing_key
+        ) else {
+            return Err(ApiError::new(String::from("Failed to generate access token"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        if self.token_repository.insert(OauthToken {
+            id: None,
+            session_id: subject_token.session_id,
+            access_token: Some(hash_sha256(access_token.clone().as_str())),
+            refresh_token: None,
+            previous_refresh_token: None,
+            refresh_token_expires_at: None,
+            id_token: None,
+            subject_token_id: subject_token.id,
+            actor_token_id: actor.and_then(|e| e.0.id),
+            previous_token_id: None,
+            dpop_jkt: data.dpop_jkt.clone(),
+            x5t_s256,
+            client_id: Some(data.client_id.clone()),
+            status: None,
+            created_at: None,
+            updated_at: None,
+        }).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to create token"), StatusCode::INTERNAL_SERVER_ERROR))
+        }
+
+        Ok(ApiSuccess::new(
+            TokenResponse {
+                access_token,
+                token_type: token_type(&data.dpop_jkt),
+                refresh_token: None,
+                id_token: None,
+                issued_token_type: Some(String::from(ACCESS_TOKEN_TYPE)),
+            },
+            StatusCode::OK
+        ))
+    }
+}
+
+impl TokenExchangeUseCase {
+    pub fn new(
+        cache: Arc<RedisCache>,
+        repository: Arc<OAuthSessionRepository>,
+        token_repository: Arc<OAuthTokenRepository>,
+        client_repository: Arc<OAuthClientRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
+        jwks_gateway: Arc<JwksGateway>,
+    ) -> Self {
+        Self { cache, repository, token_repository, client_repository, signing_key_repository, jwks_gateway }
+    }
+
+    async fn validate_client(&self, data: &TokenExchangeRequest, client: &OauthClient) -> Result<(), ApiError> {
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(data),
+            allow_public: false,
+        }).await?;
+
+        if !client.allows_grant_type(GRANT_TYPE) {
+            return Err(ApiError::oauth(OAuthErrorCode::UnauthorizedClient, "Grant type not allowed for this client"));
+        }
+
+        Ok(())
+    }
+
+    fn validate_token_types(&self, data: &TokenExchangeRequest) -> Result<(), ApiError> {
+        if data.subject_token_type != ACCESS_TOKEN_TYPE {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Unsupported subject_token_type"));
+        }
+
+        if data.requested_token_type.as_ref().is_some_and(|e| e != ACCESS_TOKEN_TYPE) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Unsupported requested_token_type"));
+        }
+
+        match (data.actor_token.as_ref(), data.actor_token_type.as_ref()) {
+            (None, None) => Ok(()),
+            (Some(_), Some(e)) if e == ACCESS_TOKEN_TYPE => Ok(()),
+            (Some(_), Some(_)) => Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Unsupported actor_token_type")),
+            _ => Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "actor_token and actor_token_type must be sent together")),
+        }
+    }
+
+    async fn validate_token(&self, data: &TokenExchangeRequest, token: &str, jwt_iss: &str, signing_keys: &[Arc<SigningKey>], name: &str) -> Result<(OauthToken, AccessToken), ApiError> {
+        let Ok(claims) = decode_access_token(token, jwt_iss, signing_keys) else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, format!("Invalid {}", name)));
+        };
+
+        let Ok(repo_token) = self.token_repository.get_by_access_token(hash_sha256(token)).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, format!("Invalid {}", name)));
+        };
+
+        if !repo_token.is_active() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, format!("{} has been revoked", name)));
+        }
+
+        if let Some(session_id) = repo_token.session_id {
+            let Ok(session) = self.repository.get(session_id).await else {
+                return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, format!("Invalid {}", name)));
+            };
+
+            if !session.is_active() {
+                return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, format!("{} has been revoked", name)));
+            }
+        }
+
+        self.validate_binding(data, &repo_token, name)?;
+
+        Ok((repo_token, claims))
+    }
+
+    /// A sender-constrained token may only be exchanged by whoever holds its key, so the request must carry a DPoP proof or a client certificate for the same key.
+    fn validate_binding(&self, data: &TokenExchangeRequest, token: &OauthToken, name: &str) -> Result<(), ApiError> {
+        if token.dpop_jkt.is_some() && token.dpop_jkt != data.dpop_jkt {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, format!("{} is bound to a DPoP key that was not presented", name)));
+        }
+
+        if let Some(x5t_s256) = token.x5t_s256.as_ref()
+            && data.client_context.client_certificate.as_ref().map(|e| e.thumbprint()).as_ref() != Some(x5t_s256) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, format!("{} is bound to a client certificate that was not presented", name)));
+        }
+
+        Ok(())
+    }
+
+    fn get_scopes(&self, data: &TokenExchangeRequest, subject: &AccessToken) -> Result<Vec<String>, ApiError> {
+        let Some(scope) = data.scope.as_ref() else {
+            return Ok(subject.scopes.clone());
+        };
+
+        let scopes = scope.split_whitespace().map(|e| e.to_string()).collect::<Vec<String>>();
+
+        if let Some(e) = scopes.iter().find(|e| !subject.scopes.contains(e)) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidScope, format!("Scope {} was not granted to the subject token", e)));
+        }
+
+        Ok(scopes)
+    }
+
+    fn get_audience(&self, data: &TokenExchangeRequest, client: &OauthClient, subject: &AccessToken) -> Result<String, ApiError> {
+        let audience = match data.audience.as_ref() {
+            Some(e) => e.clone(),
+            None => subject.aud.clone(),
+        };
+
+        if audience.is_empty() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidTarget, "An audience is required"));
+        }
+
+        if !subject.aud.is_empty() && subject.aud != audience {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidTarget, format!("Audience {} is outside of the subject token audience", audience)));
+        }
+
+        if !client.allows_exchange_audience(audience.as_str()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidTarget, format!("Client may not exchange tokens for audience {}", audience)));
+        }
+
+        Ok(audience)
+    }
+
+    async fn get_verification_keys(&self) -> Result<Vec<Arc<SigningKey>>, ApiError> {
+        let Ok(keys) = self.signing_key_repository.list_by_status(vec![
+            OauthSigningKey::STATUS_ACTIVE,
+            OauthSigningKey::STATUS_RETIRING,
+        ]).await else {
+            return Err(ApiError::new(String::from("Failed to load signing keys"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        Ok(keys.iter().filter_map(|e| SigningKey::from_model(e).ok()).collect())
+    }
+}
@@ -1,29 +1,42 @@
-use std::env;
 use std::ops::Add;
 use std::sync::Arc;
 use actix_web::http::StatusCode;
-use jsonwebtoken::EncodingKey;
+use crate::adapters::spi::cache::redis::RedisCache;
 use crate::adapters::spi::gateways::idp::IdpGateway;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
 use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
 use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
 use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::token_common::{active_signing_key, jwt_issuer};
+use crate::application::use_cases::auth::client_authentication::ClientAuthenticationUseCase;
+use crate::application::use_cases::auth::id_token::IdTokenUseCase;
+use crate::dto::auth::client_authentication::request::{ClientAuthenticationRequest, ClientCredentials};
 use crate::application::spi::repository::RepositoryInterface;
+use crate::dto::auth::token::access_token::Confirmation;
+use crate::dto::auth::token::id_token::IdTokenRequest;
 use crate::dto::auth::token::request::TokenRefreshRequest;
 use crate::dto::auth::token::response::TokenResponse;
 use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::dpop::token_type;
 use crate::utils::hasher::hash_sha256;
 use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
-use crate::domain::idp::IdPIdTokenRequest;
 use crate::domain::oauth_client::OauthClient;
 use crate::domain::oauth_session::OauthSession;
 use crate::domain::oauth_token::OauthToken;
-use crate::utils::token::{generate_access_token, generate_refresh_token};
+use crate::utils::token::{generate_access_token, AccessTokenParams, generate_refresh_token};
+
+pub const GRANT_TYPE: &str = "refresh_token";
 
 pub struct TokenRefreshUseCase {
+    cache: Arc<RedisCache>,
     repository: Arc<OAuthSessionRepository>,
     token_repository: Arc<OAuthTokenRepository>,
     client_repository: Arc<OAuthClientRepository>,
+    signing_key_repository: Arc<OAuthSigningKeyRepository>,
     idp_gateway: Arc<IdpGateway>,
+    jwks_gateway: Arc<JwksGateway>,
 }
 
 impl UseCaseInterface for TokenRefreshUseCase {
@@ -33,79 +46,115 @@ impl UseCaseInterface for TokenRefreshUseCase {
     async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
         let arc_data = Arc::new(data);
 
-        if let Err(e) = self.validate_request(arc_data.clone()) {
-            return Err(e);
-        }
+        let jwt_iss = jwt_issuer()?;
+        let signing_key = active_signing_key(&self.signing_key_repository).await?;
 
-        let (jwt_iss, encoding_key) = match self.validate_envs() {
-            Ok(e) => e,
-            Err(e) => return Err(e)
+        let refresh_token_hash = hash_sha256(arc_data.refresh_token.clone().as_str());
+
+        let Ok(token) = self.token_repository.get_by_refresh_token(refresh_token_hash.clone()).await else {
+            return Err(self.detect_reuse(refresh_token_hash).await);
         };
 
-        let Ok(token) = self.token_repository.get_by_refresh_token(hash_sha256(arc_data.refresh_token.clone().as_str())).await else {
-            return Err(ApiError::new(String::from("Invalid refresh token"), StatusCode::BAD_REQUEST));
+        if !token.is_active() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Refresh token has been revoked"));
+        }
+
+        if token.refresh_token_expires_at.is_some_and(|e| e < chrono::Utc::now().naive_utc()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Refresh token has expired"));
+        }
+
+        if token.dpop_jkt.is_some() && token.dpop_jkt != arc_data.dpop_jkt {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Refresh token is bound to a different DPoP key"));
+        }
+
+        let Some(session_id) = token.session_id else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Session not found"));
         };
 
-        let Ok(repo_session) = self.repository.get(token.session_id.unwrap()).await else {
-            return Err(ApiError::new(String::from("Session not found"), StatusCode::BAD_REQUEST));
+        let Ok(repo_session) = self.repository.get(session_id).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Session not found"));
         };
         
         let scopes = repo_session.scopes.clone().unwrap_or(vec![]);
 
         if !scopes.contains(&"offline_access".to_string()) {
-            return Err(ApiError::new(String::from("offline_access is required for refresh token"), StatusCode::BAD_REQUEST));
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "offline_access is required for refresh token"));
         }
 
         let Ok(repo_client) = self.client_repository.get_by_slug(repo_session.client_id.clone().unwrap()).await else {
-            return Err(ApiError::new(String::from("Client not found"), StatusCode::BAD_REQUEST));
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Client not found"));
         };
 
-        if let Err(e) = self.validate_client(arc_data.clone(), repo_session.clone(), repo_client.clone()) {
-            return Err(e);
-        }
+        self.validate_client(arc_data.clone(), repo_session.clone(), repo_client.clone()).await?;
+        self.validate_request(&repo_session, &repo_client)?;
 
-        let id_token = match self.idp_gateway.get_id_token_v1(IdPIdTokenRequest {
-            user_id: repo_session.user_id.clone().unwrap().to_string(),
-            client_id: repo_session.client_id.clone().unwrap().to_string(),
-            scopes: repo_session.scopes.clone().unwrap_or(vec![])
-        }).await {
-            Ok(e) => e,
-            Err(e) => return Err(e)
-        };
+        let x5t_s256 = arc_data.client_context.client_certificate.as_ref().map(|e| e.thumbprint());
+
+        if repo_client.is_public() && token.x5t_s256.is_some() && token.x5t_s256 != x5t_s256 {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Refresh token is bound to a different client certificate"));
+        }
 
         let Ok(access_token) = generate_access_token(
-            scopes,
+            AccessTokenParams {
+                scopes,
+                jwt_iss,
+                session_id: repo_session.id.unwrap().to_string(),
+                sub: repo_session.user_id.unwrap().to_string(),
+                client_id: repo_session.client_id.clone().unwrap().to_string(),
+                auth_time: repo_session.auth_time,
+                cnf: Confirmation::new(arc_data.dpop_jkt.clone(), x5t_s256.clone()),
+            },
             chrono::Utc::now(),
-            jwt_iss,
-            repo_session.id.unwrap().to_string(),
-            repo_session.user_id.unwrap().clone(),
-            repo_session.client_id.unwrap().to_string(),
-            encoding_key
+            &signing_key
         ) else {
             return Err(ApiError::new(String::from("Failed to generate access token"), StatusCode::INTERNAL_SERVER_ERROR));
         };
 
+        let id_token = IdTokenUseCase::new(self.signing_key_repository.clone(), self.idp_gateway.clone()).handle(IdTokenRequest {
+            session: repo_session,
+            access_token: access_token.clone(),
+            code: None,
+            nonce: None,
+        }).await?.data;
+
         let refresh_token = generate_refresh_token();
         let exp = chrono::Utc::now().add(chrono::Duration::days(7));
 
-        if let Err(_) = self.token_repository.edit(token.id.unwrap(), OauthToken {
+        let token_id = token.id.unwrap();
+
+        let rotated = self.token_repository.rotate_refresh_token(token_id, OauthToken {
             id: None,
-            session_id: None,
+            session_id: token.session_id,
             access_token: Some(hash_sha256(access_token.clone().as_str())),
             refresh_token: Some(hash_sha256(refresh_token.clone().as_str())),
+            previous_refresh_token: token.refresh_token.clone(),
             refresh_token_expires_at: Some(exp.naive_utc()),
+            id_token: Some(hash_sha256(id_token.clone().as_str())),
+            subject_token_id: None,
+            actor_token_id: None,
+            previous_token_id: None,
+            dpop_jkt: arc_data.dpop_jkt.clone(),
+            x5t_s256,
+            client_id: token.client_id.clone(),
             status: None,
             created_at: None,
             updated_at: None,
-        }, vec!["access_token", "refresh_token", "refresh_token_expires_at"]).await {
-            return Err(ApiError::new(String::from("Failed to save token"), StatusCode::INTERNAL_SERVER_ERROR))
+        }).await;
+
+        match rotated {
+            Ok(Some(_)) => {},
+            // Another request rotated the token first, which is a reuse of the same refresh token.
+            Ok(None) => return Err(self.detect_reuse(refresh_token_hash).await),
+            Err(_) => return Err(ApiError::new(String::from("Failed to save token"), StatusCode::INTERNAL_SERVER_ERROR)),
         }
 
         Ok(ApiSuccess::new(
             TokenResponse {
                 access_token,
-                refresh_token,
-                id_token,
+                token_type: token_type(&arc_data.dpop_jkt),
+                refresh_token: Some(refresh_token),
+                id_token: Some(id_token),
+                issued_token_type: None,
             },
             StatusCode::OK
         ))
@@ -114,47 +163,179 @@ impl UseCaseInterface for TokenRefreshUseCase {
 
 impl TokenRefreshUseCase {
     pub fn new(
+        cache: Arc<RedisCache>,
         repository: Arc<OAuthSessionRepository>,
         token_repository: Arc<OAuthTokenRepository>,
         client_repository: Arc<OAuthClientRepository>,
+        signing_key_repository: Arc<OAuthSigningKeyRepository>,
         idp_gateway: Arc<IdpGateway>,
+        jwks_gateway: Arc<JwksGateway>,
     ) -> Self {
-        Self { repository, token_repository, client_repository, idp_gateway  }
+        Self { cache, repository, token_repository, client_repository, signing_key_repository, idp_gateway, jwks_gateway }
     }
 
-    fn validate_request(&self, data: Arc<TokenRefreshRequest>) -> Result<(), ApiError> {
-        if data.grant_type != "refresh_token" {
-            return Err(ApiError::new(String::from("Invalid grant type"), StatusCode::BAD_REQUEST));
-        }
+    async fn validate_client(&self, data: Arc<TokenRefreshRequest>, session: OauthSession, client: OauthClient) -> Result<(), ApiError> {
+        if session.client_id.unwrap() != data.client_id {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidGrant, "Invalid client"));
+        };
+
+        ClientAuthenticationUseCase::new(self.cache.clone(), self.jwks_gateway.clone()).handle(ClientAuthenticationRequest {
+            client: client.clone(),
+            credentials: ClientCredentials::from(&*data),
+            allow_public: true,
+        }).await?;
 
         Ok(())
     }
 
-    fn validate_client(&self, data: Arc<TokenRefreshRequest>, session: OauthSession, client: OauthClient) -> Result<(), ApiError> {
-        if session.client_id.unwrap() != data.client_id {
-            return Err(ApiError::new(String::from("Invalid client"), StatusCode::BAD_REQUEST));
-        };
+    fn validate_request(&self, session: &OauthSession, client: &OauthClient) -> Result<(), ApiError> {
+        if !client.allows_grant_type(GRANT_TYPE) {
+            return Err(ApiError::oauth(OAuthErrorCode::UnauthorizedClient, "Grant type not allowed for this client"));
+        }
 
-        if data.client_secret != client.secret.unwrap() {
-            return Err(ApiError::new(String::from("Invalid client"), StatusCode::BAD_REQUEST));
+        let allowed_scopes = client.scopes.clone().unwrap_or_default();
+
+        if let Some(e) = session.scopes.clone().unwrap_or_default().iter().find(|e| !allowed_scopes.contains(e)) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidScope, format!("Scope {} is not allowed for this client", e)));
         }
 
         Ok(())
     }
 
-    fn validate_envs(&self) -> Result<(String, EncodingKey), ApiError> {
-        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
-            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+    async fn detect_reuse(&self, refresh_token_hash: String) -> ApiError {
+        let invalid = ApiError::oauth(OAuthErrorCode::InvalidGrant, "Invalid refresh token");
+
+        let Ok(token) = self.token_repository.get_by_previous_refresh_token(refresh_token_hash).await else {
+            return invalid;
+        };
+
+        let Some(session_id) = token.session_id else {
+            return invalid;
+        };
+
+        let Ok(session) = self.repository.get(session_id).await else {
+            return invalid;
         };
 
-        let Ok(jwt_pk) = env::var("JWT_PRIVATE_KEY") else {
-            return Err(ApiError::new(String::from("JWT_PRIVATE_KEY not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        let Ok(client) = self.client_repository.get_by_slug(session.client_id.unwrap_or_default()).await else {
+            return invalid;
         };
 
-        let Ok(encoding_key) = EncodingKey::from_rsa_pem(jwt_pk.replace("\\n", "\n").as_bytes()) else {
-            return Err(ApiError::new(String::from("Failed to parse JWT_PRIVATE_KEY"), StatusCode::INTERNAL_SERVER_ERROR));
+        if !client.is_public() || !token.is_active() {
+            return invalid;
+        }
+
+        let Some(token_id) = token.id else {
+            return invalid;
         };
 
-        Ok((jwt_iss, encoding_key))
+        if self.token_repository.revoke_lineage(token_id).await.is_err() {
+            return ApiError::new(String::from("Failed to revoke token"), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+
+        ApiError::oauth(OAuthErrorCode::InvalidGrant, "Refresh token has already been used")
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use crate::adapters::spi::gateways::idp::IdpGateway;
+    use crate::adapters::spi::gateways::jwks::JwksGateway;
+    use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+    use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+    use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+    use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+    use crate::application::spi::repository::RepositoryInterface;
+    use crate::domain::oauth_client::OauthClient;
+    use crate::domain::oauth_session::OauthSession;
+    use crate::domain::oauth_token::OauthToken;
+    use crate::utils::test_support::{cache, database, unique};
+    use super::TokenRefreshUseCase;
+
+    fn token(session_id: Option<uuid::Uuid>, refresh_token: &str) -> OauthToken {
+        OauthToken {
+            id: None,
+            session_id,
+            access_token: Some(unique("access")),
+            refresh_token: Some(refresh_token.to_string()),
+            previous_refresh_token: None,
+            refresh_token_expires_at: None,
+            id_token: None,
+            subject_token_id: None,
+            actor_token_id: None,
+            previous_token_id: None,
+            dpop_jkt: None,
+            x5t_s256: None,
+            client_id: None,
+            status: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn reused_refresh_token_revokes_the_lineage_of_a_public_client() {
+        let (Some(db), Some(cache)) = (database().await, cache()) else { return };
+
+        let client_repository = Arc::new(OAuthClientRepository::new(String::from("oauth_client"), db.clone()));
+        let session_repository = Arc::new(OAuthSessionRepository::new(String::from("oauth_session"), db.clone()));
+        let token_repository = Arc::new(OAuthTokenRepository::new(String::from("oauth_token"), db.clone()));
+
+        let slug = unique("public");
+        client_repository.insert(OauthClient {
+            name: Some(slug.clone()),
+            slug: Some(slug.clone()),
+            client_type: Some(String::from(OauthClient::TYPE_PUBLIC)),
+            ..OauthClient::default()
+        }).await.unwrap();
+
+        let session = session_repository.insert(OauthSession {
+            id: None,
+            client_id: Some(slug),
+            user_id: Some(uuid::Uuid::new_v4()),
+            scopes: Some(vec![String::from("openid"), String::from("offline_access")]),
+            redirect_uri: None,
+            state: None,
+            response_type: None,
+            response_mode: None,
+            nonce: None,
+            code_challenge: None,
+            code_challenge_method: None,
+            status: None,
+            consent_granted_at: None,
+            auth_time: None,
+            acr: None,
+            amr: None,
+            login_id: None,
+            created_at: None,
+            updated_at: None,
+        }).await.unwrap();
+
+        let refresh_token = unique("refresh");
+        let first = token_repository.insert(token(session.id, &refresh_token)).await.unwrap();
+        let rotated = token_repository.rotate_refresh_token(first.id.unwrap(), OauthToken {
+            previous_refresh_token: Some(refresh_token.clone()),
+            ..token(session.id, &unique("refresh"))
+        }).await.unwrap().unwrap();
+
+        let use_case = TokenRefreshUseCase::new(
+            cache,
+            session_repository,
+            token_repository.clone(),
+            client_repository,
+            Arc::new(OAuthSigningKeyRepository::new(String::from("oauth_signing_key"), db)),
+            Arc::new(IdpGateway::new()),
+            Arc::new(JwksGateway::new()),
+        );
+
+        let error = use_case.detect_reuse(refresh_token).await;
+        assert_eq!(error.error_description, "Refresh token has already been used");
+
+        assert!(!token_repository.get(first.id.unwrap()).await.unwrap().is_active());
+        assert!(!token_repository.get(rotated.id.unwrap()).await.unwrap().is_active());
+
+        let error = use_case.detect_reuse(unique("refresh")).await;
+        assert_eq!(error.error_description, "Invalid refresh token");
+    }
+}
@@ -4,10 +4,14 @@ use redis::AsyncCommands;
 use crate::adapters::spi::cache::redis::RedisCache;
 use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
 use crate::application::api::use_case::UseCaseInterface;
+use crate::utils::mtls::ClientCertificate;
 use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
 use crate::adapters::spi::gateways::idp::IdpGateway;
 use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
 use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::auth::dpop::DpopUseCase;
+use crate::dto::auth::dpop::request::DpopRequest;
 use crate::dto::auth::userinfo::request::UserinfoRequest;
 use crate::dto::auth::userinfo::response::UserinfoResponse;
 use crate::utils::hasher::hash_sha256;
@@ -17,7 +21,9 @@ pub struct UserinfoUseCase {
     repository: Arc<OAuthSessionRepository>,
     token_repository: Arc<OAuthTokenRepository>,
     idp_gateway: Arc<IdpGateway>,
-    access_token: String
+    access_token: String,
+    dpop: DpopRequest,
+    client_certificate: Option<ClientCertificate>,
 }
 
 impl UseCaseInterface for UserinfoUseCase {
@@ -25,31 +31,59 @@ impl UseCaseInterface for UserinfoUseCase {
     type Response = UserinfoResponse;
 
     async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Ok(token) = self.token_repository.get_by_access_token(hash_sha256(self.access_token.clone().as_str())).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidToken, "Invalid access token"));
+        };
+
+        if !token.is_active() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidToken, "Access token has been revoked"));
+        }
+
+        let Some(session_id) = token.session_id else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidToken, "Access token was not issued for a user"));
+        };
+
+        let Ok(session) = self.repository.get(session_id).await else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidToken, "Invalid session"));
+        };
+
+        if let Some(dpop_jkt) = token.dpop_jkt.as_ref() {
+            let proof_jkt = DpopUseCase::new(self.cache.clone())
+                .handle(DpopRequest { client_id: session.client_id.clone(), ..self.dpop.clone() })
+                .await
+                .map_err(|e| e.with_status(StatusCode::UNAUTHORIZED))?
+                .data;
+
+            if proof_jkt.as_ref() != Some(dpop_jkt) {
+                return Err(ApiError::oauth(OAuthErrorCode::InvalidDpopProof, "DPoP proof does not match the access token").with_status(StatusCode::UNAUTHORIZED));
+            }
+        }
+
+        if token.x5t_s256.is_some() && token.x5t_s256 != self.client_certificate.as_ref().map(|e| e.thumbprint()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidToken, "Access token is bound to a different client certificate"));
+        }
+
+        if session.user_id.map(|e| e.to_string()) != Some(data.sub.clone()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidToken, "Invalid sub"));
+        }
+
         let Ok(mut conn) = self.cache.get_pool().await else {
             return Err(ApiError::new(String::from("Getting cache connection"), StatusCode::INTERNAL_SERVER_ERROR))
         };
 
-        let Ok(value) = conn.get::<String, Option<String>>(data.sub.clone()).await else  {
-            return Err(ApiError::new(String::from("Invalid authorization code"), StatusCode::BAD_REQUEST));
+        let Ok(value) = conn.get::<String, Option<String>>(format!("sub:{}", data.sub.clone())).await else  {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRequest, "Invalid authorization code"));
         };
 
-        if value.is_some() {
-            let Ok(result) = serde_json::from_str::<Self::Response>(&value.unwrap()) else {
+        if let Some(value) = value {
+            let Ok(result) = serde_json::from_str::<Self::Response>(&value) else {
                 return Err(ApiError::new(String::from("Failed to parse session data"), StatusCode::INTERNAL_SERVER_ERROR));
             };
 
             return Ok(ApiSuccess::new(result, StatusCode::OK))
         };
 
-        let Ok(token) = self.token_repository.get_by_access_token(hash_sha256(self.access_token.clone().as_str())).await else {
-            return Err(ApiError::new(String::from("Invalid access token"), StatusCode::BAD_REQUEST));
-        };
-
-        let Ok(session) = self.repository.get(token.session_id.unwrap()).await else {
-            return Err(ApiError::new(String::from("Invalid session"), StatusCode::BAD_REQUEST));
-        };
-
-        let Ok(user) = self.idp_gateway.get_user_by_id_v1(session.user_id.clone().unwrap()).await else {
+        let Ok(user) = self.idp_gateway.get_user_by_id_v1(session.user_id.unwrap()).await else {
             return Err(ApiError::new(String::from("Failed to get user"), StatusCode::INTERNAL_SERVER_ERROR));
         };
 
@@ -64,7 +98,7 @@ impl UseCaseInterface for UserinfoUseCase {
 
         let string_result = serde_json::to_string(&result).unwrap();
 
-        if let Err(_) = conn.set_ex::<String, String, ()>(format!("sub:{}", data.sub.clone()), string_result, 60 * 5).await {
+        if conn.set_ex::<String, String, ()>(format!("sub:{}", data.sub.clone()), string_result, 60 * 5).await.is_err() {
             return Err(ApiError::new("Failed to store sub".to_string(), StatusCode::INTERNAL_SERVER_ERROR))
         }
 
@@ -78,14 +112,28 @@ impl UserinfoUseCase {
         repository: Arc<OAuthSessionRepository>,
         token_repository: Arc<OAuthTokenRepository>,
         idp_gateway: Arc<IdpGateway>,
-        access_token: String
+        access_token: String,
+        dpop: DpopRequest,
+        client_certificate: Option<ClientCertificate>,
     ) -> Self {
         Self {
             cache,
             repository,
             token_repository,
             idp_gateway,
-            access_token
+            access_token,
+            dpop,
+            client_certificate,
+        }
+    }
+
+    /// The client the access token was issued to, which DPoP nonces for this request are scoped to.
+    pub async fn client_id(&self) -> Option<String> {
+        let token = self.token_repository.get_by_access_token(hash_sha256(self.access_token.as_str())).await.ok()?;
+
+        match token.session_id {
+            Some(session_id) => self.repository.get(session_id).await.ok()?.client_id,
+            None => token.client_id,
         }
     }
 }
\ No newline at end of file
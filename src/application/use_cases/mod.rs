@@ -1 +1,4 @@
 pub mod auth;
+pub mod well_known;
+pub mod admin;
+pub mod registration;
\ No newline at end of file
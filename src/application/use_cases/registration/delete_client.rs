@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::registration::registration_authentication::RegistrationAuthenticationUseCase;
+use crate::dto::registration::request::ClientConfigurationRequest;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+
+pub struct DeleteClientUseCase {
+    repository: Arc<OAuthClientRepository>,
+    session_repository: Arc<OAuthSessionRepository>,
+    token_repository: Arc<OAuthTokenRepository>,
+}
+
+impl UseCaseInterface for DeleteClientUseCase {
+    type Request = ClientConfigurationRequest;
+    type Response = ();
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let client = RegistrationAuthenticationUseCase::new(self.repository.clone()).handle(data).await?.data;
+
+        let client_id = client.slug.clone().unwrap_or_default();
+
+        let Ok(session_ids) = self.session_repository.end_by_client(client_id.clone()).await else {
+            return Err(ApiError::new(String::from("Failed to end client sessions"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        if self.token_repository.revoke_by_client(client_id, session_ids).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to revoke client tokens"), StatusCode::INTERNAL_SERVER_ERROR));
+        }
+
+        if self.repository.delete(client.id.unwrap()).await.is_err() {
+            return Err(ApiError::new(String::from("Failed to delete client"), StatusCode::INTERNAL_SERVER_ERROR));
+        }
+
+        Ok(ApiSuccess::new((), StatusCode::NO_CONTENT))
+    }
+}
+
+impl DeleteClientUseCase {
+    pub fn new(repository: Arc<OAuthClientRepository>, session_repository: Arc<OAuthSessionRepository>, token_repository: Arc<OAuthTokenRepository>) -> Self {
+        Self { repository, session_repository, token_repository }
+    }
+}
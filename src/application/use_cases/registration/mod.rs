@@ -0,0 +1,5 @@
+pub mod register_client;
+pub mod registration_authentication;
+pub mod read_client;
+pub mod update_client;
+pub mod delete_client;
\ No newline at end of file
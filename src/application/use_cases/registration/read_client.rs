@@ -0,0 +1,34 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::registration::register_client::RegisterClientUseCase;
+use crate::application::use_cases::registration::registration_authentication::RegistrationAuthenticationUseCase;
+use crate::dto::registration::request::ClientConfigurationRequest;
+use crate::dto::registration::response::ClientInformationResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+
+pub struct ReadClientUseCase {
+    repository: Arc<OAuthClientRepository>,
+    registration_path: String,
+}
+
+impl UseCaseInterface for ReadClientUseCase {
+    type Request = ClientConfigurationRequest;
+    type Response = ClientInformationResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let client = RegistrationAuthenticationUseCase::new(self.repository.clone()).handle(data).await?.data;
+
+        Ok(ApiSuccess::new(
+            RegisterClientUseCase::client_information(&client, None, None, &self.registration_path)?,
+            StatusCode::OK,
+        ))
+    }
+}
+
+impl ReadClientUseCase {
+    pub fn new(repository: Arc<OAuthClientRepository>, registration_path: String) -> Self {
+        Self { repository, registration_path }
+    }
+}
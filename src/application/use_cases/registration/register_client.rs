@@ -0,0 +1,421 @@
+use std::env;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use reqwest::Url;
+use sqlx::types::Json;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::auth::bc_authorize::{DELIVERY_MODES_SUPPORTED, DELIVERY_MODE_PING, DELIVERY_MODE_POLL};
+use crate::application::use_cases::auth::client_authentication::{AUTH_METHOD_NONE, CLIENT_SECRET_BASIC, CLIENT_SECRET_JWT, CLIENT_SECRET_POST, PRIVATE_KEY_JWT, TOKEN_ENDPOINT_AUTH_METHODS_SUPPORTED};
+use crate::application::use_cases::auth::par::RESPONSE_TYPES_SUPPORTED;
+use crate::application::use_cases::auth::{bc_authorize, token_authorization_code, token_client_credentials, token_exchange, token_refresh};
+use crate::application::use_cases::well_known::discovery::{GRANT_TYPES_SUPPORTED, SCOPES_SUPPORTED};
+use crate::domain::oauth_client::OauthClient;
+use crate::dto::registration::request::{ClientMetadata, ClientRegistrationRequest};
+use crate::dto::registration::response::ClientInformationResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::encryption::encrypt;
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::hasher::{hash_secret, hash_sha256};
+use crate::utils::mtls::{SELF_SIGNED_TLS_CLIENT_AUTH, TLS_CLIENT_AUTH};
+use crate::utils::outbound::{allow_private_networks, validate_outbound_uri};
+use crate::utils::redirect_uri::{validate_native_redirect_uri, validate_web_redirect_uri};
+use crate::utils::token::generate_client_secret;
+
+pub const APPLICATION_TYPES_SUPPORTED: [&str; 2] = [OauthClient::APPLICATION_TYPE_WEB, OauthClient::APPLICATION_TYPE_NATIVE];
+pub const METADATA_FIELDS: [&str; 28] = [
+    "name",
+    "secret",
+    "secret_hash",
+    "previous_secret",
+    "previous_secret_hash",
+    "previous_secret_expires_at",
+    "secret_rotated_at",
+    "urls",
+    "request_uris",
+    "scopes",
+    "logos",
+    "post_logout_redirect_uris",
+    "backchannel_logout_uri",
+    "frontchannel_logout_uri",
+    "grant_types",
+    "backchannel_token_delivery_mode",
+    "backchannel_client_notification_endpoint",
+    "client_type",
+    "application_type",
+    "token_endpoint_auth_method",
+    "tls_client_auth_subject_dn",
+    "tls_client_auth_san_dns",
+    "tls_client_auth_san_uri",
+    "tls_client_auth_san_ip",
+    "tls_client_auth_san_email",
+    "tls_client_certificate_thumbprint",
+    "jwks",
+    "jwks_uri",
+];
+const DEFAULT_SCOPE: &str = "openid";
+const PUBLIC_GRANT_TYPES: [&str; 2] = [token_authorization_code::GRANT_TYPE, token_refresh::GRANT_TYPE];
+const SECRET_AUTH_METHODS: [&str; 3] = [CLIENT_SECRET_BASIC, CLIENT_SECRET_POST, CLIENT_SECRET_JWT];
+/// Grants that act without a user at the authorization endpoint, which anonymous registrants cannot give themselves.
+const TRUSTED_GRANT_TYPES: [&str; 3] = [token_client_credentials::GRANT_TYPE, token_exchange::GRANT_TYPE, bc_authorize::GRANT_TYPE];
+
+pub struct RegisterClientUseCase {
+    repository: Arc<OAuthClientRepository>,
+    registration_path: String,
+}
+
+impl UseCaseInterface for RegisterClientUseCase {
+    type Request = ClientRegistrationRequest;
+    type Response = ClientInformationResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        Self::validate_initial_access_token(data.initial_access_token.as_deref())?;
+
+        let metadata = Self::validate_metadata(data.metadata, Self::registration_protected())?;
+
+        let mut client = OauthClient {
+            slug: Some(uuid::Uuid::new_v4().to_string()),
+            ..Default::default()
+        };
+
+        let client_secret = Self::apply_metadata(&mut client, metadata)?;
+        let registration_access_token = generate_client_secret();
+        client.registration_access_token = Some(hash_sha256(registration_access_token.as_str()));
+
+        let Ok(client) = self.repository.insert(client).await else {
+            return Err(ApiError::new(String::from("Failed to register client"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        Ok(ApiSuccess::new(
+            Self::client_information(&client, client_secret, Some(registration_access_token), &self.registration_path)?,
+            StatusCode::CREATED,
+        ))
+    }
+}
+
+impl RegisterClientUseCase {
+    pub fn new(repository: Arc<OAuthClientRepository>, registration_path: String) -> Self {
+        Self { repository, registration_path }
+    }
+
+    /// Validates client metadata. Registrants that are not `trusted` registered without an initial access token.
+    pub fn validate_metadata(mut metadata: ClientMetadata, trusted: bool) -> Result<ClientMetadata, ApiError> {
+        let auth_method = metadata.token_endpoint_auth_method.get_or_insert_with(|| String::from(CLIENT_SECRET_BASIC)).clone();
+
+        if auth_method != AUTH_METHOD_NONE && !TOKEN_ENDPOINT_AUTH_METHODS_SUPPORTED.contains(&auth_method.as_str()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Unsupported token endpoint auth method"));
+        }
+
+        let application_type = metadata.application_type.get_or_insert_with(|| String::from(OauthClient::APPLICATION_TYPE_WEB)).clone();
+
+        if !APPLICATION_TYPES_SUPPORTED.contains(&application_type.as_str()) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Unsupported application type"));
+        }
+
+        let grant_types = metadata.grant_types.get_or_insert_with(|| vec![String::from(token_authorization_code::GRANT_TYPE)]).clone();
+
+        if grant_types.is_empty() || grant_types.iter().any(|e| !GRANT_TYPES_SUPPORTED.contains(&e.as_str())) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Unsupported grant type"));
+        }
+
+        if !trusted && let Some(e) = grant_types.iter().find(|e| TRUSTED_GRANT_TYPES.contains(&e.as_str())) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, format!("Grant type {} requires an initial access token", e)));
+        }
+
+        if auth_method == AUTH_METHOD_NONE && grant_types.iter().any(|e| !PUBLIC_GRANT_TYPES.contains(&e.as_str())) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Public clients can only use the authorization_code and refresh_token grants"));
+        }
+
+        let uses_code = grant_types.iter().any(|e| e == token_authorization_code::GRANT_TYPE);
+        let response_types = metadata.response_types.get_or_insert_with(|| match uses_code {
+            true => RESPONSE_TYPES_SUPPORTED.iter().map(|e| e.to_string()).collect(),
+            false => vec![],
+        }).clone();
+
+        if response_types.iter().any(|e| !RESPONSE_TYPES_SUPPORTED.contains(&e.as_str())) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Unsupported response type"));
+        }
+
+        if response_types.is_empty() == uses_code {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Response types do not match grant types"));
+        }
+
+        let redirect_uris = metadata.redirect_uris.get_or_insert_with(Vec::new).clone();
+
+        if uses_code && redirect_uris.is_empty() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidRedirectUri, "Redirect URIs are required for the authorization_code grant"));
+        }
+
+        for uri in redirect_uris.iter().chain(metadata.post_logout_redirect_uris.iter().flatten()) {
+            let result = match application_type.as_str() {
+                OauthClient::APPLICATION_TYPE_NATIVE => validate_native_redirect_uri(uri).map(|_| ()),
+                _ => validate_web_redirect_uri(uri),
+            };
+
+            result.map_err(|e| ApiError::oauth(OAuthErrorCode::InvalidRedirectUri, format!("Invalid redirect URI {}: {}", uri, e)))?;
+        }
+
+        if metadata.request_uris.iter().flatten().any(|e| !Self::is_outbound_url(e.split('#').next().unwrap_or_default(), &["https"])) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Invalid request URI"));
+        }
+
+        let scope = metadata.scope.get_or_insert_with(|| String::from(DEFAULT_SCOPE)).clone();
+
+        if scope.split(' ').any(|e| !SCOPES_SUPPORTED.contains(&e)) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Unsupported scope"));
+        }
+
+        if metadata.logo_uri.as_ref().is_some_and(|e| !Self::is_url(e, &["http", "https"])) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Invalid logo URI"));
+        }
+
+        if metadata.backchannel_logout_uri.as_ref().is_some_and(|e| !Self::is_outbound_url(e, &["http", "https"])) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Invalid backchannel logout URI"));
+        }
+
+        if metadata.frontchannel_logout_uri.as_ref().is_some_and(|e| !Self::is_outbound_url(e, &["http", "https"])) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Invalid frontchannel logout URI"));
+        }
+
+        if metadata.jwks.is_some() && metadata.jwks_uri.is_some() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "jwks and jwks_uri cannot be used together"));
+        }
+
+        if metadata.jwks_uri.as_ref().is_some_and(|e| !Self::is_outbound_url(e, &["https"])) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Invalid jwks_uri"));
+        }
+
+        if auth_method == PRIVATE_KEY_JWT && metadata.jwks.is_none() && metadata.jwks_uri.is_none() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "private_key_jwt requires jwks or jwks_uri"));
+        }
+
+        let tls_identities = [
+            &metadata.tls_client_auth_subject_dn,
+            &metadata.tls_client_auth_san_dns,
+            &metadata.tls_client_auth_san_uri,
+            &metadata.tls_client_auth_san_ip,
+            &metadata.tls_client_auth_san_email,
+        ].iter().filter(|e| e.is_some()).count();
+
+        if auth_method == TLS_CLIENT_AUTH && tls_identities != 1 {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "tls_client_auth requires exactly one certificate identity"));
+        }
+
+        if auth_method == SELF_SIGNED_TLS_CLIENT_AUTH && metadata.tls_client_certificate_thumbprint.is_none() {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "self_signed_tls_client_auth requires tls_client_certificate_thumbprint"));
+        }
+
+        if grant_types.iter().any(|e| e == bc_authorize::GRANT_TYPE) {
+            let mode = metadata.backchannel_token_delivery_mode.get_or_insert_with(|| String::from(DELIVERY_MODE_POLL)).clone();
+
+            if !DELIVERY_MODES_SUPPORTED.contains(&mode.as_str()) {
+                return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Unsupported backchannel token delivery mode"));
+            }
+
+            if mode == DELIVERY_MODE_PING && !metadata.backchannel_client_notification_endpoint.as_ref().is_some_and(|e| Self::is_outbound_url(e, &["https"])) {
+                return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "Invalid backchannel client notification endpoint"));
+            }
+        }
+
+        Ok(metadata)
+    }
+
+    pub fn apply_metadata(client: &mut OauthClient, metadata: ClientMetadata) -> Result<Option<String>, ApiError> {
+        let auth_method = metadata.token_endpoint_auth_method.unwrap_or_default();
+
+        client.name = metadata.client_name.or(client.slug.clone());
+        client.urls = metadata.redirect_uris;
+        client.request_uris = Some(metadata.request_uris.unwrap_or_default());
+        client.scopes = metadata.scope.map(|e| e.split(' ').map(|e| e.to_string()).collect());
+        client.logos = Some(Json(metadata.logo_uri.into_iter().collect()));
+        client.post_logout_redirect_uris = Some(metadata.post_logout_redirect_uris.unwrap_or_default());
+        client.backchannel_logout_uri = metadata.backchannel_logout_uri;
+        client.frontchannel_logout_uri = metadata.frontchannel_logout_uri;
+        client.grant_types = metadata.grant_types;
+        client.backchannel_token_delivery_mode = metadata.backchannel_token_delivery_mode;
+        client.backchannel_client_notification_endpoint = metadata.backchannel_client_notification_endpoint;
+        client.client_type = Some(String::from(match auth_method.as_str() {
+            AUTH_METHOD_NONE => OauthClient::TYPE_PUBLIC,
+            _ => OauthClient::TYPE_CONFIDENTIAL,
+        }));
+        client.application_type = metadata.application_type;
+        client.tls_client_auth_subject_dn = metadata.tls_client_auth_subject_dn;
+        client.tls_client_auth_san_dns = metadata.tls_client_auth_san_dns;
+        client.tls_client_auth_san_uri = metadata.tls_client_auth_san_uri;
+        client.tls_client_auth_san_ip = metadata.tls_client_auth_san_ip;
+        client.tls_client_auth_san_email = metadata.tls_client_auth_san_email;
+        client.tls_client_certificate_thumbprint = metadata.tls_client_certificate_thumbprint;
+        client.jwks = metadata.jwks.map(Json);
+        client.jwks_uri = metadata.jwks_uri;
+
+        let keep_plaintext = auth_method == CLIENT_SECRET_JWT;
+
+        if !keep_plaintext {
+            client.secret = None;
+            client.previous_secret = None;
+        }
+
+        if !SECRET_AUTH_METHODS.contains(&auth_method.as_str()) {
+            client.secret_hash = None;
+            client.previous_secret_hash = None;
+            client.previous_secret_expires_at = None;
+        }
+
+        client.token_endpoint_auth_method = Some(auth_method.clone());
+
+        let has_secret = client.secret_hash.is_some() && (!keep_plaintext || client.secret.is_some());
+
+        if has_secret || !SECRET_AUTH_METHODS.contains(&auth_method.as_str()) {
+            return Ok(None);
+        }
+
+        let secret = generate_client_secret();
+
+        client.secret_hash = Some(hash_secret(secret.as_str()).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?);
+        client.secret = match keep_plaintext {
+            true => Some(encrypt(&secret).map_err(|e| ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))?),
+            false => None,
+        };
+        client.secret_rotated_at = Some(chrono::Utc::now().naive_utc());
+        client.previous_secret = None;
+        client.previous_secret_hash = None;
+        client.previous_secret_expires_at = None;
+
+        Ok(Some(secret))
+    }
+
+    pub fn client_information(
+        client: &OauthClient,
+        client_secret: Option<String>,
+        registration_access_token: Option<String>,
+        registration_path: &str,
+    ) -> Result<ClientInformationResponse, ApiError> {
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let client_id = client.slug.clone().unwrap_or_default();
+
+        Ok(ClientInformationResponse {
+            client_id: client_id.clone(),
+            client_secret,
+            client_id_issued_at: client.created_at.map(|e| e.and_utc().timestamp()).unwrap_or_default(),
+            client_secret_expires_at: client.secret_hash.is_some().then_some(0),
+            registration_access_token,
+            registration_client_uri: format!("{}{}/{}", jwt_iss.trim_end_matches('/'), registration_path, client_id),
+            metadata: ClientMetadata::from(client),
+        })
+    }
+
+    /// Whether registration requires the initial access token, so that every registrant has been vetted.
+    pub fn registration_protected() -> bool {
+        env::var("REGISTRATION_INITIAL_ACCESS_TOKEN").is_ok_and(|e| !e.is_empty())
+    }
+
+    fn validate_initial_access_token(initial_access_token: Option<&str>) -> Result<(), ApiError> {
+        let Some(expected) = env::var("REGISTRATION_INITIAL_ACCESS_TOKEN").ok().filter(|e| !e.is_empty()) else {
+            if env::var("REGISTRATION_OPEN").is_ok_and(|e| e == "true") {
+                return Ok(());
+            }
+
+            return Err(ApiError::oauth(OAuthErrorCode::AccessDenied, "Client registration is closed").with_status(StatusCode::FORBIDDEN));
+        };
+
+        match initial_access_token {
+            Some(e) if hash_sha256(e) == hash_sha256(expected.as_str()) => Ok(()),
+            Some(_) => Err(ApiError::oauth(OAuthErrorCode::InvalidToken, "Invalid initial access token")),
+            None => Err(ApiError::oauth(OAuthErrorCode::InvalidToken, "Missing initial access token")),
+        }
+    }
+
+    fn is_url(uri: &str, schemes: &[&str]) -> bool {
+        Url::parse(uri).is_ok_and(|e| schemes.contains(&e.scheme()) && e.fragment().is_none())
+    }
+
+    /// Like `is_url`, for URIs the server fetches or posts to, which must also name a public host.
+    fn is_outbound_url(uri: &str, schemes: &[&str]) -> bool {
+        Self::is_url(uri, schemes) && validate_outbound_uri(uri, allow_private_networks()).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dto::registration::request::ClientMetadata;
+    use crate::utils::oauth_error::OAuthErrorCode;
+    use super::RegisterClientUseCase;
+
+    fn metadata() -> ClientMetadata {
+        ClientMetadata {
+            redirect_uris: Some(vec![String::from("https://client.example/callback")]),
+            ..ClientMetadata::default()
+        }
+    }
+
+    fn rejection(metadata: ClientMetadata) -> (OAuthErrorCode, String) {
+        let error = RegisterClientUseCase::validate_metadata(metadata, false).unwrap_err();
+
+        (error.error, error.error_description)
+    }
+
+    #[test]
+    fn accepts_https_redirect_uris() {
+        assert!(RegisterClientUseCase::validate_metadata(metadata(), false).is_ok());
+    }
+
+    #[test]
+    fn rejects_redirect_uris_a_web_client_cannot_use() {
+        for uri in ["http://client.example/callback", "https://client.example/callback#fragment", "com.client.example:/callback", "/callback"] {
+            let (error, _) = rejection(ClientMetadata {
+                redirect_uris: Some(vec![String::from(uri)]),
+                ..metadata()
+            });
+
+            assert_eq!(error, OAuthErrorCode::InvalidRedirectUri, "{}", uri);
+        }
+    }
+
+    #[test]
+    fn rejects_native_redirect_uris_on_localhost_or_a_bare_scheme() {
+        for uri in ["http://localhost:8080/callback", "http://client.example/callback", "app:/callback"] {
+            let (error, _) = rejection(ClientMetadata {
+                redirect_uris: Some(vec![String::from(uri)]),
+                application_type: Some(String::from("native")),
+                ..metadata()
+            });
+
+            assert_eq!(error, OAuthErrorCode::InvalidRedirectUri, "{}", uri);
+        }
+    }
+
+    #[test]
+    fn rejects_post_logout_redirect_uris_like_redirect_uris() {
+        let (error, _) = rejection(ClientMetadata {
+            post_logout_redirect_uris: Some(vec![String::from("http://client.example/logout")]),
+            ..metadata()
+        });
+
+        assert_eq!(error, OAuthErrorCode::InvalidRedirectUri);
+    }
+
+    #[test]
+    fn rejects_fetched_uris_that_are_not_https() {
+        let (_, description) = rejection(ClientMetadata {
+            request_uris: Some(vec![String::from("http://client.example/request.jwt")]),
+            ..metadata()
+        });
+        assert_eq!(description, "Invalid request URI");
+
+        let (_, description) = rejection(ClientMetadata {
+            jwks_uri: Some(String::from("http://client.example/jwks.json")),
+            ..metadata()
+        });
+        assert_eq!(description, "Invalid jwks_uri");
+
+        let (_, description) = rejection(ClientMetadata {
+            logo_uri: Some(String::from("javascript:alert(1)")),
+            ..metadata()
+        });
+        assert_eq!(description, "Invalid logo URI");
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::domain::oauth_client::OauthClient;
+use crate::dto::registration::request::ClientConfigurationRequest;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::hasher::hash_sha256;
+
+pub struct RegistrationAuthenticationUseCase {
+    repository: Arc<OAuthClientRepository>,
+}
+
+impl UseCaseInterface for RegistrationAuthenticationUseCase {
+    type Request = ClientConfigurationRequest;
+    type Response = OauthClient;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Some(registration_access_token) = data.registration_access_token else {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidToken, "Missing registration access token"));
+        };
+
+        let Ok(client) = self.repository.get_by_slug(data.client_id).await else {
+            return Err(Self::invalid_token());
+        };
+
+        if client.registration_access_token.as_ref() != Some(&hash_sha256(registration_access_token.as_str())) {
+            return Err(Self::invalid_token());
+        }
+
+        Ok(ApiSuccess::new(client, StatusCode::OK))
+    }
+}
+
+impl RegistrationAuthenticationUseCase {
+    pub fn new(repository: Arc<OAuthClientRepository>) -> Self {
+        Self { repository }
+    }
+
+    fn invalid_token() -> ApiError {
+        ApiError::oauth(OAuthErrorCode::InvalidToken, "Invalid registration access token")
+    }
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use actix_web::web;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::registration::register_client::{RegisterClientUseCase, METADATA_FIELDS};
+use crate::application::use_cases::registration::registration_authentication::RegistrationAuthenticationUseCase;
+use crate::dto::registration::request::ClientUpdateRequest;
+use crate::dto::registration::response::ClientInformationResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::oauth_error::OAuthErrorCode;
+use crate::utils::hasher::{hash_sha256, verify_secret};
+use crate::utils::token::generate_client_secret;
+
+pub struct UpdateClientUseCase {
+    repository: Arc<OAuthClientRepository>,
+    registration_path: String,
+}
+
+impl UseCaseInterface for UpdateClientUseCase {
+    type Request = ClientUpdateRequest;
+    type Response = ClientInformationResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let mut client = RegistrationAuthenticationUseCase::new(self.repository.clone()).handle(data.configuration).await?.data;
+
+        if client.slug.as_ref() != Some(&data.client_id) {
+            return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "client_id does not match the registered client"));
+        }
+
+        if let Some(client_secret) = data.client_secret {
+            let secret_hash = client.secret_hash.clone().unwrap_or_default();
+
+            if !web::block(move || verify_secret(&client_secret, &secret_hash)).await.unwrap_or(false) {
+                return Err(ApiError::oauth(OAuthErrorCode::InvalidClientMetadata, "client_secret does not match the registered client"));
+            }
+        }
+
+        let metadata = RegisterClientUseCase::validate_metadata(data.metadata, RegisterClientUseCase::registration_protected())?;
+        let client_secret = RegisterClientUseCase::apply_metadata(&mut client, metadata)?;
+        let registration_access_token = generate_client_secret();
+        client.registration_access_token = Some(hash_sha256(registration_access_token.as_str()));
+
+        let fields = METADATA_FIELDS.iter().copied().chain(["registration_access_token"]).collect::<Vec<&str>>();
+
+        let Ok(client) = self.repository.edit(client.id.unwrap(), client, fields).await else {
+            return Err(ApiError::new(String::from("Failed to update client"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        Ok(ApiSuccess::new(
+            RegisterClientUseCase::client_information(&client, client_secret, Some(registration_access_token), &self.registration_path)?,
+            StatusCode::OK,
+        ))
+    }
+}
+
+impl UpdateClientUseCase {
+    pub fn new(repository: Arc<OAuthClientRepository>, registration_path: String) -> Self {
+        Self { repository, registration_path }
+    }
+}
@@ -0,0 +1,99 @@
+use std::env;
+use actix_web::http::StatusCode;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::application::use_cases::auth::{bc_authorize, client_authentication, device_authorization, par, token_authorization_code, token_client_credentials, token_exchange, token_refresh};
+use crate::dto::well_known::discovery::request::DiscoveryRequest;
+use crate::dto::well_known::discovery::response::DiscoveryResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::dpop::DPOP_SIGNING_ALGORITHMS;
+use crate::utils::response_mode::RESPONSE_MODES_SUPPORTED;
+use crate::utils::token::SIGNING_ALGORITHM;
+
+pub const SCOPES_SUPPORTED: [&str; 4] = ["openid", "profile", "email", "offline_access"];
+pub const CLAIMS_SUPPORTED: [&str; 13] = ["sub", "given_name", "family_name", "gender", "birthdate", "email", "created_at", "updated_at", "auth_time", "nonce", "acr", "amr", "sid"];
+pub const GRANT_TYPES_SUPPORTED: [&str; 6] = [
+    token_authorization_code::GRANT_TYPE,
+    token_refresh::GRANT_TYPE,
+    token_client_credentials::GRANT_TYPE,
+    device_authorization::GRANT_TYPE,
+    token_exchange::GRANT_TYPE,
+    bc_authorize::GRANT_TYPE,
+];
+
+pub struct DiscoveryUseCase;
+
+impl UseCaseInterface for DiscoveryUseCase {
+    type Request = DiscoveryRequest;
+    type Response = DiscoveryResponse;
+
+    async fn handle(&self, data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let Ok(jwt_iss) = env::var("JWT_ISSUER") else {
+            return Err(ApiError::new(String::from("JWT_ISSUER not found"), StatusCode::INTERNAL_SERVER_ERROR));
+        };
+
+        let base_url = jwt_iss.trim_end_matches('/');
+        let token_endpoint_auth_methods = [
+            client_authentication::TOKEN_ENDPOINT_AUTH_METHODS_SUPPORTED.as_slice(),
+            &[client_authentication::AUTH_METHOD_NONE],
+        ].concat();
+        let client_assertion_algorithms = [
+            client_authentication::CLIENT_SECRET_JWT_ALGORITHMS.as_slice(),
+            client_authentication::PRIVATE_KEY_JWT_ALGORITHMS.as_slice(),
+        ].concat();
+
+        Ok(ApiSuccess::new(DiscoveryResponse {
+            issuer: jwt_iss.clone(),
+            authorization_endpoint: format!("{}{}", base_url, data.authorize_path),
+            token_endpoint: format!("{}{}", base_url, data.token_path),
+            userinfo_endpoint: format!("{}{}", base_url, data.userinfo_path),
+            revocation_endpoint: format!("{}{}", base_url, data.revocation_path),
+            introspection_endpoint: format!("{}{}", base_url, data.introspection_path),
+            end_session_endpoint: format!("{}{}", base_url, data.end_session_path),
+            device_authorization_endpoint: format!("{}{}", base_url, data.device_authorization_path),
+            backchannel_authentication_endpoint: format!("{}{}", base_url, data.backchannel_authentication_path),
+            pushed_authorization_request_endpoint: format!("{}{}", base_url, data.par_path),
+            registration_endpoint: format!("{}{}", base_url, data.registration_path),
+            jwks_uri: format!("{}{}", base_url, data.jwks_path),
+            require_pushed_authorization_requests: true,
+            request_parameter_supported: true,
+            request_uri_parameter_supported: true,
+            require_request_uri_registration: true,
+            request_object_signing_alg_values_supported: client_assertion_algorithms.clone(),
+            scopes_supported: Self::to_strings(&SCOPES_SUPPORTED),
+            claims_supported: Self::to_strings(&CLAIMS_SUPPORTED),
+            response_types_supported: Self::to_strings(&par::RESPONSE_TYPES_SUPPORTED),
+            response_modes_supported: Self::to_strings(&RESPONSE_MODES_SUPPORTED),
+            authorization_response_iss_parameter_supported: true,
+            grant_types_supported: Self::to_strings(&GRANT_TYPES_SUPPORTED),
+            subject_types_supported: vec![String::from("public")],
+            code_challenge_methods_supported: Self::to_strings(&par::CODE_CHALLENGE_METHODS_SUPPORTED),
+            token_endpoint_auth_methods_supported: Self::to_strings(&token_endpoint_auth_methods),
+            token_endpoint_auth_signing_alg_values_supported: client_assertion_algorithms.clone(),
+            revocation_endpoint_auth_methods_supported: Self::to_strings(&token_endpoint_auth_methods),
+            revocation_endpoint_auth_signing_alg_values_supported: client_assertion_algorithms.clone(),
+            introspection_endpoint_auth_methods_supported: Self::to_strings(&client_authentication::TOKEN_ENDPOINT_AUTH_METHODS_SUPPORTED),
+            introspection_endpoint_auth_signing_alg_values_supported: client_assertion_algorithms.clone(),
+            introspection_signing_alg_values_supported: vec![SIGNING_ALGORITHM],
+            id_token_signing_alg_values_supported: vec![SIGNING_ALGORITHM],
+            authorization_signing_alg_values_supported: vec![SIGNING_ALGORITHM],
+            dpop_signing_alg_values_supported: DPOP_SIGNING_ALGORITHMS.to_vec(),
+            tls_client_certificate_bound_access_tokens: true,
+            backchannel_logout_supported: true,
+            backchannel_logout_session_supported: true,
+            frontchannel_logout_supported: true,
+            frontchannel_logout_session_supported: true,
+            backchannel_token_delivery_modes_supported: Self::to_strings(&bc_authorize::DELIVERY_MODES_SUPPORTED),
+            backchannel_user_code_parameter_supported: false,
+        }, StatusCode::OK))
+    }
+}
+
+impl DiscoveryUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn to_strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|e| e.to_string()).collect()
+    }
+}
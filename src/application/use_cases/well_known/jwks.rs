@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::application::api::use_case::UseCaseInterface;
+use crate::domain::oauth_signing_key::OauthSigningKey;
+use crate::dto::well_known::jwks::response::JwksResponse;
+use crate::utils::api_response::{ApiError, ApiSuccess};
+use crate::utils::signing_key::SigningKey;
+
+pub struct JwksUseCase {
+    repository: Arc<OAuthSigningKeyRepository>,
+}
+
+impl UseCaseInterface for JwksUseCase {
+    type Request = ();
+    type Response = JwksResponse;
+
+    async fn handle(&self, _data: Self::Request) -> Result<ApiSuccess<Self::Response>, ApiError> {
+        let keys = match self.repository.list_by_status(vec![
+            OauthSigningKey::STATUS_NEXT,
+            OauthSigningKey::STATUS_ACTIVE,
+            OauthSigningKey::STATUS_RETIRING,
+        ]).await {
+            Ok(e) => e,
+            Err(e) => return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
+        };
+
+        let mut jwks = vec![];
+
+        for key in keys.iter() {
+            match SigningKey::from_model(key) {
+                Ok(e) => jwks.push(e.jwk.clone()),
+                Err(e) => return Err(ApiError::new(e, StatusCode::INTERNAL_SERVER_ERROR))
+            }
+        }
+
+        Ok(ApiSuccess::new(JwksResponse {
+            keys: jwks,
+        }, StatusCode::OK))
+    }
+}
+
+impl JwksUseCase {
+    pub fn new(repository: Arc<OAuthSigningKeyRepository>) -> Self {
+        Self { repository }
+    }
+}
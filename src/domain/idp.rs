@@ -23,6 +23,40 @@ pub struct IdpVerifyCredentialRequest {
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct IdpVerifyCredentialResponse {
     pub verified: bool,
+    #[serde(rename = "userId", default)]
+    pub user_id: Option<Uuid>,
+    #[serde(default)]
+    pub acr: Option<String>,
+    #[serde(default)]
+    pub amr: Option<Vec<String>>,
+    #[serde(rename = "authTime", default)]
+    pub auth_time: Option<i64>,
+}
+
+impl IdpVerifyCredentialResponse {
+    /// When the IdP authenticated the user, falling back to `now` when it doesn't say or names a time in the future.
+    pub fn authenticated_at(&self, now: chrono::DateTime<chrono::Utc>) -> chrono::NaiveDateTime {
+        self.auth_time
+            .and_then(|e| chrono::DateTime::from_timestamp(e, 0))
+            .filter(|e| *e <= now)
+            .unwrap_or(now)
+            .naive_utc()
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct IdpBackchannelAuthenticationRequest {
+    #[serde(rename = "sessionId")]
+    pub session_id: Uuid,
+    #[serde(rename = "userId")]
+    pub user_id: Uuid,
+    #[serde(rename = "clientId")]
+    pub client_id: String,
+    pub scopes: Vec<String>,
+    #[serde(rename = "bindingMessage")]
+    pub binding_message: Option<String>,
+    #[serde(rename = "expiresIn")]
+    pub expires_in: i64,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
@@ -3,3 +3,5 @@ pub mod oauth_session;
 pub mod oauth_token;
 pub mod idp;
 pub mod oauth_consent;
+pub mod oauth_signing_key;
+pub mod oauth_logout_delivery;
\ No newline at end of file
@@ -1,16 +1,70 @@
+use jsonwebtoken::jwk::JwkSet;
 use sqlx::types::Json;
 
-#[derive(sqlx::FromRow, Debug, serde::Serialize, Clone)]
+#[derive(sqlx::FromRow, Debug, serde::Serialize, Clone, Default)]
 pub struct OauthClient {
     pub id: Option<uuid::Uuid>,
     pub name: Option<String>,
     pub slug: Option<String>,
     pub secret: Option<String>,
+    pub secret_hash: Option<String>,
+    pub previous_secret: Option<String>,
+    pub previous_secret_hash: Option<String>,
+    pub previous_secret_expires_at: Option<chrono::NaiveDateTime>,
+    pub secret_rotated_at: Option<chrono::NaiveDateTime>,
     pub urls: Option<Vec<String>>,
+    pub request_uris: Option<Vec<String>>,
     pub scopes: Option<Vec<String>>,
     pub mandatory_scopes: Option<Vec<String>>,
     pub status: Option<i32>,
     pub logos: Option<Json<Vec<String>>>,
+    pub post_logout_redirect_uris: Option<Vec<String>>,
+    pub backchannel_logout_uri: Option<String>,
+    pub frontchannel_logout_uri: Option<String>,
+    pub grant_types: Option<Vec<String>>,
+    pub token_exchange_audiences: Option<Vec<String>>,
+    pub backchannel_token_delivery_mode: Option<String>,
+    pub backchannel_client_notification_endpoint: Option<String>,
+    pub client_type: Option<String>,
+    pub application_type: Option<String>,
+    pub token_endpoint_auth_method: Option<String>,
+    pub tls_client_auth_subject_dn: Option<String>,
+    pub tls_client_auth_san_dns: Option<String>,
+    pub tls_client_auth_san_uri: Option<String>,
+    pub tls_client_auth_san_ip: Option<String>,
+    pub tls_client_auth_san_email: Option<String>,
+    pub tls_client_certificate_thumbprint: Option<String>,
+    pub jwks: Option<Json<JwkSet>>,
+    pub jwks_uri: Option<String>,
+    pub registration_access_token: Option<String>,
     pub created_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+impl OauthClient {
+    pub const TYPE_CONFIDENTIAL: &'static str = "confidential";
+    pub const TYPE_PUBLIC: &'static str = "public";
+
+    pub const APPLICATION_TYPE_WEB: &'static str = "web";
+    pub const APPLICATION_TYPE_NATIVE: &'static str = "native";
+
+    pub fn is_public(&self) -> bool {
+        self.client_type.as_deref() == Some(Self::TYPE_PUBLIC)
+    }
+
+    pub fn is_native(&self) -> bool {
+        self.application_type.as_deref() == Some(Self::APPLICATION_TYPE_NATIVE)
+    }
+
+    pub fn allows_grant_type(&self, grant_type: &str) -> bool {
+        self.grant_types.as_ref().is_some_and(|e| e.iter().any(|e| e == grant_type))
+    }
+
+    pub fn allows_exchange_audience(&self, audience: &str) -> bool {
+        self.token_exchange_audiences.as_ref().is_some_and(|e| e.iter().any(|e| e == audience))
+    }
+
+    pub fn in_secret_overlap(&self) -> bool {
+        self.previous_secret_expires_at.is_some_and(|e| e > chrono::Utc::now().naive_utc())
+    }
 }
\ No newline at end of file
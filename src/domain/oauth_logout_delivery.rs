@@ -0,0 +1,20 @@
+#[derive(sqlx::FromRow, Debug, serde::Serialize, Clone)]
+pub struct OauthLogoutDelivery {
+    pub id: Option<uuid::Uuid>,
+    pub session_id: Option<uuid::Uuid>,
+    pub client_id: Option<String>,
+    pub logout_uri: Option<String>,
+    pub status: Option<i32>,
+    pub attempts: Option<i32>,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+impl OauthLogoutDelivery {
+    pub const STATUS_PENDING: i32 = 0;
+    pub const STATUS_DELIVERED: i32 = 1;
+    pub const STATUS_FAILED: i32 = 2;
+}
@@ -7,10 +7,26 @@ pub struct OauthSession {
     pub redirect_uri: Option<String>,
     pub state: Option<String>,
     pub response_type: Option<String>,
+    pub response_mode: Option<String>,
+    pub nonce: Option<String>,
     pub code_challenge: Option<String>,
     pub code_challenge_method: Option<String>,
     pub status: Option<i32>,
     pub consent_granted_at: Option<chrono::NaiveDateTime>,
+    pub auth_time: Option<chrono::NaiveDateTime>,
+    pub acr: Option<String>,
+    pub amr: Option<Vec<String>>,
+    pub login_id: Option<String>,
     pub created_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
-}
\ No newline at end of file
+}
+
+impl OauthSession {
+    pub const STATUS_ENDED: i32 = 0;
+    pub const STATUS_ACTIVE: i32 = 1;
+    pub const STATUS_PENDING: i32 = 2;
+
+    pub fn is_active(&self) -> bool {
+        self.status == Some(Self::STATUS_ACTIVE)
+    }
+}
@@ -0,0 +1,30 @@
+#[derive(sqlx::FromRow, Debug, serde::Serialize, Clone)]
+pub struct OauthSigningKey {
+    pub id: Option<uuid::Uuid>,
+    pub kid: Option<String>,
+    pub algorithm: Option<String>,
+    #[serde(skip_serializing)]
+    pub private_key: Option<String>,
+    pub status: Option<i32>,
+    pub activated_at: Option<chrono::NaiveDateTime>,
+    pub retired_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+    pub updated_at: Option<chrono::NaiveDateTime>,
+}
+
+impl OauthSigningKey {
+    pub const STATUS_NEXT: i32 = 0;
+    pub const STATUS_ACTIVE: i32 = 1;
+    pub const STATUS_RETIRING: i32 = 2;
+    pub const STATUS_REVOKED: i32 = 3;
+
+    pub fn status_name(&self) -> &'static str {
+        match self.status {
+            Some(Self::STATUS_NEXT) => "next",
+            Some(Self::STATUS_ACTIVE) => "active",
+            Some(Self::STATUS_RETIRING) => "retiring",
+            Some(Self::STATUS_REVOKED) => "revoked",
+            _ => "unknown",
+        }
+    }
+}
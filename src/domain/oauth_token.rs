@@ -4,8 +4,25 @@ pub struct OauthToken {
     pub session_id: Option<uuid::Uuid>,
     pub access_token: Option<String>,
     pub refresh_token: Option<String>,
+    pub previous_refresh_token: Option<String>,
     pub refresh_token_expires_at: Option<chrono::NaiveDateTime>,
+    pub id_token: Option<String>,
+    pub subject_token_id: Option<uuid::Uuid>,
+    pub actor_token_id: Option<uuid::Uuid>,
+    pub previous_token_id: Option<uuid::Uuid>,
+    pub dpop_jkt: Option<String>,
+    pub x5t_s256: Option<String>,
+    pub client_id: Option<String>,
     pub status: Option<i32>,
     pub created_at: Option<chrono::NaiveDateTime>,
     pub updated_at: Option<chrono::NaiveDateTime>,
-}
\ No newline at end of file
+}
+
+impl OauthToken {
+    pub const STATUS_REVOKED: i32 = 0;
+    pub const STATUS_ACTIVE: i32 = 1;
+
+    pub fn is_active(&self) -> bool {
+        self.status == Some(Self::STATUS_ACTIVE)
+    }
+}
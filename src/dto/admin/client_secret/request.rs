@@ -0,0 +1,6 @@
+#[derive(Debug, serde::Deserialize)]
+pub struct RotateClientSecretRequest {
+    #[serde(default)]
+    pub client_id: String,
+    pub overlap: Option<i64>,
+}
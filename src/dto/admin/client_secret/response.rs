@@ -0,0 +1,7 @@
+#[derive(Debug, serde::Serialize)]
+pub struct ClientSecretResponse {
+    pub client_id: String,
+    pub client_secret: String,
+    pub secret_rotated_at: chrono::NaiveDateTime,
+    pub previous_secret_expires_at: Option<chrono::NaiveDateTime>,
+}
@@ -0,0 +1,2 @@
+pub mod signing_keys;
+pub mod client_secret;
\ No newline at end of file
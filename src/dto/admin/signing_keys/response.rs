@@ -0,0 +1,14 @@
+#[derive(Debug, serde::Serialize)]
+pub struct SigningKeyResponse {
+    pub kid: String,
+    pub algorithm: String,
+    pub status: String,
+    pub activated_at: Option<chrono::NaiveDateTime>,
+    pub retired_at: Option<chrono::NaiveDateTime>,
+    pub created_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct SigningKeysResponse {
+    pub keys: Vec<SigningKeyResponse>,
+}
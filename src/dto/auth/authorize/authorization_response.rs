@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+use serde::Serialize;
+use crate::domain::oauth_session::OauthSession;
+use crate::utils::api_response::{error_uri, ApiError};
+
+#[derive(Debug, Serialize)]
+pub struct AuthorizationResponseClaims {
+    pub iss: String,
+    pub aud: String,
+    pub exp: usize,
+    #[serde(flatten)]
+    pub params: BTreeMap<String, String>,
+}
+
+#[derive(Debug)]
+pub struct AuthorizationResponseRequest {
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub response_mode: Option<String>,
+    pub params: Vec<(&'static str, String)>,
+}
+
+impl AuthorizationResponseRequest {
+    pub fn from_session(session: &OauthSession, params: Vec<(&'static str, String)>) -> Self {
+        let state = session.state.clone().filter(|e| !e.is_empty()).map(|e| ("state", e));
+
+        Self {
+            client_id: session.client_id.clone().unwrap_or_default(),
+            redirect_uri: session.redirect_uri.clone().unwrap_or_default(),
+            response_mode: session.response_mode.clone(),
+            params: params.into_iter().chain(state).collect(),
+        }
+    }
+
+    pub fn error(client_id: String, redirect_uri: String, response_mode: Option<String>, state: &str, error: &ApiError) -> Self {
+        let params = [
+            Some(("error", error.error.as_str().to_string())),
+            Some(("error_description", error.error_description.clone())),
+            error_uri(error.error).map(|e| ("error_uri", e)),
+            Some(("state", state.to_string())).filter(|_| !state.is_empty()),
+        ];
+
+        Self { client_id, redirect_uri, response_mode, params: params.into_iter().flatten().collect() }
+    }
+}
@@ -1,2 +1,4 @@
 pub mod request;
-pub mod token_data;
\ No newline at end of file
+pub mod response;
+pub mod token_data;
+pub mod authorization_response;
\ No newline at end of file
@@ -3,9 +3,19 @@ use uuid::Uuid;
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct AuthorizeRequest {
     pub client_id: Option<String>,
+    #[serde(alias = "request_uri")]
     pub uri: Option<String>,
     pub session_id: Option<String>,
     pub user_id: Option<Uuid>,
     pub auth_token: Option<String>,
     pub consent_id: Option<Uuid>,
+    pub request: Option<String>,
+    pub scope: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub response_type: Option<String>,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub response_mode: Option<String>,
+    pub nonce: Option<String>,
 }
@@ -0,0 +1,8 @@
+#[derive(Debug)]
+pub enum AuthorizeResponse {
+    Redirect(String),
+    FormPost {
+        action: String,
+        params: Vec<(String, String)>,
+    },
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CibaData {
+    pub client_id: String,
+    pub session_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub status: i32,
+    pub interval: i64,
+    pub expires_at: i64,
+    pub last_polled_at: Option<i64>,
+    pub ping: Option<CibaPing>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CibaPing {
+    pub auth_req_id: String,
+    pub client_notification_token: String,
+}
+
+impl CibaData {
+    pub const STATUS_PENDING: i32 = 0;
+    pub const STATUS_APPROVED: i32 = 1;
+    pub const STATUS_DENIED: i32 = 2;
+
+    pub fn expires_in(&self) -> i64 {
+        self.expires_at - chrono::Utc::now().timestamp()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod request;
+pub mod response;
+pub mod ciba_data;
\ No newline at end of file
@@ -0,0 +1,18 @@
+use crate::dto::auth::client_authentication::request::ClientAuthenticationContext;
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct BcAuthorizeRequest {
+    #[serde(default)]
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
+    pub scope: String,
+    pub login_hint: Option<String>,
+    pub id_token_hint: Option<String>,
+    pub binding_message: Option<String>,
+    pub client_notification_token: Option<String>,
+    pub requested_expiry: Option<i64>,
+    #[serde(skip)]
+    pub client_context: ClientAuthenticationContext,
+}
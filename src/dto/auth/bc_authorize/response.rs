@@ -0,0 +1,7 @@
+#[derive(Debug, serde::Serialize)]
+pub struct BcAuthorizeResponse {
+    pub auth_req_id: String,
+    pub expires_in: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<i64>,
+}
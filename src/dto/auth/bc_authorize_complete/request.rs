@@ -0,0 +1,9 @@
+use uuid::Uuid;
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct BcAuthorizeCompleteRequest {
+    pub session_id: Uuid,
+    pub user_id: Uuid,
+    pub auth_token: String,
+    pub approved: bool,
+}
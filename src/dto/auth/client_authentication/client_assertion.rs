@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+pub const CLIENT_ASSERTION_TYPE: &str = "urn:ietf:params:oauth:client-assertion-type:jwt-bearer";
+
+#[derive(Debug, Deserialize)]
+pub struct ClientAssertionClaims {
+    pub exp: i64,
+    pub jti: String,
+}
+
+/// Claims read from an assertion before it is verified, to find the client it names.
+#[derive(Debug, Deserialize)]
+pub struct UnverifiedClientAssertionClaims {
+    pub iss: Option<String>,
+    pub sub: Option<String>,
+}
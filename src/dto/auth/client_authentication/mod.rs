@@ -0,0 +1,2 @@
+pub mod request;
+pub mod client_assertion;
\ No newline at end of file
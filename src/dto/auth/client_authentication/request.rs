@@ -0,0 +1,147 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use jsonwebtoken::dangerous::insecure_decode;
+use percent_encoding::percent_decode_str;
+use crate::domain::oauth_client::OauthClient;
+use crate::dto::auth::bc_authorize::request::BcAuthorizeRequest;
+use crate::dto::auth::client_authentication::client_assertion::UnverifiedClientAssertionClaims;
+use crate::dto::auth::device_authorization::request::DeviceAuthorizationRequest;
+use crate::dto::auth::introspect::request::IntrospectRequest;
+use crate::dto::auth::par::request::ParRequest;
+use crate::dto::auth::revoke::request::RevokeRequest;
+use crate::dto::auth::token::request::{TokenCibaRequest, TokenClientCredentialsRequest, TokenDeviceCodeRequest, TokenExchangeRequest, TokenRefreshRequest, TokenRequest};
+use crate::utils::mtls::ClientCertificate;
+
+#[derive(Debug, Clone, Default)]
+pub struct ClientAuthenticationContext {
+    pub authorization: Option<String>,
+    pub client_certificate: Option<ClientCertificate>,
+    pub endpoint_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClientCredentials {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
+    pub context: ClientAuthenticationContext,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClientAuthenticationRequest {
+    pub client: OauthClient,
+    pub credentials: ClientCredentials,
+    pub allow_public: bool,
+}
+
+impl ClientCredentials {
+    pub fn basic(&self) -> Result<Option<(String, String)>, String> {
+        let Some(encoded) = self.context.authorization.as_deref().and_then(|e| e.strip_prefix("Basic ")) else {
+            return Ok(None);
+        };
+
+        let Some((client_id, client_secret)) = STANDARD.decode(encoded.trim()).ok()
+            .and_then(|e| String::from_utf8(e).ok())
+            .and_then(|e| e.split_once(':').map(|(id, secret)| (form_decode(id), form_decode(secret)))) else {
+            return Err(String::from("Invalid basic authorization header"));
+        };
+
+        Ok(Some((client_id, client_secret)))
+    }
+
+    pub fn resolve_client_id(&self) -> String {
+        if !self.client_id.is_empty() {
+            return self.client_id.clone();
+        }
+
+        match self.basic() {
+            Ok(Some((client_id, _))) => client_id,
+            _ => self.assertion_client_id().unwrap_or_default(),
+        }
+    }
+
+    /// The assertion is only read here; it is verified once the client it names is loaded.
+    fn assertion_client_id(&self) -> Option<String> {
+        let claims = insecure_decode::<UnverifiedClientAssertionClaims>(self.client_assertion.as_ref()?).ok()?.claims;
+
+        claims.sub.filter(|e| claims.iss.as_ref() == Some(e))
+    }
+}
+
+fn form_decode(value: &str) -> String {
+    percent_decode_str(&value.replace('+', " ")).decode_utf8_lossy().to_string()
+}
+
+macro_rules! client_credentials {
+    ($($request:ty),+ $(,)?) => {
+        $(
+            impl From<&$request> for ClientCredentials {
+                fn from(data: &$request) -> Self {
+                    Self {
+                        client_id: data.client_id.clone(),
+                        client_secret: data.client_secret.clone(),
+                        client_assertion_type: data.client_assertion_type.clone(),
+                        client_assertion: data.client_assertion.clone(),
+                        context: data.client_context.clone(),
+                    }
+                }
+            }
+
+            impl $request {
+                pub fn set_client_context(&mut self, context: ClientAuthenticationContext) {
+                    self.client_context = context;
+                    self.client_id = ClientCredentials::from(&*self).resolve_client_id();
+                }
+            }
+        )+
+    };
+}
+
+client_credentials!(
+    ParRequest,
+    RevokeRequest,
+    IntrospectRequest,
+    DeviceAuthorizationRequest,
+    BcAuthorizeRequest,
+    TokenRequest,
+    TokenRefreshRequest,
+    TokenClientCredentialsRequest,
+    TokenDeviceCodeRequest,
+    TokenExchangeRequest,
+    TokenCibaRequest,
+);
+
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use super::{ClientAuthenticationContext, ClientCredentials};
+
+    fn credentials(client_id: &str, claims: serde_json::Value) -> ClientCredentials {
+        ClientCredentials {
+            client_id: client_id.to_string(),
+            client_secret: None,
+            client_assertion_type: None,
+            client_assertion: Some(encode(&Header::default(), &claims, &EncodingKey::from_secret(b"secret")).unwrap()),
+            context: ClientAuthenticationContext::default(),
+        }
+    }
+
+    #[test]
+    fn resolves_the_client_named_by_the_assertion() {
+        let credentials = credentials("", serde_json::json!({ "iss": "susanoo", "sub": "susanoo" }));
+        assert_eq!(credentials.resolve_client_id(), "susanoo");
+    }
+
+    #[test]
+    fn ignores_assertions_whose_iss_and_sub_differ() {
+        let credentials = credentials("", serde_json::json!({ "iss": "susanoo", "sub": "amaterasu" }));
+        assert_eq!(credentials.resolve_client_id(), "");
+    }
+
+    #[test]
+    fn prefers_the_client_id_parameter() {
+        let credentials = credentials("amaterasu", serde_json::json!({ "iss": "susanoo", "sub": "susanoo" }));
+        assert_eq!(credentials.resolve_client_id(), "amaterasu");
+    }
+}
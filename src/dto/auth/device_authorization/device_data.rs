@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeviceData {
+    pub client_id: String,
+    pub scopes: Vec<String>,
+    pub user_code: String,
+    pub status: i32,
+    pub session_id: Option<uuid::Uuid>,
+    pub user_id: Option<uuid::Uuid>,
+    pub interval: i64,
+    pub expires_at: i64,
+    pub last_polled_at: Option<i64>,
+}
+
+impl DeviceData {
+    pub const STATUS_PENDING: i32 = 0;
+    pub const STATUS_APPROVED: i32 = 1;
+    pub const STATUS_DENIED: i32 = 2;
+
+    pub fn expires_in(&self) -> i64 {
+        self.expires_at - chrono::Utc::now().timestamp()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod request;
+pub mod response;
+pub mod device_data;
\ No newline at end of file
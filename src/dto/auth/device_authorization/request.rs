@@ -0,0 +1,13 @@
+use crate::dto::auth::client_authentication::request::ClientAuthenticationContext;
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct DeviceAuthorizationRequest {
+    #[serde(default)]
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
+    pub scope: Option<String>,
+    #[serde(skip)]
+    pub client_context: ClientAuthenticationContext,
+}
@@ -0,0 +1,5 @@
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct DeviceConfirmRequest {
+    pub user_code: String,
+    pub approve: bool,
+}
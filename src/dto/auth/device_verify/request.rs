@@ -0,0 +1,4 @@
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct DeviceVerifyRequest {
+    pub user_code: Option<String>,
+}
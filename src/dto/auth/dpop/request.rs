@@ -0,0 +1,8 @@
+#[derive(Debug, Clone)]
+pub struct DpopRequest {
+    pub proof: Option<String>,
+    pub method: String,
+    pub path: String,
+    pub access_token: Option<String>,
+    pub client_id: Option<String>,
+}
@@ -0,0 +1,16 @@
+use std::collections::HashMap;
+use serde::Serialize;
+
+pub const BACKCHANNEL_LOGOUT_EVENT: &str = "http://schemas.openid.net/event/backchannel-logout";
+
+#[derive(Debug, Serialize)]
+pub struct LogoutTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub iat: usize,
+    pub exp: usize,
+    pub jti: String,
+    pub sid: String,
+    pub events: HashMap<String, serde_json::Value>,
+}
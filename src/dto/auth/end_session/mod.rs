@@ -0,0 +1,3 @@
+pub mod request;
+pub mod response;
+pub mod logout_token;
\ No newline at end of file
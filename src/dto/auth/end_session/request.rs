@@ -0,0 +1,7 @@
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct EndSessionRequest {
+    pub id_token_hint: Option<String>,
+    pub client_id: Option<String>,
+    pub post_logout_redirect_uri: Option<String>,
+    pub state: Option<String>,
+}
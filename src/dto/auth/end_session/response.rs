@@ -0,0 +1,5 @@
+#[derive(Debug, serde::Serialize)]
+pub struct EndSessionResponse {
+    pub redirect_uri: Option<String>,
+    pub frontchannel_logout_uris: Vec<String>,
+}
@@ -0,0 +1,3 @@
+pub mod request;
+pub mod response;
+pub mod token_introspection;
\ No newline at end of file
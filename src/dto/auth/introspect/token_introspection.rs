@@ -0,0 +1,10 @@
+use serde::Serialize;
+use crate::dto::auth::introspect::response::IntrospectResponse;
+
+#[derive(Debug, Serialize)]
+pub struct TokenIntrospectionClaims {
+    pub iss: String,
+    pub aud: String,
+    pub iat: usize,
+    pub token_introspection: IntrospectResponse,
+}
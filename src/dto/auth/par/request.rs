@@ -1,11 +1,51 @@
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
+use crate::dto::auth::authorize::request::AuthorizeRequest;
+use crate::dto::auth::client_authentication::request::ClientAuthenticationContext;
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct ParRequest {
+    #[serde(default)]
     pub client_id: String,
-    pub client_secret: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
+    #[serde(default)]
     pub scope: String,
+    #[serde(default)]
     pub redirect_uri: String,
+    #[serde(default)]
     pub response_type: String,
+    #[serde(default)]
     pub state: String,
+    #[serde(default)]
     pub code_challenge: String,
+    #[serde(default)]
     pub code_challenge_method: String,
-}
\ No newline at end of file
+    pub response_mode: Option<String>,
+    pub nonce: Option<String>,
+    pub request: Option<String>,
+    pub request_uri: Option<String>,
+    #[serde(skip)]
+    pub client_context: ClientAuthenticationContext,
+}
+
+impl From<&AuthorizeRequest> for ParRequest {
+    fn from(data: &AuthorizeRequest) -> Self {
+        Self {
+            client_id: data.client_id.clone().unwrap_or_default(),
+            client_secret: None,
+            client_assertion_type: None,
+            client_assertion: None,
+            scope: data.scope.clone().unwrap_or_default(),
+            redirect_uri: data.redirect_uri.clone().unwrap_or_default(),
+            response_type: data.response_type.clone().unwrap_or_default(),
+            state: data.state.clone().unwrap_or_default(),
+            code_challenge: data.code_challenge.clone().unwrap_or_default(),
+            code_challenge_method: data.code_challenge_method.clone().unwrap_or_default(),
+            response_mode: data.response_mode.clone(),
+            nonce: data.nonce.clone(),
+            request: data.request.clone(),
+            request_uri: data.uri.clone(),
+            client_context: ClientAuthenticationContext::default(),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::domain::oauth_client::OauthClient;
+use crate::dto::auth::par::request::ParRequest;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct RequestObjectClaims {
+    pub jti: String,
+    pub iat: i64,
+    pub exp: i64,
+    pub client_id: Option<String>,
+    pub scope: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub response_type: Option<String>,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub response_mode: Option<String>,
+    pub nonce: Option<String>,
+    pub request: Option<String>,
+    pub request_uri: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct RequestObjectRequest {
+    pub client: OauthClient,
+    pub request: ParRequest,
+}
@@ -0,0 +1,14 @@
+use crate::dto::auth::client_authentication::request::ClientAuthenticationContext;
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct RevokeRequest {
+    pub token: String,
+    pub token_type_hint: Option<String>,
+    #[serde(default)]
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
+    #[serde(skip)]
+    pub client_context: ClientAuthenticationContext,
+}
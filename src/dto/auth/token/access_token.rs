@@ -3,13 +3,46 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AccessToken {
     pub scopes: Vec<String>,
-    pub sub: uuid::Uuid,
+    pub sub: String,
     pub exp: usize,
     pub iat: usize,
     pub iss: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub aud: String,
     pub jti: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub sid: String,
     pub client_id: String,
-    pub auth_time: usize
+    pub auth_time: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cnf: Option<Confirmation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Actor {
+    pub sub: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Box<Actor>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Confirmation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jkt: Option<String>,
+    #[serde(rename = "x5t#S256", default, skip_serializing_if = "Option::is_none")]
+    pub x5t_s256: Option<String>,
+}
+
+impl Confirmation {
+    pub fn new(jkt: Option<String>, x5t_s256: Option<String>) -> Option<Self> {
+        if jkt.is_none() && x5t_s256.is_none() {
+            return None;
+        }
+
+        Some(Self { jkt, x5t_s256 })
+    }
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+use serde::Serialize;
+use crate::domain::idp::IdpUser;
+use crate::domain::oauth_session::OauthSession;
+
+#[derive(Debug, Serialize)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub azp: String,
+    pub exp: usize,
+    pub iat: usize,
+    pub auth_time: usize,
+    pub sid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amr: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub at_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub c_hash: Option<String>,
+    #[serde(flatten)]
+    pub user: IdTokenUserClaims,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct IdTokenUserClaims {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gender: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub birthdate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl IdTokenUserClaims {
+    pub fn from_user(user: IdpUser, scopes: &[String]) -> Self {
+        let mut claims = Self::default();
+
+        if scopes.iter().any(|e| e == "profile") {
+            claims.given_name = user.name;
+            claims.family_name = user.family_name;
+            claims.gender = user.gender;
+            claims.birthdate = user.birthdate;
+            claims.updated_at = user.updated_at
+                .and_then(|e| chrono::DateTime::parse_from_rfc3339(&e).ok())
+                .map(|e| e.timestamp());
+        }
+
+        if scopes.iter().any(|e| e == "email") {
+            claims.email = user.email;
+        }
+
+        claims
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct IdTokenHintClaims {
+    pub sub: String,
+    pub aud: String,
+    pub sid: String,
+}
+
+#[derive(Debug)]
+pub struct IdTokenRequest {
+    pub session: OauthSession,
+    pub access_token: String,
+    pub code: Option<String>,
+    pub nonce: Option<String>,
+}
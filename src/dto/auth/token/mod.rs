@@ -1,3 +1,4 @@
 pub mod access_token;
+pub mod id_token;
 pub mod request;
 pub mod response;
\ No newline at end of file
@@ -1,17 +1,150 @@
+use crate::dto::auth::client_authentication::request::ClientAuthenticationContext;
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "grant_type")]
+pub enum TokenGrantRequest {
+    #[serde(rename = "authorization_code")]
+    AuthorizationCode(TokenRequest),
+    #[serde(rename = "refresh_token")]
+    RefreshToken(TokenRefreshRequest),
+    #[serde(rename = "client_credentials")]
+    ClientCredentials(TokenClientCredentialsRequest),
+    #[serde(rename = "urn:ietf:params:oauth:grant-type:device_code")]
+    DeviceCode(TokenDeviceCodeRequest),
+    #[serde(rename = "urn:ietf:params:oauth:grant-type:token-exchange")]
+    TokenExchange(TokenExchangeRequest),
+    #[serde(rename = "urn:openid:params:grant-type:ciba")]
+    Ciba(TokenCibaRequest),
+    #[serde(other)]
+    Unsupported,
+}
+
+impl TokenGrantRequest {
+    pub fn set_dpop_jkt(&mut self, dpop_jkt: Option<String>) {
+        match self {
+            Self::AuthorizationCode(e) => e.dpop_jkt = dpop_jkt,
+            Self::RefreshToken(e) => e.dpop_jkt = dpop_jkt,
+            Self::ClientCredentials(e) => e.dpop_jkt = dpop_jkt,
+            Self::DeviceCode(e) => e.dpop_jkt = dpop_jkt,
+            Self::TokenExchange(e) => e.dpop_jkt = dpop_jkt,
+            Self::Ciba(e) => e.dpop_jkt = dpop_jkt,
+            Self::Unsupported => {},
+        }
+    }
+
+    pub fn client_id(&self) -> Option<&str> {
+        match self {
+            Self::AuthorizationCode(e) => Some(e.client_id.as_str()),
+            Self::RefreshToken(e) => Some(e.client_id.as_str()),
+            Self::ClientCredentials(e) => Some(e.client_id.as_str()),
+            Self::DeviceCode(e) => Some(e.client_id.as_str()),
+            Self::TokenExchange(e) => Some(e.client_id.as_str()),
+            Self::Ciba(e) => Some(e.client_id.as_str()),
+            Self::Unsupported => None,
+        }
+    }
+
+    pub fn set_client_context(&mut self, context: ClientAuthenticationContext) {
+        match self {
+            Self::AuthorizationCode(e) => e.set_client_context(context),
+            Self::RefreshToken(e) => e.set_client_context(context),
+            Self::ClientCredentials(e) => e.set_client_context(context),
+            Self::DeviceCode(e) => e.set_client_context(context),
+            Self::TokenExchange(e) => e.set_client_context(context),
+            Self::Ciba(e) => e.set_client_context(context),
+            Self::Unsupported => {},
+        }
+    }
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct TokenRequest {
+    #[serde(default)]
     pub client_id: String,
-    pub client_secret: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
     pub code: String,
-    pub grant_type: String,
     pub redirect_uri: String,
     pub code_verifier: String,
+    #[serde(skip)]
+    pub dpop_jkt: Option<String>,
+    #[serde(skip)]
+    pub client_context: ClientAuthenticationContext,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct TokenRefreshRequest {
+    #[serde(default)]
     pub client_id: String,
-    pub client_secret: String,
-    pub grant_type: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
     pub refresh_token: String,
+    #[serde(skip)]
+    pub dpop_jkt: Option<String>,
+    #[serde(skip)]
+    pub client_context: ClientAuthenticationContext,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct TokenClientCredentialsRequest {
+    #[serde(default)]
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
+    pub scope: Option<String>,
+    #[serde(skip)]
+    pub dpop_jkt: Option<String>,
+    #[serde(skip)]
+    pub client_context: ClientAuthenticationContext,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct TokenDeviceCodeRequest {
+    #[serde(default)]
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
+    pub device_code: String,
+    #[serde(skip)]
+    pub dpop_jkt: Option<String>,
+    #[serde(skip)]
+    pub client_context: ClientAuthenticationContext,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct TokenExchangeRequest {
+    #[serde(default)]
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
+    pub subject_token: String,
+    pub subject_token_type: String,
+    pub actor_token: Option<String>,
+    pub actor_token_type: Option<String>,
+    pub requested_token_type: Option<String>,
+    pub audience: Option<String>,
+    pub scope: Option<String>,
+    #[serde(skip)]
+    pub dpop_jkt: Option<String>,
+    #[serde(skip)]
+    pub client_context: ClientAuthenticationContext,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct TokenCibaRequest {
+    #[serde(default)]
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_assertion_type: Option<String>,
+    pub client_assertion: Option<String>,
+    pub auth_req_id: String,
+    #[serde(skip)]
+    pub dpop_jkt: Option<String>,
+    #[serde(skip)]
+    pub client_context: ClientAuthenticationContext,
 }
@@ -1,6 +1,11 @@
 #[derive(Debug, serde::Serialize)]
 pub struct TokenResponse {
     pub access_token: String,
-    pub refresh_token: String,
-    pub id_token: String,
+    pub token_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issued_token_type: Option<String>,
 }
\ No newline at end of file
@@ -0,0 +1,2 @@
+pub mod request;
+pub mod response;
\ No newline at end of file
@@ -0,0 +1,105 @@
+use jsonwebtoken::jwk::JwkSet;
+use crate::domain::oauth_client::OauthClient;
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct ClientMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect_uris: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_uris: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grant_types: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_types: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_endpoint_auth_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwks: Option<JwkSet>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwks_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_logout_redirect_uris: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backchannel_logout_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frontchannel_logout_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backchannel_token_delivery_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backchannel_client_notification_endpoint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_auth_subject_dn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_auth_san_dns: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_auth_san_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_auth_san_ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_auth_san_email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_certificate_thumbprint: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ClientRegistrationRequest {
+    #[serde(flatten)]
+    pub metadata: ClientMetadata,
+    #[serde(skip)]
+    pub initial_access_token: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ClientUpdateRequest {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    #[serde(flatten)]
+    pub metadata: ClientMetadata,
+    #[serde(skip)]
+    pub configuration: ClientConfigurationRequest,
+}
+
+#[derive(Debug, Default)]
+pub struct ClientConfigurationRequest {
+    pub client_id: String,
+    pub registration_access_token: Option<String>,
+}
+
+impl From<&OauthClient> for ClientMetadata {
+    fn from(client: &OauthClient) -> Self {
+        let grant_types = client.grant_types.clone().unwrap_or_default();
+
+        Self {
+            redirect_uris: client.urls.clone(),
+            request_uris: client.request_uris.clone(),
+            client_name: client.name.clone(),
+            logo_uri: client.logos.as_ref().and_then(|e| e.first().cloned()),
+            scope: client.scopes.as_ref().map(|e| e.join(" ")),
+            response_types: Some(grant_types.iter().filter(|e| e.as_str() == "authorization_code").map(|_| String::from("code")).collect()),
+            grant_types: Some(grant_types),
+            application_type: client.application_type.clone(),
+            token_endpoint_auth_method: client.token_endpoint_auth_method.clone(),
+            jwks: client.jwks.as_ref().map(|e| e.0.clone()),
+            jwks_uri: client.jwks_uri.clone(),
+            post_logout_redirect_uris: client.post_logout_redirect_uris.clone(),
+            backchannel_logout_uri: client.backchannel_logout_uri.clone(),
+            frontchannel_logout_uri: client.frontchannel_logout_uri.clone(),
+            backchannel_token_delivery_mode: client.backchannel_token_delivery_mode.clone(),
+            backchannel_client_notification_endpoint: client.backchannel_client_notification_endpoint.clone(),
+            tls_client_auth_subject_dn: client.tls_client_auth_subject_dn.clone(),
+            tls_client_auth_san_dns: client.tls_client_auth_san_dns.clone(),
+            tls_client_auth_san_uri: client.tls_client_auth_san_uri.clone(),
+            tls_client_auth_san_ip: client.tls_client_auth_san_ip.clone(),
+            tls_client_auth_san_email: client.tls_client_auth_san_email.clone(),
+            tls_client_certificate_thumbprint: client.tls_client_certificate_thumbprint.clone(),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+#[derive(Debug)]
+pub struct DiscoveryRequest {
+    pub par_path: String,
+    pub authorize_path: String,
+    pub token_path: String,
+    pub userinfo_path: String,
+    pub revocation_path: String,
+    pub introspection_path: String,
+    pub end_session_path: String,
+    pub device_authorization_path: String,
+    pub backchannel_authentication_path: String,
+    pub registration_path: String,
+    pub jwks_path: String,
+}
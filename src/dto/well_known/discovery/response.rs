@@ -0,0 +1,47 @@
+use jsonwebtoken::Algorithm;
+
+#[derive(Debug, serde::Serialize)]
+pub struct DiscoveryResponse {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: String,
+    pub revocation_endpoint: String,
+    pub introspection_endpoint: String,
+    pub end_session_endpoint: String,
+    pub device_authorization_endpoint: String,
+    pub backchannel_authentication_endpoint: String,
+    pub pushed_authorization_request_endpoint: String,
+    pub registration_endpoint: String,
+    pub jwks_uri: String,
+    pub require_pushed_authorization_requests: bool,
+    pub request_parameter_supported: bool,
+    pub request_uri_parameter_supported: bool,
+    pub require_request_uri_registration: bool,
+    pub request_object_signing_alg_values_supported: Vec<Algorithm>,
+    pub scopes_supported: Vec<String>,
+    pub claims_supported: Vec<String>,
+    pub response_types_supported: Vec<String>,
+    pub response_modes_supported: Vec<String>,
+    pub authorization_response_iss_parameter_supported: bool,
+    pub grant_types_supported: Vec<String>,
+    pub subject_types_supported: Vec<String>,
+    pub code_challenge_methods_supported: Vec<String>,
+    pub token_endpoint_auth_methods_supported: Vec<String>,
+    pub token_endpoint_auth_signing_alg_values_supported: Vec<Algorithm>,
+    pub revocation_endpoint_auth_methods_supported: Vec<String>,
+    pub revocation_endpoint_auth_signing_alg_values_supported: Vec<Algorithm>,
+    pub introspection_endpoint_auth_methods_supported: Vec<String>,
+    pub introspection_endpoint_auth_signing_alg_values_supported: Vec<Algorithm>,
+    pub introspection_signing_alg_values_supported: Vec<Algorithm>,
+    pub id_token_signing_alg_values_supported: Vec<Algorithm>,
+    pub authorization_signing_alg_values_supported: Vec<Algorithm>,
+    pub dpop_signing_alg_values_supported: Vec<Algorithm>,
+    pub tls_client_certificate_bound_access_tokens: bool,
+    pub backchannel_logout_supported: bool,
+    pub backchannel_logout_session_supported: bool,
+    pub frontchannel_logout_supported: bool,
+    pub frontchannel_logout_session_supported: bool,
+    pub backchannel_token_delivery_modes_supported: Vec<String>,
+    pub backchannel_user_code_parameter_supported: bool,
+}
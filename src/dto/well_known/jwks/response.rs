@@ -0,0 +1,6 @@
+use jsonwebtoken::jwk::Jwk;
+
+#[derive(Debug, serde::Serialize)]
+pub struct JwksResponse {
+    pub keys: Vec<Jwk>,
+}
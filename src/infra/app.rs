@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 use actix_web::{App, HttpServer, web};
 use actix_web::middleware::Logger;
 use crate::adapters::api;
@@ -6,22 +7,75 @@ use crate::adapters::spi::cache::cache::CacheAdapter;
 use crate::adapters::spi::cache::redis::RedisCache;
 use crate::adapters::spi::db::db::DBAdapter;
 use crate::adapters::spi::db::postgres_db::PostgresDB;
+use crate::adapters::spi::gateways::logout::LogoutGateway;
+use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
+use crate::adapters::spi::repositories::oauth_logout_delivery::OAuthLogoutDeliveryRepository;
+use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
+use crate::application::spi::repository::RepositoryInterface;
+use crate::application::use_cases::admin::rotate_client_secret::RotateClientSecretUseCase;
+use crate::application::use_cases::admin::rotate_signing_keys::RotateSigningKeysUseCase;
+use crate::application::use_cases::auth::backchannel_logout::{BackchannelLogoutUseCase, RESUME_INTERVAL_SECONDS};
 use crate::infra::dependencies::add_dependencies;
+use crate::infra::tls::{load_tls_settings, store_client_certificate};
+use crate::utils::api_response::invalid_request_error;
+use crate::utils::encryption::check_encryption_key;
 
 pub async fn start_app() -> std::io::Result<()> {
+    check_encryption_key().expect("Invalid DATA_ENCRYPTION_KEY");
+
     let psql = Arc::new(DBAdapter::get_db_connection::<PostgresDB>().await.expect("Failed to connect to postgres database"));
     let redis = Arc::new(CacheAdapter::get_cache_connection::<RedisCache>().expect("Failed to connect to redis"));
 
-    HttpServer::new(move || {
+    RotateSigningKeysUseCase::new(Arc::new(OAuthSigningKeyRepository::new(String::from("oauth_signing_key"), psql.clone())))
+        .ensure_key_set()
+        .await
+        .expect("Failed to prepare signing keys");
+
+    RotateClientSecretUseCase::new(Arc::new(OAuthClientRepository::new(String::from("oauth_client"), psql.clone())))
+        .migrate_plaintext_secrets()
+        .await
+        .expect("Failed to hash client secrets");
+
+    let backchannel_logout = BackchannelLogoutUseCase::new(
+        Arc::new(OAuthClientRepository::new(String::from("oauth_client"), psql.clone())),
+        Arc::new(OAuthSessionRepository::new(String::from("oauth_session"), psql.clone())),
+        Arc::new(OAuthLogoutDeliveryRepository::new(String::from("oauth_logout_delivery"), psql.clone())),
+        Arc::new(OAuthSigningKeyRepository::new(String::from("oauth_signing_key"), psql.clone())),
+        Arc::new(LogoutGateway::new()),
+    );
+
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(Duration::from_secs(RESUME_INTERVAL_SECONDS));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = backchannel_logout.resume_pending().await {
+                log::warn!("Failed to resume back-channel logout deliveries: {}", e.error_description);
+            }
+        }
+    });
+
+    let tls = load_tls_settings().expect("Failed to load TLS settings");
+    let trust_verifier = tls.as_ref().and_then(|e| e.trust_verifier.clone());
+
+    let server = HttpServer::new(move || {
         let cors = actix_cors::Cors::default()
             .allowed_origin("http://localhost:3001");
         
         App::new()
         .wrap(Logger::default())
         .wrap(cors)
+        .app_data(web::FormConfig::default().error_handler(|e, _| invalid_request_error(e)))
+        .app_data(web::JsonConfig::default().error_handler(|e, _| invalid_request_error(e)))
+        .app_data(web::QueryConfig::default().error_handler(|e, _| invalid_request_error(e)))
+        .service(api::well_known::router::well_known_router())
         .service(web::scope("/api/v1")
             .service(api::health::router::health_router())
             .service(api::auth::router::auth_router())
+            .service(api::admin::router::admin_router())
+            .service(api::registration::router::registration_router())
         )
         .configure(|config| add_dependencies(
             config,
@@ -29,7 +83,12 @@ pub async fn start_app() -> std::io::Result<()> {
             redis.clone()
         ))
     })
-    .bind(("0.0.0.0", 8000))?
+    .on_connect(move |conn, extensions| store_client_certificate(conn, extensions, trust_verifier.as_ref()));
+
+    match tls {
+        Some(e) => server.bind_rustls_0_23(("0.0.0.0", 8000), e.config)?,
+        None => server.bind(("0.0.0.0", 8000))?,
+    }
     .run()
     .await
 }
\ No newline at end of file
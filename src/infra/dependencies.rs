@@ -2,10 +2,16 @@ use std::sync::Arc;
 use actix_web::web;
 use crate::adapters::spi::cache::redis::RedisCache;
 use crate::adapters::spi::db::postgres_db::PostgresDB;
+use crate::adapters::spi::gateways::ciba::CibaGateway;
 use crate::adapters::spi::gateways::idp::IdpGateway;
+use crate::adapters::spi::gateways::jwks::JwksGateway;
+use crate::adapters::spi::gateways::logout::LogoutGateway;
+use crate::adapters::spi::gateways::request_object::RequestObjectGateway;
 use crate::adapters::spi::repositories::oauth_client::OAuthClientRepository;
 use crate::adapters::spi::repositories::oauth_consent::OAuthConsentRepository;
+use crate::adapters::spi::repositories::oauth_logout_delivery::OAuthLogoutDeliveryRepository;
 use crate::adapters::spi::repositories::oauth_session::OAuthSessionRepository;
+use crate::adapters::spi::repositories::oauth_signing_key::OAuthSigningKeyRepository;
 use crate::adapters::spi::repositories::oauth_token::OAuthTokenRepository;
 use crate::application::spi::repository::RepositoryInterface;
 
@@ -14,8 +20,14 @@ pub fn add_dependencies(config: &mut web::ServiceConfig, psql: Arc<PostgresDB>,
     let oauth_session_repository = web::Data::new(OAuthSessionRepository::new(String::from("oauth_session"), psql.clone()));
     let oauth_token_repository = web::Data::new(OAuthTokenRepository::new(String::from("oauth_token"), psql.clone()));
     let oauth_consent_repository = web::Data::new(OAuthConsentRepository::new(String::from("oauth_consent"), psql.clone()));
+    let oauth_signing_key_repository = web::Data::new(OAuthSigningKeyRepository::new(String::from("oauth_signing_key"), psql.clone()));
+    let oauth_logout_delivery_repository = web::Data::new(OAuthLogoutDeliveryRepository::new(String::from("oauth_logout_delivery"), psql.clone()));
 
     let idp_gateway = web::Data::new(IdpGateway::new());
+    let logout_gateway = web::Data::new(LogoutGateway::new());
+    let ciba_gateway = web::Data::new(CibaGateway::new());
+    let jwks_gateway = web::Data::new(JwksGateway::new());
+    let request_object_gateway = web::Data::new(RequestObjectGateway::new());
 
     let redis_cache = web::Data::new(redis.as_ref().to_owned());
 
@@ -23,8 +35,14 @@ pub fn add_dependencies(config: &mut web::ServiceConfig, psql: Arc<PostgresDB>,
     config.app_data(oauth_session_repository.clone());
     config.app_data(oauth_token_repository.clone());
     config.app_data(oauth_consent_repository.clone());
+    config.app_data(oauth_signing_key_repository.clone());
+    config.app_data(oauth_logout_delivery_repository.clone());
 
     config.app_data(redis_cache.clone());
 
     config.app_data(idp_gateway.clone());
+    config.app_data(logout_gateway.clone());
+    config.app_data(ciba_gateway.clone());
+    config.app_data(jwks_gateway.clone());
+    config.app_data(request_object_gateway.clone());
 }
\ No newline at end of file
@@ -1,2 +1,3 @@
 pub mod app;
 mod dependencies;
+mod tls;
\ No newline at end of file
@@ -0,0 +1,125 @@
+use std::any::Any;
+use std::env;
+use std::sync::Arc;
+use actix_tls::accept::rustls_0_23::TlsStream;
+use actix_web::dev::Extensions;
+use actix_web::rt::net::TcpStream;
+use rustls::{DigitallySignedStruct, DistinguishedName, Error, RootCertStore, ServerConfig, SignatureScheme};
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, UnixTime};
+use rustls::pki_types::pem::PemObject;
+use rustls::server::WebPkiClientVerifier;
+use rustls::server::danger::{ClientCertVerified, ClientCertVerifier};
+use crate::utils::mtls::ClientCertificate;
+
+pub struct TlsSettings {
+    pub config: ServerConfig,
+    pub trust_verifier: Option<Arc<dyn ClientCertVerifier>>,
+}
+
+#[derive(Debug)]
+struct OptionalClientCertVerifier {
+    provider: Arc<CryptoProvider>,
+    root_hint_subjects: Vec<DistinguishedName>,
+}
+
+impl ClientCertVerifier for OptionalClientCertVerifier {
+    fn offer_client_auth(&self) -> bool {
+        true
+    }
+
+    fn client_auth_mandatory(&self) -> bool {
+        false
+    }
+
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        &self.root_hint_subjects
+    }
+
+    fn verify_client_cert(&self, _: &CertificateDer<'_>, _: &[CertificateDer<'_>], _: UnixTime) -> Result<ClientCertVerified, Error> {
+        Ok(ClientCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, Error> {
+        verify_tls12_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(&self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct) -> Result<HandshakeSignatureValid, Error> {
+        verify_tls13_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+pub fn load_tls_settings() -> Result<Option<TlsSettings>, String> {
+    let (Ok(cert_file), Ok(key_file)) = (env::var("TLS_CERT_FILE"), env::var("TLS_KEY_FILE")) else {
+        return Ok(None);
+    };
+
+    if cert_file.is_empty() || key_file.is_empty() {
+        return Ok(None);
+    }
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+    let Ok(certs) = CertificateDer::pem_file_iter(&cert_file).and_then(|e| e.collect::<Result<Vec<_>, _>>()) else {
+        return Err(format!("Failed to read TLS certificate from {}", cert_file));
+    };
+
+    let Ok(key) = PrivateKeyDer::from_pem_file(&key_file) else {
+        return Err(format!("Failed to read TLS private key from {}", key_file));
+    };
+
+    let (trust_verifier, root_hint_subjects) = match env::var("TLS_CLIENT_CA_FILE").ok().filter(|e| !e.is_empty()) {
+        Some(ca_file) => {
+            let Ok(ca_certs) = CertificateDer::pem_file_iter(&ca_file).and_then(|e| e.collect::<Result<Vec<_>, _>>()) else {
+                return Err(format!("Failed to read TLS client CA from {}", ca_file));
+            };
+
+            let mut roots = RootCertStore::empty();
+            roots.add_parsable_certificates(ca_certs);
+            let root_hint_subjects = roots.subjects();
+
+            let Ok(verifier) = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider.clone()).build() else {
+                return Err(String::from("Failed to build TLS client certificate verifier"));
+            };
+
+            (Some(verifier), root_hint_subjects)
+        },
+        None => (None, vec![]),
+    };
+
+    let client_cert_verifier = Arc::new(OptionalClientCertVerifier { provider: provider.clone(), root_hint_subjects });
+
+    let Ok(builder) = ServerConfig::builder_with_provider(provider).with_safe_default_protocol_versions() else {
+        return Err(String::from("Failed to configure TLS protocol versions"));
+    };
+
+    let Ok(config) = builder
+        .with_client_cert_verifier(client_cert_verifier)
+        .with_single_cert(certs, key) else {
+        return Err(String::from("Invalid TLS certificate or private key"));
+    };
+
+    Ok(Some(TlsSettings { config, trust_verifier }))
+}
+
+pub fn store_client_certificate(conn: &dyn Any, extensions: &mut Extensions, trust_verifier: Option<&Arc<dyn ClientCertVerifier>>) {
+    let Some(stream) = conn.downcast_ref::<TlsStream<TcpStream>>() else {
+        return;
+    };
+
+    let Some((end_entity, intermediates)) = stream.get_ref().1.peer_certificates().and_then(|e| e.split_first()) else {
+        return;
+    };
+
+    let trusted = trust_verifier.is_some_and(|e| e.verify_client_cert(end_entity, intermediates, UnixTime::now()).is_ok());
+
+    extensions.insert(ClientCertificate {
+        chain: std::iter::once(end_entity).chain(intermediates).map(|e| e.to_vec()).collect(),
+        trusted,
+    });
+}
\ No newline at end of file
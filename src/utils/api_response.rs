@@ -1,32 +1,105 @@
 use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+use crate::utils::oauth_error::OAuthErrorCode;
+
+const WWW_AUTHENTICATE_REALM: &str = "aphrodite";
 
 #[derive(serde::Serialize, Debug)]
 pub struct ApiError {
-    pub error: String,
+    pub error: OAuthErrorCode,
+    pub error_description: String,
     pub status_code: u16
 }
 
 impl ApiError {
-    pub fn new(error: String, status_code: StatusCode) -> Self {
+    pub fn new(error_description: String, status_code: StatusCode) -> Self {
+        Self::build(OAuthErrorCode::from_status_code(status_code), error_description, status_code)
+    }
+
+    pub fn oauth(error: OAuthErrorCode, error_description: impl Into<String>) -> Self {
+        Self::build(error, error_description.into(), error.status_code())
+    }
+
+    pub fn with_status(mut self, status_code: StatusCode) -> Self {
+        self.status_code = status_code.as_u16();
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        StatusCode::from_u16(self.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    pub fn to_response(&self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status());
+        response.insert_header(("Cache-Control", "no-store"));
+
+        if let Some(challenge) = self.www_authenticate() {
+            response.insert_header(("WWW-Authenticate", challenge));
+        }
+
+        response.json(ApiErrorResponse::from(self))
+    }
+
+    fn build(error: OAuthErrorCode, error_description: String, status_code: StatusCode) -> Self {
         if std::env::var("RUST_LOG").unwrap_or("INFO".to_string()).to_lowercase().contains("debug") {
-            eprintln!("[ERROR] {}", error);
+            eprintln!("[ERROR] {}: {}", error.as_str(), error_description);
         }
 
-        Self { error, status_code: status_code.as_u16() }
+        Self { error, error_description, status_code: status_code.as_u16() }
+    }
+
+    fn www_authenticate(&self) -> Option<String> {
+        if self.status() != StatusCode::UNAUTHORIZED {
+            return None;
+        }
+
+        match self.error {
+            OAuthErrorCode::InvalidClient => Some(format!(r#"Basic realm="{}""#, WWW_AUTHENTICATE_REALM)),
+            OAuthErrorCode::InvalidToken => Some(format!(
+                r#"Bearer realm="{}", error="{}", error_description="{}""#,
+                WWW_AUTHENTICATE_REALM,
+                self.error.as_str(),
+                self.error_description.replace(['"', '\\'], "")
+            )),
+            _ => None,
+        }
     }
 }
 
 #[derive(serde::Serialize)]
 pub struct ApiErrorResponse {
-    pub error: String,
+    pub error: OAuthErrorCode,
+    pub error_description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_uri: Option<String>,
 }
 
 impl ApiErrorResponse {
-    pub fn new(error: String) -> Self {
-        Self { error }
+    pub fn new(error: OAuthErrorCode, error_description: String) -> Self {
+        Self { error, error_description, error_uri: error_uri(error) }
     }
 }
 
+impl From<&ApiError> for ApiErrorResponse {
+    fn from(error: &ApiError) -> Self {
+        Self::new(error.error, error.error_description.clone())
+    }
+}
+
+/// Points at the documentation of an error code when `ERROR_URI` is set.
+pub fn error_uri(error: OAuthErrorCode) -> Option<String> {
+    std::env::var("ERROR_URI")
+        .ok()
+        .filter(|e| !e.is_empty())
+        .map(|e| format!("{}#{}", e, error.as_str()))
+}
+
+/// Renders request extraction failures as `invalid_request`.
+pub fn invalid_request_error<E: std::fmt::Debug + std::fmt::Display + 'static>(error: E) -> actix_web::Error {
+    let response = ApiError::oauth(OAuthErrorCode::InvalidRequest, error.to_string()).to_response();
+    actix_web::error::InternalError::from_response(error, response).into()
+}
+
 #[derive(serde::Serialize)]
 pub struct ApiSuccess<T> {
     pub data: T,
@@ -37,4 +110,4 @@ impl<T> ApiSuccess<T> {
     pub fn new(data: T, status_code: StatusCode) -> Self {
         Self { data, status_code: status_code.as_u16() }
     }
-}
\ No newline at end of file
+}
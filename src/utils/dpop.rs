@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use jsonwebtoken::jwk::ThumbprintHash;
+use sha2::{Digest, Sha256};
+
+pub const DPOP_HEADER: &str = "DPoP";
+pub const DPOP_NONCE_HEADER: &str = "DPoP-Nonce";
+pub const DPOP_TOKEN_TYPE: &str = "DPoP";
+pub const BEARER_TOKEN_TYPE: &str = "Bearer";
+pub const DPOP_PROOF_TYPE: &str = "dpop+jwt";
+pub const DPOP_PROOF_MAX_AGE: i64 = 60;
+pub const DPOP_SIGNING_ALGORITHMS: [Algorithm; 4] = [Algorithm::ES256, Algorithm::RS256, Algorithm::PS256, Algorithm::EdDSA];
+
+#[derive(Debug, serde::Deserialize)]
+pub struct DpopProofClaims {
+    pub jti: String,
+    pub htm: String,
+    pub htu: String,
+    pub iat: i64,
+    pub ath: Option<String>,
+    pub nonce: Option<String>,
+}
+
+pub struct DpopProof {
+    pub claims: DpopProofClaims,
+    pub jkt: String,
+}
+
+pub fn decode_dpop_proof(proof: &str) -> Result<DpopProof, String> {
+    let Ok(header) = decode_header(proof) else {
+        return Err(String::from("Invalid DPoP proof header"));
+    };
+
+    if header.typ.as_deref() != Some(DPOP_PROOF_TYPE) {
+        return Err(String::from("Invalid DPoP proof type"));
+    }
+
+    if !DPOP_SIGNING_ALGORITHMS.contains(&header.alg) {
+        return Err(String::from("Unsupported DPoP proof algorithm"));
+    }
+
+    let Some(jwk) = header.jwk else {
+        return Err(String::from("Missing DPoP proof key"));
+    };
+
+    if has_private_key(proof) {
+        return Err(String::from("DPoP proof key must not contain private parameters"));
+    }
+
+    let Ok(decoding_key) = DecodingKey::from_jwk(&jwk) else {
+        return Err(String::from("Invalid DPoP proof key"));
+    };
+
+    let mut validation = Validation::new(header.alg);
+    validation.required_spec_claims = HashSet::new();
+    validation.validate_exp = false;
+    validation.validate_aud = false;
+
+    let Ok(token) = decode::<DpopProofClaims>(proof, &decoding_key, &validation) else {
+        return Err(String::from("Invalid DPoP proof signature"));
+    };
+
+    Ok(DpopProof {
+        claims: token.claims,
+        jkt: jwk.thumbprint(ThumbprintHash::SHA256),
+    })
+}
+
+/// Checks that a proof was made for this request, recently, and for the presented access token if there is one.
+pub fn validate_dpop_claims(claims: &DpopProofClaims, method: &str, url: &str, access_token: Option<&str>, now: i64) -> Result<(), String> {
+    let htu = claims.htu.split(['?', '#']).next().unwrap_or_default();
+
+    if claims.htm != method || htu != url {
+        return Err(String::from("DPoP proof does not match the request"));
+    }
+
+    if (now - claims.iat).abs() > DPOP_PROOF_MAX_AGE {
+        return Err(String::from("DPoP proof has expired"));
+    }
+
+    if let Some(access_token) = access_token
+        && claims.ath != Some(access_token_hash(access_token)) {
+        return Err(String::from("DPoP proof does not match the access token"));
+    }
+
+    Ok(())
+}
+
+pub fn access_token_hash(access_token: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(access_token.as_bytes()))
+}
+
+pub fn token_type(dpop_jkt: &Option<String>) -> String {
+    match dpop_jkt {
+        Some(_) => String::from(DPOP_TOKEN_TYPE),
+        None => String::from(BEARER_TOKEN_TYPE),
+    }
+}
+
+fn has_private_key(proof: &str) -> bool {
+    let Some(Ok(header)) = proof.split('.').next().map(|e| URL_SAFE_NO_PAD.decode(e)) else {
+        return true;
+    };
+
+    let Ok(header) = serde_json::from_slice::<serde_json::Value>(&header) else {
+        return true;
+    };
+
+    header.get("jwk").and_then(|e| e.get("d")).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_lc_rs::rand::SystemRandom;
+    use aws_lc_rs::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+    use base64::Engine;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use jsonwebtoken::jwk::Jwk;
+    use sha2::{Digest, Sha256};
+    use super::{access_token_hash, decode_dpop_proof, validate_dpop_claims, DpopProofClaims};
+
+    const URL: &str = "https://as.example.com/api/v1/auth/token";
+
+    struct ProofKey {
+        der: Vec<u8>,
+        jwk: serde_json::Value,
+    }
+
+    impl ProofKey {
+        fn generate() -> Self {
+            let der = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &SystemRandom::new()).unwrap().as_ref().to_vec();
+            let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &der).unwrap();
+            let point = pair.public_key().as_ref();
+
+            let jwk = serde_json::json!({
+                "kty": "EC",
+                "crv": "P-256",
+                "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+                "y": URL_SAFE_NO_PAD.encode(&point[33..65]),
+            });
+
+            Self { der, jwk }
+        }
+
+        fn thumbprint(&self) -> String {
+            let canonical = format!(r#"{{"crv":"P-256","kty":"EC","x":{},"y":{}}}"#, self.jwk["x"], self.jwk["y"]);
+            URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+        }
+
+        fn sign(&self, typ: &str, jwk: &serde_json::Value, claims: &serde_json::Value) -> String {
+            let header = serde_json::json!({ "typ": typ, "alg": "ES256", "jwk": jwk });
+            let input = format!("{}.{}", URL_SAFE_NO_PAD.encode(header.to_string()), URL_SAFE_NO_PAD.encode(claims.to_string()));
+
+            let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &self.der).unwrap();
+            let signature = pair.sign(&SystemRandom::new(), input.as_bytes()).unwrap();
+
+            format!("{}.{}", input, URL_SAFE_NO_PAD.encode(signature.as_ref()))
+        }
+
+        fn proof(&self, claims: &serde_json::Value) -> String {
+            self.sign("dpop+jwt", &self.jwk, claims)
+        }
+    }
+
+    fn claims(now: i64) -> serde_json::Value {
+        serde_json::json!({ "jti": "e1j3V_bKic8-LAEB", "htm": "POST", "htu": URL, "iat": now })
+    }
+
+    fn proof_claims(value: serde_json::Value) -> DpopProofClaims {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn decodes_a_valid_proof() {
+        let key = ProofKey::generate();
+        let now = chrono::Utc::now().timestamp();
+
+        let proof = decode_dpop_proof(key.proof(&claims(now)).as_str()).unwrap();
+
+        assert_eq!(proof.jkt, key.thumbprint());
+        assert_eq!(proof.claims.htm, "POST");
+        assert_eq!(proof.claims.iat, now);
+    }
+
+    #[test]
+    fn rejects_malformed_proofs() {
+        let key = ProofKey::generate();
+        let claims = claims(chrono::Utc::now().timestamp());
+
+        assert!(decode_dpop_proof(key.sign("JWT", &key.jwk, &claims).as_str()).is_err());
+        assert!(decode_dpop_proof(key.sign("dpop+jwt", &ProofKey::generate().jwk, &claims).as_str()).is_err());
+
+        let mut private = key.jwk.clone();
+        private["d"] = serde_json::Value::String(URL_SAFE_NO_PAD.encode([1u8; 32]));
+        assert!(decode_dpop_proof(key.sign("dpop+jwt", &private, &claims).as_str()).is_err());
+
+        let mut header = Header::new(Algorithm::HS256);
+        header.typ = Some(String::from("dpop+jwt"));
+        header.jwk = Some(serde_json::from_value::<Jwk>(key.jwk.clone()).unwrap());
+        let symmetric = encode(&header, &claims, &EncodingKey::from_secret(b"secret")).unwrap();
+        assert!(decode_dpop_proof(symmetric.as_str()).is_err());
+
+        assert!(decode_dpop_proof("not.a.proof").is_err());
+    }
+
+    #[test]
+    fn validates_request_binding() {
+        let now = chrono::Utc::now().timestamp();
+
+        assert!(validate_dpop_claims(&proof_claims(claims(now)), "POST", URL, None, now).is_ok());
+        assert!(validate_dpop_claims(&proof_claims(claims(now)), "GET", URL, None, now).is_err());
+        assert!(validate_dpop_claims(&proof_claims(claims(now)), "POST", "https://as.example.com/api/v1/auth/userinfo", None, now).is_err());
+
+        let mut query = claims(now);
+        query["htu"] = serde_json::Value::String(format!("{}?a=b#c", URL));
+        assert!(validate_dpop_claims(&proof_claims(query), "POST", URL, None, now).is_ok());
+    }
+
+    #[test]
+    fn validates_proof_age() {
+        let now = chrono::Utc::now().timestamp();
+
+        assert!(validate_dpop_claims(&proof_claims(claims(now - 30)), "POST", URL, None, now).is_ok());
+        assert!(validate_dpop_claims(&proof_claims(claims(now - 61)), "POST", URL, None, now).is_err());
+        assert!(validate_dpop_claims(&proof_claims(claims(now + 61)), "POST", URL, None, now).is_err());
+    }
+
+    #[test]
+    fn validates_access_token_hash() {
+        let now = chrono::Utc::now().timestamp();
+        let access_token = "Kz~8mXK1EalYznwH-LC-1fBAo.4Ljp~zsPE_NeO.gxU";
+
+        assert_eq!(access_token_hash(access_token), "fUHyO2r2Z3DZ53EsNrWBb0xWXoaNy59IiKCAqksmQEo");
+
+        let mut bound = claims(now);
+        bound["ath"] = serde_json::Value::String(access_token_hash(access_token));
+
+        assert!(validate_dpop_claims(&proof_claims(bound.clone()), "POST", URL, Some(access_token), now).is_ok());
+        assert!(validate_dpop_claims(&proof_claims(bound), "POST", URL, Some("other"), now).is_err());
+        assert!(validate_dpop_claims(&proof_claims(claims(now)), "POST", URL, Some(access_token), now).is_err());
+    }
+}
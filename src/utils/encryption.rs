@@ -0,0 +1,73 @@
+use std::env;
+use aws_lc_rs::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rand::{rng, RngCore};
+
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+pub fn check_encryption_key() -> Result<(), String> {
+    encryption_key().map(|_| ())
+}
+
+pub fn encrypt(plaintext: &str) -> Result<String, String> {
+    let key = encryption_key()?;
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rng().fill_bytes(&mut nonce);
+
+    let mut in_out = plaintext.as_bytes().to_vec();
+
+    if key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut in_out).is_err() {
+        return Err(String::from("Failed to encrypt value"));
+    }
+
+    Ok(format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode([nonce.as_slice(), in_out.as_slice()].concat())))
+}
+
+pub fn decrypt(value: &str) -> Result<String, String> {
+    let Some(encoded) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+        return Ok(value.to_string());
+    };
+
+    let Ok(data) = STANDARD.decode(encoded) else {
+        return Err(String::from("Invalid encrypted value"));
+    };
+
+    if data.len() < NONCE_LEN {
+        return Err(String::from("Invalid encrypted value"));
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let Ok(nonce) = Nonce::try_assume_unique_for_key(nonce) else {
+        return Err(String::from("Invalid encrypted value"));
+    };
+
+    let mut in_out = ciphertext.to_vec();
+
+    let Ok(plaintext) = encryption_key()?.open_in_place(nonce, Aad::empty(), &mut in_out) else {
+        return Err(String::from("Failed to decrypt value"));
+    };
+
+    String::from_utf8(plaintext.to_vec()).map_err(|_| String::from("Failed to decrypt value"))
+}
+
+fn encryption_key() -> Result<LessSafeKey, String> {
+    let Ok(value) = env::var("DATA_ENCRYPTION_KEY") else {
+        return Err(String::from("DATA_ENCRYPTION_KEY not found"));
+    };
+
+    let Ok(bytes) = STANDARD.decode(value.trim()) else {
+        return Err(String::from("DATA_ENCRYPTION_KEY must be base64 encoded"));
+    };
+
+    let Ok(key) = UnboundKey::new(&AES_256_GCM, &bytes) else {
+        return Err(String::from("DATA_ENCRYPTION_KEY must be 32 bytes"));
+    };
+
+    Ok(LessSafeKey::new(key))
+}
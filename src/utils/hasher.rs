@@ -1,5 +1,12 @@
+use argon2::{Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version};
+use argon2::password_hash::SaltString;
+use argon2::password_hash::rand_core::OsRng;
 use sha2::{Digest, Sha256, Sha512};
 
+const SECRET_HASH_MEMORY_COST: u32 = 19 * 1024;
+const SECRET_HASH_TIME_COST: u32 = 2;
+const SECRET_HASH_PARALLELISM: u32 = 1;
+
 pub fn hash_sha512(input: &str) -> String {
     let mut hasher = Sha512::new();
     hasher.update(input);
@@ -15,4 +22,25 @@ pub fn hash_sha256(input: &str) -> String {
 
     let digest = hasher.finalize();
     hex::encode(digest)
+}
+
+pub fn hash_secret(input: &str) -> Result<String, String> {
+    let Ok(params) = Params::new(SECRET_HASH_MEMORY_COST, SECRET_HASH_TIME_COST, SECRET_HASH_PARALLELISM, None) else {
+        return Err(String::from("Invalid secret hash parameters"));
+    };
+
+    let salt = SaltString::generate(&mut OsRng);
+
+    match Argon2::new(Algorithm::Argon2id, Version::V0x13, params).hash_password(input.as_bytes(), &salt) {
+        Ok(e) => Ok(e.to_string()),
+        Err(_) => Err(String::from("Failed to hash secret"))
+    }
+}
+
+pub fn verify_secret(input: &str, hash: &str) -> bool {
+    let Ok(hash) = PasswordHash::new(hash) else {
+        return false;
+    };
+
+    Argon2::default().verify_password(input.as_bytes(), &hash).is_ok()
 }
\ No newline at end of file
@@ -1,4 +1,15 @@
 pub mod entropy;
 pub mod api_response;
 pub mod hasher;
-pub mod token;
\ No newline at end of file
+pub mod token;
+pub mod signing_key;
+pub mod dpop;
+pub mod mtls;
+pub mod redirect_uri;
+pub mod html;
+pub mod response_mode;
+pub mod oauth_error;
+pub mod encryption;
+pub mod outbound;
+#[cfg(test)]
+pub mod test_support;
\ No newline at end of file
@@ -0,0 +1,297 @@
+use std::net::IpAddr;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use sha2::{Digest, Sha256};
+use x509_parser::asn1_rs::{Tag, ToDer};
+use x509_parser::prelude::{AttributeTypeAndValue, FromDer, GeneralName, X509Certificate, X509Name};
+
+pub const TLS_CLIENT_AUTH: &str = "tls_client_auth";
+pub const SELF_SIGNED_TLS_CLIENT_AUTH: &str = "self_signed_tls_client_auth";
+
+/// Attribute type names of RFC 4514 and the common extensions, with their OIDs.
+const ATTRIBUTE_TYPES: [(&str, &str); 12] = [
+    ("CN", "2.5.4.3"),
+    ("L", "2.5.4.7"),
+    ("ST", "2.5.4.8"),
+    ("O", "2.5.4.10"),
+    ("OU", "2.5.4.11"),
+    ("C", "2.5.4.6"),
+    ("STREET", "2.5.4.9"),
+    ("DC", "0.9.2342.19200300.100.1.25"),
+    ("UID", "0.9.2342.19200300.100.1.1"),
+    ("SERIALNUMBER", "2.5.4.5"),
+    ("EMAILADDRESS", "1.2.840.113549.1.9.1"),
+    ("E", "1.2.840.113549.1.9.1"),
+];
+
+/// A distinguished name as a sequence of RDNs in certificate order, each a set of attribute OIDs and values.
+pub type DistinguishedName = Vec<Vec<(String, String)>>;
+
+#[derive(Debug, Clone)]
+pub struct ClientCertificate {
+    pub chain: Vec<Vec<u8>>,
+    pub trusted: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct CertificateIdentity {
+    pub subject: DistinguishedName,
+    pub san_dns: Vec<String>,
+    pub san_uri: Vec<String>,
+    pub san_ip: Vec<String>,
+    pub san_email: Vec<String>,
+}
+
+impl ClientCertificate {
+    pub fn thumbprint(&self) -> String {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(self.chain.first().map(|e| e.as_slice()).unwrap_or_default()))
+    }
+
+    pub fn identity(&self) -> Result<CertificateIdentity, String> {
+        let Some(der) = self.chain.first() else {
+            return Err(String::from("Missing client certificate"));
+        };
+
+        let Ok((_, certificate)) = X509Certificate::from_der(der) else {
+            return Err(String::from("Invalid client certificate"));
+        };
+
+        let mut identity = CertificateIdentity {
+            subject: parse_certificate_name(certificate.subject()),
+            ..Default::default()
+        };
+
+        if let Ok(Some(san)) = certificate.subject_alternative_name() {
+            for name in &san.value.general_names {
+                match name {
+                    GeneralName::DNSName(e) => identity.san_dns.push(e.to_string()),
+                    GeneralName::URI(e) => identity.san_uri.push(e.to_string()),
+                    GeneralName::RFC822Name(e) => identity.san_email.push(e.to_string()),
+                    GeneralName::IPAddress(e) => {
+                        if let Some(ip) = parse_ip(e) {
+                            identity.san_ip.push(ip.to_string());
+                        }
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(identity)
+    }
+}
+
+/// Compares a registered RFC 4514 string with the subject of a certificate, RDN by RDN and in order.
+///
+/// RFC 4514 lists RDNs starting from the last one in the certificate. Values are compared ignoring case and insignificant whitespace.
+pub fn distinguished_name_matches(registered: &str, presented: &DistinguishedName) -> bool {
+    let Some(mut registered) = parse_distinguished_name(registered) else {
+        return false;
+    };
+
+    registered.reverse();
+
+    registered.len() == presented.len() && registered.iter().zip(presented).all(|(a, b)| normalize_rdn(a) == normalize_rdn(b))
+}
+
+/// Parses an RFC 4514 string into RDNs in the order they are written, resolving attribute type names to OIDs and unescaping values.
+pub fn parse_distinguished_name(dn: &str) -> Option<DistinguishedName> {
+    let mut rdns: DistinguishedName = vec![];
+    let mut rdn = vec![];
+    let mut chars = dn.trim().chars().peekable();
+
+    if chars.peek().is_none() {
+        return Some(rdns);
+    }
+
+    loop {
+        let mut attribute_type = String::new();
+
+        loop {
+            match chars.next()? {
+                '=' => break,
+                e => attribute_type.push(e),
+            }
+        }
+
+        let attribute_type = attribute_type_oid(attribute_type.trim())?;
+
+        while chars.next_if(|e| *e == ' ').is_some() {}
+
+        let mut value = vec![];
+        let mut hex = false;
+        let mut separator = None;
+
+        if chars.next_if(|e| *e == '#').is_some() {
+            hex = true;
+        }
+
+        while let Some(e) = chars.next() {
+            match e {
+                ',' | '+' => {
+                    separator = Some(e);
+                    break;
+                },
+                '\\' if !hex => {
+                    let e = chars.next()?;
+
+                    match e.to_digit(16) {
+                        Some(high) => value.push((high * 16 + chars.next()?.to_digit(16)?) as u8),
+                        None if "\"+,;<>\\ #=".contains(e) => value.extend_from_slice(e.to_string().as_bytes()),
+                        None => return None,
+                    }
+                },
+                e => value.extend_from_slice(e.to_string().as_bytes()),
+            }
+        }
+
+        let value = String::from_utf8(value).ok()?;
+
+        let value = match hex {
+            true if !value.trim().is_empty() && value.trim().chars().all(|e| e.is_ascii_hexdigit()) => format!("#{}", value.trim().to_ascii_lowercase()),
+            true => return None,
+            false => value,
+        };
+
+        rdn.push((attribute_type, value));
+
+        match separator {
+            Some('+') => {},
+            Some(_) => rdns.push(std::mem::take(&mut rdn)),
+            None => {
+                rdns.push(rdn);
+                return Some(rdns);
+            }
+        }
+    }
+}
+
+fn parse_certificate_name(name: &X509Name) -> DistinguishedName {
+    name.iter()
+        .map(|rdn| rdn.iter().map(|e| (e.attr_type().to_id_string(), attribute_value(e))).collect())
+        .collect()
+}
+
+fn attribute_value(attribute: &AttributeTypeAndValue) -> String {
+    if let Ok(e) = attribute.as_str() {
+        return e.to_string();
+    }
+
+    let value = attribute.attr_value();
+
+    if value.tag() == Tag::BmpString {
+        let units = value.data.chunks_exact(2).map(|e| u16::from_be_bytes([e[0], e[1]])).collect::<Vec<u16>>();
+
+        if let Ok(e) = String::from_utf16(&units) {
+            return e;
+        }
+    }
+
+    // Other values are compared in the `#` hex form RFC 4514 uses for them.
+    let der = value.to_der_vec().unwrap_or_default();
+    format!("#{}", der.iter().map(|e| format!("{:02x}", e)).collect::<String>())
+}
+
+fn attribute_type_oid(name: &str) -> Option<String> {
+    let oid = name.strip_prefix("oid.").or(name.strip_prefix("OID.")).unwrap_or(name);
+
+    if !oid.is_empty() && oid.split('.').all(|e| !e.is_empty() && e.chars().all(|e| e.is_ascii_digit())) {
+        return Some(oid.to_string());
+    }
+
+    ATTRIBUTE_TYPES.iter()
+        .find(|(e, _)| e.eq_ignore_ascii_case(name))
+        .map(|(_, e)| e.to_string())
+}
+
+fn normalize_rdn(rdn: &[(String, String)]) -> Vec<(String, String)> {
+    let mut rdn = rdn.iter()
+        .map(|(oid, value)| (oid.clone(), value.split_whitespace().collect::<Vec<&str>>().join(" ").to_lowercase()))
+        .collect::<Vec<(String, String)>>();
+
+    rdn.sort();
+    rdn
+}
+
+fn parse_ip(bytes: &[u8]) -> Option<IpAddr> {
+    match bytes.len() {
+        4 => <[u8; 4]>::try_from(bytes).ok().map(IpAddr::from),
+        16 => <[u8; 16]>::try_from(bytes).ok().map(IpAddr::from),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rcgen::{CertificateParams, DnType, DnValue, KeyPair};
+    use super::{distinguished_name_matches, parse_distinguished_name, ClientCertificate};
+
+    fn certificate(subject: &[(DnType, DnValue)]) -> ClientCertificate {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(vec![String::from("client.example.com")]).unwrap();
+        params.distinguished_name = rcgen::DistinguishedName::new();
+
+        for (attribute, value) in subject {
+            params.distinguished_name.push(attribute.clone(), value.clone());
+        }
+
+        ClientCertificate {
+            chain: vec![params.self_signed(&key).unwrap().der().to_vec()],
+            trusted: true,
+        }
+    }
+
+    fn subject() -> Vec<(DnType, DnValue)> {
+        vec![
+            (DnType::CountryName, DnValue::PrintableString("JP".try_into().unwrap())),
+            (DnType::OrganizationName, DnValue::Utf8String(String::from("Example, Inc."))),
+            (DnType::CommonName, DnValue::Utf8String(String::from("client one"))),
+        ]
+    }
+
+    #[test]
+    fn matches_escaped_values_in_rfc_4514_order() {
+        let identity = certificate(&subject()).identity().unwrap();
+
+        assert!(distinguished_name_matches(r"CN=client one,O=Example\, Inc.,C=JP", &identity.subject));
+        assert!(distinguished_name_matches(r"cn = Client  One, o=example\2C inc., c=jp", &identity.subject));
+        assert!(distinguished_name_matches(r"2.5.4.3=client one,OID.2.5.4.10=Example\, Inc.,C=JP", &identity.subject));
+    }
+
+    #[test]
+    fn rejects_reordered_or_split_names() {
+        let identity = certificate(&subject()).identity().unwrap();
+
+        assert!(!distinguished_name_matches(r"C=JP,O=Example\, Inc.,CN=client one", &identity.subject));
+        assert!(!distinguished_name_matches("CN=client one,O=Example, Inc.,C=JP", &identity.subject));
+        assert!(!distinguished_name_matches(r"CN=client one,O=Example\, Inc.", &identity.subject));
+        assert!(!distinguished_name_matches(r"CN=client two,O=Example\, Inc.,C=JP", &identity.subject));
+        assert!(!distinguished_name_matches(r"CN=client one+UID=1,O=Example\, Inc.,C=JP", &identity.subject));
+    }
+
+    #[test]
+    fn rejects_values_smuggled_through_separators() {
+        let identity = certificate(&[
+            (DnType::OrganizationName, DnValue::Utf8String(String::from("Example"))),
+            (DnType::CommonName, DnValue::Utf8String(String::from("evil,O=Example"))),
+        ]).identity().unwrap();
+
+        assert!(!distinguished_name_matches("CN=evil,O=Example,O=Example", &identity.subject));
+        assert!(distinguished_name_matches(r"CN=evil\,O\=Example,O=Example", &identity.subject));
+    }
+
+    #[test]
+    fn parses_rfc_4514_syntax() {
+        assert_eq!(
+            parse_distinguished_name(r"CN=a\+b+UID=x,DC=#0403616263").unwrap(),
+            vec![
+                vec![(String::from("2.5.4.3"), String::from("a+b")), (String::from("0.9.2342.19200300.100.1.1"), String::from("x"))],
+                vec![(String::from("0.9.2342.19200300.100.1.25"), String::from("#0403616263"))],
+            ],
+        );
+        assert_eq!(parse_distinguished_name(r"CN=caf\C3\A9").unwrap(), vec![vec![(String::from("2.5.4.3"), String::from("café"))]]);
+        assert!(parse_distinguished_name("CN").is_none());
+        assert!(parse_distinguished_name("XX=a").is_none());
+        assert!(parse_distinguished_name(r"CN=a\q").is_none());
+    }
+}
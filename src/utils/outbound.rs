@@ -0,0 +1,167 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+use reqwest::Url;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+/// Whether outbound requests may target loopback, private and link-local addresses, which is only meant for local development.
+pub fn allow_private_networks() -> bool {
+    std::env::var("OUTBOUND_ALLOW_PRIVATE_NETWORKS").is_ok_and(|e| e == "true")
+}
+
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(ip),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        || a >= 240
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (b == 18 || b == 19)))
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80
+        || first == 0x2001 && ip.segments()[1] == 0x0db8)
+}
+
+/// Checks that an outbound URI uses http(s) and does not name a loopback, private or link-local host.
+///
+/// Host names are checked again against their resolved addresses when the request is made.
+pub fn validate_outbound_uri(uri: &str, allow_private: bool) -> Result<Url, String> {
+    let Ok(url) = Url::parse(uri) else {
+        return Err(String::from("Invalid URI"));
+    };
+
+    if url.scheme() != "https" && url.scheme() != "http" {
+        return Err(String::from("URI must use http or https"));
+    }
+
+    if allow_private {
+        return Ok(url);
+    }
+
+    let Some(host) = url.host_str().map(|e| e.trim_start_matches('[').trim_end_matches(']').to_ascii_lowercase()) else {
+        return Err(String::from("URI must have a host"));
+    };
+
+    let private = match host.parse::<IpAddr>() {
+        Ok(ip) => !is_public_ip(ip),
+        Err(_) => host == "localhost" || host.ends_with(".localhost"),
+    };
+
+    if private {
+        return Err(String::from("URI must not target a private network"));
+    }
+
+    Ok(url)
+}
+
+/// Builds an HTTP client for client-supplied URIs that does not follow redirects and refuses to connect to non-public addresses.
+pub fn outbound_client(timeout: Duration, allow_private: bool) -> Result<reqwest::Client, reqwest::Error> {
+    let builder = reqwest::Client::builder()
+        .timeout(timeout)
+        .redirect(reqwest::redirect::Policy::none());
+
+    if allow_private {
+        return builder.build();
+    }
+
+    builder.dns_resolver(Arc::new(PublicResolver)).build()
+}
+
+/// Reads a response body, failing once it grows past `limit` bytes instead of buffering all of it.
+pub async fn read_limited(mut resp: reqwest::Response, limit: usize) -> Result<Vec<u8>, String> {
+    if resp.content_length().is_some_and(|e| e > limit as u64) {
+        return Err(String::from("Response body is too large"));
+    }
+
+    let mut body = vec![];
+
+    while let Some(chunk) = resp.chunk().await.map_err(|e| e.to_string())? {
+        if body.len() + chunk.len() > limit {
+            return Err(String::from("Response body is too large"));
+        }
+
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+
+        Box::pin(async move {
+            let addrs = actix_web::rt::task::spawn_blocking(move || (host.as_str(), 0).to_socket_addrs())
+                .await
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)??
+                .collect::<Vec<SocketAddr>>();
+
+            if addrs.is_empty() || addrs.iter().any(|e| !is_public_ip(e.ip())) {
+                return Err("Host resolves to a private network".into());
+            }
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use reqwest::dns::Resolve;
+    use super::{is_public_ip, validate_outbound_uri, PublicResolver};
+
+    #[test]
+    fn classifies_addresses() {
+        for ip in ["127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1", "0.0.0.0", "::1", "fd00::1", "fe80::1", "::ffff:127.0.0.1"] {
+            assert!(!is_public_ip(ip.parse::<IpAddr>().unwrap()), "{}", ip);
+        }
+
+        for ip in ["8.8.8.8", "1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse::<IpAddr>().unwrap()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn validates_uris() {
+        assert!(validate_outbound_uri("https://rp.example.com/logout", false).is_ok());
+        assert!(validate_outbound_uri("ftp://rp.example.com/logout", false).is_err());
+        assert!(validate_outbound_uri("http://localhost:8080/logout", false).is_err());
+        assert!(validate_outbound_uri("http://[::1]/logout", false).is_err());
+        assert!(validate_outbound_uri("http://localhost:8080/logout", true).is_ok());
+    }
+
+    #[actix_web::test]
+    async fn resolver_refuses_private_addresses() {
+        let result = PublicResolver.resolve("localhost".parse().unwrap()).await;
+
+        assert!(result.is_err());
+    }
+}
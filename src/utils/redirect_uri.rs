@@ -0,0 +1,64 @@
+use reqwest::Url;
+
+const LOOPBACK_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "[::1]"];
+const LOOPBACK_IPS: [&str; 2] = ["127.0.0.1", "[::1]"];
+
+pub fn validate_web_redirect_uri(uri: &str) -> Result<(), String> {
+    let url = parse_redirect_uri(uri)?;
+
+    match url.scheme() {
+        "https" => Ok(()),
+        "http" if url.host_str().is_some_and(|e| LOOPBACK_HOSTS.contains(&e)) => Ok(()),
+        _ => Err(String::from("Redirect URI must use https or a loopback address")),
+    }
+}
+
+pub fn validate_native_redirect_uri(uri: &str) -> Result<Url, String> {
+    let url = parse_redirect_uri(uri)?;
+
+    match url.scheme() {
+        "http" => match url.host_str() {
+            Some("localhost") => Err(String::from("Loopback redirect URI must use 127.0.0.1 or [::1] instead of localhost")),
+            Some(host) if LOOPBACK_IPS.contains(&host) => Ok(url),
+            _ => Err(String::from("Native redirect URI over http must use a loopback IP address")),
+        },
+        "https" => Ok(url),
+        scheme if scheme.split('.').count() < 2 || scheme.split('.').any(|e| e.is_empty()) => {
+            Err(String::from("Private-use URI scheme must be a reverse domain name"))
+        },
+        _ => Ok(url),
+    }
+}
+
+pub fn match_native_redirect_uri(registered: &[String], uri: &str) -> Result<(), String> {
+    let url = validate_native_redirect_uri(uri)?;
+
+    let matched = match url.scheme() {
+        "http" => registered.iter()
+            .filter_map(|e| Url::parse(e).ok())
+            .any(|mut e| e.set_port(url.port()).is_ok() && e == url),
+        _ => registered.iter().any(|e| e == uri),
+    };
+
+    if !matched {
+        return Err(String::from("Redirect URI is not registered for this client"));
+    }
+
+    Ok(())
+}
+
+fn parse_redirect_uri(uri: &str) -> Result<Url, String> {
+    let Ok(url) = Url::parse(uri) else {
+        return Err(String::from("Invalid redirect URI"));
+    };
+
+    if url.fragment().is_some() {
+        return Err(String::from("Redirect URI must not contain a fragment"));
+    }
+
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err(String::from("Redirect URI must not contain credentials"));
+    }
+
+    Ok(url)
+}
@@ -0,0 +1,59 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::Url;
+use crate::dto::auth::authorize::response::AuthorizeResponse;
+
+pub const RESPONSE_MODE_QUERY: &str = "query";
+pub const RESPONSE_MODE_FRAGMENT: &str = "fragment";
+pub const RESPONSE_MODE_FORM_POST: &str = "form_post";
+pub const RESPONSE_MODE_JWT: &str = "jwt";
+pub const RESPONSE_MODES_SUPPORTED: [&str; 7] = [
+    RESPONSE_MODE_QUERY, RESPONSE_MODE_FRAGMENT, RESPONSE_MODE_FORM_POST,
+    "query.jwt", "fragment.jwt", "form_post.jwt", RESPONSE_MODE_JWT,
+];
+const JWT_SUFFIX: &str = ".jwt";
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// Returns how the response is delivered and whether it is wrapped in a signed JWT (JARM).
+pub fn parse_response_mode(response_mode: Option<&str>) -> Result<(&str, bool), String> {
+    let response_mode = response_mode.unwrap_or(RESPONSE_MODE_QUERY);
+
+    if !RESPONSE_MODES_SUPPORTED.contains(&response_mode) {
+        return Err(String::from("Invalid response mode"));
+    }
+
+    if response_mode == RESPONSE_MODE_JWT {
+        return Ok((RESPONSE_MODE_QUERY, true));
+    }
+
+    match response_mode.strip_suffix(JWT_SUFFIX) {
+        Some(e) => Ok((e, true)),
+        None => Ok((response_mode, false)),
+    }
+}
+
+pub fn authorization_response(redirect_uri: &str, delivery: &str, params: Vec<(&str, String)>) -> Result<AuthorizeResponse, String> {
+    let Ok(mut url) = Url::parse(redirect_uri) else {
+        return Err(String::from("Invalid redirect URI"));
+    };
+
+    match delivery {
+        RESPONSE_MODE_FORM_POST => Ok(AuthorizeResponse::FormPost {
+            action: redirect_uri.to_string(),
+            params: params.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+        }),
+        RESPONSE_MODE_FRAGMENT => {
+            let fragment = params
+                .iter()
+                .map(|(k, v)| format!("{}={}", utf8_percent_encode(k, COMPONENT), utf8_percent_encode(v, COMPONENT)))
+                .collect::<Vec<String>>()
+                .join("&");
+
+            url.set_fragment(Some(&fragment));
+            Ok(AuthorizeResponse::Redirect(url.to_string()))
+        },
+        _ => {
+            url.query_pairs_mut().extend_pairs(&params);
+            Ok(AuthorizeResponse::Redirect(url.to_string()))
+        },
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+use crate::adapters::spi::cache::redis::RedisCache;
+use crate::adapters::spi::db::postgres_db::PostgresDB;
+use crate::application::spi::cache::CacheInterface;
+use crate::application::spi::db::DBInterface;
+
+/// Connects to the database configured by the `DB_*` variables, or returns `None` so the test is skipped.
+pub async fn database() -> Option<Arc<PostgresDB>> {
+    std::env::var("DB_HOST").ok()?;
+
+    Some(Arc::new(PostgresDB::new().await))
+}
+
+/// Connects to the Redis server configured by the `REDIS_*` variables, or returns `None` so the test is skipped.
+pub fn cache() -> Option<Arc<RedisCache>> {
+    std::env::var("REDIS_HOST").ok()?;
+
+    Some(Arc::new(RedisCache::new()))
+}
+
+pub fn unique(prefix: &str) -> String {
+    format!("{}-{}", prefix, uuid::Uuid::new_v4())
+}
@@ -1,40 +1,210 @@
+use std::sync::Arc;
 use base64::Engine;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use chrono::DateTime;
-use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
-use rand::{rng, RngCore};
-use crate::dto::auth::token::access_token::AccessToken;
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, Header, Validation};
+use rand::{rng, Rng, RngCore};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use crate::dto::auth::authorize::authorization_response::AuthorizationResponseClaims;
+use crate::dto::auth::end_session::logout_token::{LogoutTokenClaims, BACKCHANNEL_LOGOUT_EVENT};
+use crate::dto::auth::introspect::response::IntrospectResponse;
+use crate::dto::auth::introspect::token_introspection::TokenIntrospectionClaims;
+use crate::dto::auth::token::access_token::{AccessToken, Actor, Confirmation};
+use crate::dto::auth::token::id_token::{IdTokenClaims, IdTokenHintClaims, IdTokenUserClaims};
+use crate::domain::oauth_session::OauthSession;
 use crate::utils::hasher::hash_sha512;
+use crate::utils::signing_key::SigningKey;
+
+pub const SIGNING_ALGORITHM: Algorithm = Algorithm::RS256;
+pub const ACCESS_TOKEN_EXPIRES_IN: i64 = 4 * 60 * 60;
+pub const ID_TOKEN_EXPIRES_IN: i64 = 60 * 60;
+pub const LOGOUT_TOKEN_EXPIRES_IN: i64 = 2 * 60;
+pub const AUTHORIZATION_RESPONSE_EXPIRES_IN: i64 = 10 * 60;
+pub const USER_CODE_LENGTH: usize = 8;
+const USER_CODE_ALPHABET: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+
+pub struct AccessTokenParams {
+    pub scopes: Vec<String>,
+    pub jwt_iss: String,
+    pub session_id: String,
+    pub sub: String,
+    pub client_id: String,
+    pub auth_time: Option<chrono::NaiveDateTime>,
+    pub cnf: Option<Confirmation>,
+}
 
 pub fn generate_access_token(
-    scopes: Vec<String>,
+    params: AccessTokenParams,
     now: DateTime<chrono::Utc>,
-    jwt_iss: String,
-    session_id: String,
-    user_id: uuid::Uuid,
-    client_id: String,
-    encoding_key: EncodingKey
+    signing_key: &SigningKey
 ) -> Result<String, String> {
     let id = hash_sha512(uuid::Uuid::new_v4().to_string().as_str());
-    let exp = now.timestamp() + 4 * 60 * 60;
+    let exp = now.timestamp() + ACCESS_TOKEN_EXPIRES_IN;
 
     let token = AccessToken {
-        scopes,
-        sub: user_id.clone(),
-        exp: exp.clone() as usize,
+        scopes: params.scopes,
+        sub: params.sub,
+        exp: exp as usize,
         iat: now.timestamp() as usize,
-        iss: jwt_iss,
-        aud: "".to_string(),
+        iss: params.jwt_iss,
+        aud: String::new(),
         jti: id.clone(),
+        sid: params.session_id,
+        client_id: params.client_id,
+        auth_time: params.auth_time.map(|e| e.and_utc().timestamp()).unwrap_or(now.timestamp()) as usize,
+        act: None,
+        cnf: params.cnf,
+    };
+
+    sign_jwt(&token, "JWT", signing_key)
+}
+
+pub struct IdTokenParams<'a> {
+    pub user: IdTokenUserClaims,
+    pub session: &'a OauthSession,
+    pub jwt_iss: String,
+    pub access_token: &'a str,
+    pub code: Option<&'a str>,
+    pub nonce: Option<String>,
+}
+
+pub fn generate_id_token(
+    params: IdTokenParams,
+    now: DateTime<chrono::Utc>,
+    signing_key: &SigningKey
+) -> Result<String, String> {
+    let session = params.session;
+
+    let (Some(session_id), Some(user_id), Some(client_id)) = (session.id, session.user_id, session.client_id.clone()) else {
+        return Err(String::from("Session is not authenticated"));
+    };
+
+    let token = IdTokenClaims {
+        iss: params.jwt_iss,
+        sub: user_id.to_string(),
+        aud: client_id.clone(),
+        azp: client_id,
+        exp: (now.timestamp() + ID_TOKEN_EXPIRES_IN) as usize,
+        iat: now.timestamp() as usize,
+        auth_time: session.auth_time.map(|e| e.and_utc().timestamp()).unwrap_or(now.timestamp()) as usize,
+        sid: session_id.to_string(),
+        nonce: params.nonce,
+        acr: session.acr.clone(),
+        amr: session.amr.clone(),
+        at_hash: Some(token_hash(params.access_token)),
+        c_hash: params.code.map(token_hash),
+        user: params.user,
+    };
+
+    sign_jwt(&token, "JWT", signing_key)
+}
+
+/// Left-most half of the SHA-256 digest, as used by `at_hash` and `c_hash`.
+pub fn token_hash(value: &str) -> String {
+    let digest = Sha256::digest(value.as_bytes());
+    URL_SAFE_NO_PAD.encode(&digest[..digest.len() / 2])
+}
+
+pub struct ExchangedAccessTokenParams<'a> {
+    pub subject: &'a AccessToken,
+    pub scopes: Vec<String>,
+    pub client_id: String,
+    pub audience: String,
+    pub act: Actor,
+    pub cnf: Option<Confirmation>,
+}
+
+pub fn generate_exchanged_access_token(
+    params: ExchangedAccessTokenParams,
+    now: DateTime<chrono::Utc>,
+    signing_key: &SigningKey
+) -> Result<String, String> {
+    let subject = params.subject;
+
+    let token = AccessToken {
+        scopes: params.scopes,
+        sub: subject.sub.clone(),
+        exp: (now.timestamp() + ACCESS_TOKEN_EXPIRES_IN).min(subject.exp as i64) as usize,
+        iat: now.timestamp() as usize,
+        iss: subject.iss.clone(),
+        aud: params.audience,
+        jti: hash_sha512(uuid::Uuid::new_v4().to_string().as_str()),
+        sid: subject.sid.clone(),
+        client_id: params.client_id,
+        auth_time: subject.auth_time,
+        act: Some(params.act),
+        cnf: params.cnf,
+    };
+
+    sign_jwt(&token, "JWT", signing_key)
+}
+
+pub fn generate_introspection_token(
+    introspection: IntrospectResponse,
+    now: DateTime<chrono::Utc>,
+    jwt_iss: String,
+    audience: String,
+    signing_key: &SigningKey
+) -> Result<String, String> {
+    let token = TokenIntrospectionClaims {
+        iss: jwt_iss,
+        aud: audience,
+        iat: now.timestamp() as usize,
+        token_introspection: introspection,
+    };
+
+    sign_jwt(&token, "token-introspection+jwt", signing_key)
+}
+
+pub fn generate_logout_token(
+    now: DateTime<chrono::Utc>,
+    jwt_iss: String,
+    session_id: String,
+    user_id: uuid::Uuid,
+    client_id: String,
+    signing_key: &SigningKey
+) -> Result<String, String> {
+    let token = LogoutTokenClaims {
+        iss: jwt_iss,
+        sub: user_id.to_string(),
+        aud: client_id,
+        iat: now.timestamp() as usize,
+        exp: (now.timestamp() + LOGOUT_TOKEN_EXPIRES_IN) as usize,
+        jti: uuid::Uuid::new_v4().to_string(),
         sid: session_id,
-        client_id,
-        auth_time: now.timestamp() as usize,
+        events: [(BACKCHANNEL_LOGOUT_EVENT.to_string(), serde_json::json!({}))].into(),
+    };
+
+    sign_jwt(&token, "logout+jwt", signing_key)
+}
+
+pub fn generate_authorization_response_token(
+    params: Vec<(&str, String)>,
+    now: DateTime<chrono::Utc>,
+    jwt_iss: String,
+    client_id: String,
+    signing_key: &SigningKey
+) -> Result<String, String> {
+    let token = AuthorizationResponseClaims {
+        iss: jwt_iss,
+        aud: client_id,
+        exp: (now.timestamp() + AUTHORIZATION_RESPONSE_EXPIRES_IN) as usize,
+        params: params.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
     };
 
+    sign_jwt(&token, "JWT", signing_key)
+}
+
+pub fn sign_jwt<T: Serialize>(claims: &T, typ: &str, signing_key: &SigningKey) -> Result<String, String> {
+    let mut header = Header::new(SIGNING_ALGORITHM);
+    header.typ = Some(typ.to_string());
+    header.kid = Some(signing_key.kid.clone());
+
     let Ok(result) = encode(
-        &Header::new(Algorithm::RS256),
-        &token,
-        &encoding_key
+        &header,
+        claims,
+        &signing_key.encoding_key
     ) else {
         return Err(String::from("Failed to encode JWT"));
     };
@@ -45,5 +215,228 @@ pub fn generate_access_token(
 pub fn generate_refresh_token() -> String {
     let mut buf = [0u8; 64];
     rng().fill_bytes(&mut buf);
-    URL_SAFE_NO_PAD.encode(&buf)
-}
\ No newline at end of file
+    URL_SAFE_NO_PAD.encode(buf)
+}
+
+pub fn generate_client_secret() -> String {
+    let mut buf = [0u8; 32];
+    rng().fill_bytes(&mut buf);
+    URL_SAFE_NO_PAD.encode(buf)
+}
+
+pub fn generate_device_code() -> String {
+    let mut buf = [0u8; 32];
+    rng().fill_bytes(&mut buf);
+    URL_SAFE_NO_PAD.encode(buf)
+}
+
+pub fn generate_auth_req_id() -> String {
+    let mut buf = [0u8; 32];
+    rng().fill_bytes(&mut buf);
+    URL_SAFE_NO_PAD.encode(buf)
+}
+
+pub fn generate_dpop_nonce() -> String {
+    let mut buf = [0u8; 16];
+    rng().fill_bytes(&mut buf);
+    URL_SAFE_NO_PAD.encode(buf)
+}
+
+pub fn generate_user_code() -> String {
+    let mut rng = rng();
+
+    (0..USER_CODE_LENGTH)
+        .map(|_| USER_CODE_ALPHABET[rng.random_range(0..USER_CODE_ALPHABET.len())] as char)
+        .collect()
+}
+
+pub fn normalize_user_code(user_code: &str) -> String {
+    user_code
+        .chars()
+        .filter(|e| e.is_ascii_alphanumeric())
+        .map(|e| e.to_ascii_uppercase())
+        .collect()
+}
+
+/// Decodes an `id_token_hint`. The hint may be expired, so only the signature and issuer are checked.
+pub fn decode_id_token_hint(token: &str, jwt_iss: &str, signing_keys: &[Arc<SigningKey>]) -> Result<IdTokenHintClaims, String> {
+    let Ok(header) = decode_header(token) else {
+        return Err(String::from("Invalid token header"));
+    };
+
+    let Some(signing_key) = signing_keys.iter().find(|e| Some(e.kid.clone()) == header.kid) else {
+        return Err(String::from("Unknown signing key"));
+    };
+
+    let Ok(decoding_key) = signing_key.decoding_key() else {
+        return Err(String::from("Invalid signing key"));
+    };
+
+    let mut validation = Validation::new(SIGNING_ALGORITHM);
+    validation.set_issuer(&[jwt_iss]);
+    validation.set_required_spec_claims(&["iss", "sub", "aud"]);
+    validation.validate_exp = false;
+    validation.validate_aud = false;
+
+    match decode::<IdTokenHintClaims>(token, &decoding_key, &validation) {
+        Ok(e) => Ok(e.claims),
+        Err(_) => Err(String::from("Invalid id_token_hint"))
+    }
+}
+
+pub fn decode_access_token(token: &str, jwt_iss: &str, signing_keys: &[Arc<SigningKey>]) -> Result<AccessToken, String> {
+    let Ok(header) = decode_header(token) else {
+        return Err(String::from("Invalid token header"));
+    };
+
+    let Some(signing_key) = signing_keys.iter().find(|e| Some(e.kid.clone()) == header.kid) else {
+        return Err(String::from("Unknown signing key"));
+    };
+
+    let Ok(decoding_key) = signing_key.decoding_key() else {
+        return Err(String::from("Invalid signing key"));
+    };
+
+    let mut validation = Validation::new(SIGNING_ALGORITHM);
+    validation.set_issuer(&[jwt_iss]);
+    validation.validate_aud = false;
+
+    match decode::<AccessToken>(token, &decoding_key, &validation) {
+        Ok(e) => Ok(e.claims),
+        Err(_) => Err(String::from("Invalid access token"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use jsonwebtoken::{decode, Validation};
+    use crate::domain::idp::IdpVerifyCredentialResponse;
+    use crate::domain::oauth_session::OauthSession;
+    use crate::dto::auth::token::id_token::IdTokenUserClaims;
+    use crate::utils::signing_key::SigningKey;
+    use super::{generate_access_token, generate_id_token, AccessTokenParams, IdTokenParams, SIGNING_ALGORITHM};
+
+    const ISSUER: &str = "https://as.example.com";
+    const CLIENT_ID: &str = "amaterasu";
+
+    fn session(auth_time: Option<chrono::NaiveDateTime>) -> OauthSession {
+        OauthSession {
+            id: Some(uuid::Uuid::new_v4()),
+            client_id: Some(CLIENT_ID.to_string()),
+            user_id: Some(uuid::Uuid::new_v4()),
+            scopes: Some(vec![String::from("openid")]),
+            redirect_uri: None,
+            state: None,
+            response_type: None,
+            response_mode: None,
+            nonce: None,
+            code_challenge: None,
+            code_challenge_method: None,
+            status: Some(OauthSession::STATUS_ACTIVE),
+            consent_granted_at: None,
+            auth_time,
+            acr: None,
+            amr: None,
+            login_id: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn id_token_claims(session: &OauthSession, code: Option<&str>, nonce: Option<&str>, now: DateTime<Utc>) -> serde_json::Value {
+        let signing_key = SigningKey::generate().unwrap();
+
+        let token = generate_id_token(IdTokenParams {
+            user: IdTokenUserClaims::default(),
+            session,
+            jwt_iss: ISSUER.to_string(),
+            access_token: "access-token",
+            code,
+            nonce: nonce.map(String::from),
+        }, now, &signing_key).unwrap();
+
+        let mut validation = Validation::new(SIGNING_ALGORITHM);
+        validation.set_issuer(&[ISSUER]);
+        validation.set_audience(&[CLIENT_ID]);
+
+        decode::<serde_json::Value>(&token, &signing_key.decoding_key().unwrap(), &validation).unwrap().claims
+    }
+
+    #[test]
+    fn binds_access_token_and_code_hashes() {
+        let claims = id_token_claims(&session(None), Some("code"), None, Utc::now());
+
+        assert_eq!(claims["at_hash"], "Pxa-1wifRlPl7yG_0oJNfw");
+        assert_eq!(claims["c_hash"], "VpTQii5T_8rgwxA-Wtb2Bw");
+
+        let claims = id_token_claims(&session(None), None, None, Utc::now());
+
+        assert_eq!(claims["at_hash"], "Pxa-1wifRlPl7yG_0oJNfw");
+        assert!(claims.get("c_hash").is_none());
+    }
+
+    #[test]
+    fn echoes_nonce_only_when_given() {
+        let claims = id_token_claims(&session(None), None, Some("n-0S6_WzA2Mj"), Utc::now());
+        assert_eq!(claims["nonce"], "n-0S6_WzA2Mj");
+
+        let claims = id_token_claims(&session(None), None, None, Utc::now());
+        assert!(claims.get("nonce").is_none());
+    }
+
+    #[test]
+    fn takes_auth_time_from_the_idp_verification() {
+        let now = Utc::now();
+        let authenticated = now.timestamp() - 600;
+
+        let verification: IdpVerifyCredentialResponse = serde_json::from_value(serde_json::json!({
+            "verified": true,
+            "authTime": authenticated,
+        })).unwrap();
+
+        let claims = id_token_claims(&session(Some(verification.authenticated_at(now))), None, None, now);
+        assert_eq!(claims["auth_time"], authenticated);
+        assert_eq!(claims["iat"], now.timestamp());
+    }
+
+    #[test]
+    fn falls_back_to_now_without_a_usable_idp_auth_time() {
+        let now = Utc::now();
+
+        let missing: IdpVerifyCredentialResponse = serde_json::from_value(serde_json::json!({ "verified": true })).unwrap();
+        assert_eq!(missing.authenticated_at(now).and_utc().timestamp(), now.timestamp());
+
+        let future: IdpVerifyCredentialResponse = serde_json::from_value(serde_json::json!({
+            "verified": true,
+            "authTime": now.timestamp() + 3600,
+        })).unwrap();
+        assert_eq!(future.authenticated_at(now).and_utc().timestamp(), now.timestamp());
+    }
+
+    #[test]
+    fn keeps_the_session_auth_time_on_access_tokens() {
+        let signing_key = SigningKey::generate().unwrap();
+        let now = Utc::now();
+        let authenticated = now.timestamp() - 3600;
+
+        let token = generate_access_token(AccessTokenParams {
+            scopes: vec![String::from("openid")],
+            jwt_iss: ISSUER.to_string(),
+            session_id: uuid::Uuid::new_v4().to_string(),
+            sub: uuid::Uuid::new_v4().to_string(),
+            client_id: CLIENT_ID.to_string(),
+            auth_time: DateTime::from_timestamp(authenticated, 0).map(|e| e.naive_utc()),
+            cnf: None,
+        }, now, &signing_key).unwrap();
+
+        let mut validation = Validation::new(SIGNING_ALGORITHM);
+        validation.set_issuer(&[ISSUER]);
+        validation.validate_aud = false;
+
+        let claims = decode::<serde_json::Value>(&token, &signing_key.decoding_key().unwrap(), &validation).unwrap().claims;
+        assert_eq!(claims["auth_time"], authenticated);
+        assert_eq!(claims["iat"], now.timestamp());
+        assert!(claims.get("aud").is_none());
+    }
+}